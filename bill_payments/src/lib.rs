@@ -3,20 +3,27 @@
 
 extern crate alloc;
 
+#[cfg(test)]
+use remitwise_common::MAX_PAGE_LIMIT;
 use remitwise_common::{
     clamp_limit, EventCategory, EventPriority, RemitwiseEvents, ARCHIVE_BUMP_AMOUNT,
-    ARCHIVE_LIFETIME_THRESHOLD, CONTRACT_VERSION, INSTANCE_BUMP_AMOUNT,
-    INSTANCE_LIFETIME_THRESHOLD, MAX_BATCH_SIZE, MAX_PAGE_LIMIT,
+    ARCHIVE_LIFETIME_THRESHOLD, CONTRACT_VERSION, MAX_BATCH_SIZE, PERSISTENT_BUMP_AMOUNT,
+    PERSISTENT_LIFETIME_THRESHOLD,
 };
-#[cfg(test)]
-use remitwise_common::MAX_PAGE_LIMIT;
 
 use alloc::vec::Vec as StdVec;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
+    Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+/// Upper bound on `frequency_days` for recurring bills (~100 years).
+pub const MAX_FREQUENCY_DAYS: u32 = 36500;
+const SECONDS_PER_DAY: u64 = 86400;
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Bill {
@@ -36,6 +43,403 @@ pub struct Bill {
     /// Intended currency/asset for this bill (e.g. "XLM", "USDC", "NGN").
     /// Defaults to "XLM" for entries created before this field was introduced.
     pub currency: String,
+    /// Address that receives the payment. `None` means the bill is settled
+    /// off-chain and paying it only records the payment.
+    pub payee: Option<Address>,
+    /// Token contract the payment is made in; set together with `payee`.
+    pub token: Option<Address>,
+    /// Amount paid so far; the bill is paid once this reaches `amount`.
+    pub amount_paid: i128,
+    /// Token actually transferred to the payee, if any.
+    pub paid_token: Option<Address>,
+    /// Registered biller this bill is payable to, if any.
+    pub biller_id: Option<u32>,
+    /// Whether `execute_due_bills` pays this bill on its due date.
+    pub auto_pay: bool,
+    /// Auto-pay attempts that failed since the bill became due.
+    pub auto_pay_failures: u32,
+    /// Who besides the owner may pay the bill.
+    pub payable_by: PayerPolicy,
+    /// Address that made the payment completing the bill.
+    pub paid_by: Option<Address>,
+    /// Grace period and late-fee terms.
+    pub late_fee: LateFeePolicy,
+    /// Late fees paid so far. Not counted in `amount_paid`.
+    pub late_fee_paid: i128,
+    /// How the next occurrence's due date is computed. `recurring` and
+    /// `frequency_days` mirror it for older clients.
+    pub recurrence: RecurrenceRule,
+    /// Position of this bill in its recurring series, starting at 1.
+    pub occurrence: u32,
+    /// Whether `amount` is fixed, an estimate or a confirmed variable amount.
+    pub amount_kind: AmountKind,
+    /// Highest amount the owner has agreed to. Auto-pay skips the bill while
+    /// a biller's update has raised `amount` above it.
+    pub approved_amount: i128,
+    /// Where the bill's dispute stands; details via `get_dispute`.
+    pub dispute: DisputeStatus,
+    /// Days before the due date of the last reminder emitted for the bill.
+    pub reminded_days: Option<u32>,
+    /// Participants' shares when the bill is split; empty otherwise. A split
+    /// bill is paid once every share is.
+    pub shares: Vec<BillShare>,
+}
+
+/// How a bill's amount is known.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AmountKind {
+    /// The same amount every occurrence.
+    Fixed,
+    /// Variable bill whose amount is an estimate until `update_bill_amount`.
+    Estimated,
+    /// Variable bill whose amount has been confirmed.
+    Actual,
+}
+
+/// An owner's unpaid total, split by whether the amounts are confirmed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpaidBreakdown {
+    /// Remaining balance of fixed and confirmed bills.
+    pub actual: i128,
+    /// Remaining balance of bills with estimated amounts.
+    pub estimated: i128,
+}
+
+/// How a recurring bill repeats.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Recurrence {
+    /// Not recurring.
+    None,
+    /// Every `n` days.
+    EveryDays(u32),
+    /// Every week on the given weekday (0 = Monday ... 6 = Sunday).
+    Weekly(u32),
+    /// Every month on the given day (1-31), clamped to the month's last day.
+    MonthlyOnDay(u32),
+    /// Every year on the due date's month and day; 29 February falls back to
+    /// 28 February in common years.
+    Yearly,
+}
+
+/// A recurrence with optional end conditions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecurrenceRule {
+    pub recurrence: Recurrence,
+    /// No occurrence is created with a due date after this time.
+    pub ends_at: Option<u64>,
+    /// Total number of bills in the series, the first one included.
+    pub max_occurrences: Option<u32>,
+}
+
+impl RecurrenceRule {
+    /// Rule for the `recurring` / `frequency_days` pair taken by `create_bill`.
+    pub fn from_frequency(recurring: bool, frequency_days: u32) -> Self {
+        RecurrenceRule {
+            recurrence: if recurring {
+                Recurrence::EveryDays(frequency_days)
+            } else {
+                Recurrence::None
+            },
+            ends_at: None,
+            max_occurrences: None,
+        }
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        self.recurrence != Recurrence::None
+    }
+
+    /// Approximate period in days, reported as `frequency_days`.
+    pub fn nominal_days(&self) -> u32 {
+        match self.recurrence {
+            Recurrence::None => 0,
+            Recurrence::EveryDays(days) => days,
+            Recurrence::Weekly(_) => 7,
+            Recurrence::MonthlyOnDay(_) => 30,
+            Recurrence::Yearly => 365,
+        }
+    }
+
+    /// Due date of the occurrence after the `occurrence`-th one, due at
+    /// `due_date`, or `None` if the series ends there. Keeps the time of day.
+    ///
+    /// # Errors
+    /// * `InvalidDueDate` - If the next due date overflows
+    pub fn next_due_date(&self, due_date: u64, occurrence: u32) -> Result<Option<u64>, Error> {
+        if self.max_occurrences.is_some_and(|max| occurrence >= max) {
+            return Ok(None);
+        }
+        let day = due_date / SECONDS_PER_DAY;
+        let time_of_day = due_date % SECONDS_PER_DAY;
+        let next_day = match self.recurrence {
+            Recurrence::None => return Ok(None),
+            Recurrence::EveryDays(days) => day + days as u64,
+            Recurrence::Weekly(weekday) => {
+                let ahead = (weekday as u64 + 7 - calendar::weekday(day)) % 7;
+                day + if ahead == 0 { 7 } else { ahead }
+            }
+            Recurrence::MonthlyOnDay(day_of_month) => {
+                let (year, month, _) = calendar::civil_from_days(day);
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                let day_of_month = day_of_month.min(calendar::days_in_month(year, month));
+                calendar::days_from_civil(year, month, day_of_month)
+            }
+            Recurrence::Yearly => {
+                let (year, month, day_of_month) = calendar::civil_from_days(day);
+                let day_of_month = day_of_month.min(calendar::days_in_month(year + 1, month));
+                calendar::days_from_civil(year + 1, month, day_of_month)
+            }
+        };
+        let next_due_date = next_day
+            .checked_mul(SECONDS_PER_DAY)
+            .and_then(|start| start.checked_add(time_of_day))
+            .ok_or(Error::InvalidDueDate)?;
+        if self.ends_at.is_some_and(|end| next_due_date > end) {
+            return Ok(None);
+        }
+        Ok(Some(next_due_date))
+    }
+
+    fn validate(&self, due_date: u64) -> Result<(), Error> {
+        let valid = match self.recurrence {
+            Recurrence::None => true,
+            Recurrence::EveryDays(days) => days > 0 && days <= MAX_FREQUENCY_DAYS,
+            Recurrence::Weekly(weekday) => weekday <= 6,
+            Recurrence::MonthlyOnDay(day) => (1..=31).contains(&day),
+            Recurrence::Yearly => true,
+        };
+        if !valid || self.max_occurrences == Some(0) {
+            return Err(Error::InvalidFrequency);
+        }
+        if self.ends_at.is_some_and(|end| end < due_date) {
+            return Err(Error::InvalidDueDate);
+        }
+        Ok(())
+    }
+}
+
+/// Whether a bill charges late fees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LateFeePolicy {
+    /// No grace period and no fees.
+    None,
+    /// Charge fees under the given terms.
+    Charge(LateFeeRule),
+}
+
+/// Late-fee terms for a bill.
+///
+/// Fees start once `grace_days` have passed after the due date. Each started
+/// period of `period_days` adds `flat_fee` plus `rate_bps` of the bill amount,
+/// up to `max_fee` in total.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateFeeRule {
+    pub grace_days: u32,
+    pub period_days: u32,
+    pub flat_fee: i128,
+    /// Percentage fee in basis points (1/100 of a percent) of `amount`.
+    pub rate_bps: u32,
+    pub max_fee: i128,
+}
+
+impl LateFeeRule {
+    /// Fee accrued by `now` on a bill of `amount` due at `due_date`.
+    pub fn accrued(&self, amount: i128, due_date: u64, now: u64) -> i128 {
+        let grace_end = due_date.saturating_add((self.grace_days as u64) * SECONDS_PER_DAY);
+        if now <= grace_end || self.period_days == 0 {
+            return 0;
+        }
+        let period = (self.period_days as u64) * SECONDS_PER_DAY;
+        let periods = (now - grace_end - 1) / period + 1;
+        let per_period = self
+            .flat_fee
+            .saturating_add(amount.saturating_mul(self.rate_bps as i128) / 10_000);
+        per_period.saturating_mul(periods as i128).min(self.max_fee)
+    }
+}
+
+/// Who may pay a bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayerPolicy {
+    /// Only the bill owner.
+    Owner,
+    /// Any address.
+    Anyone,
+    /// The owner plus the listed addresses (at most `MAX_ALLOWED_PAYERS`).
+    Allowlist(Vec<Address>),
+}
+
+impl Bill {
+    /// Amount still owed on the bill.
+    pub fn remaining(&self) -> i128 {
+        self.amount - self.amount_paid
+    }
+
+    /// Late-fee terms, if the bill charges late fees.
+    pub fn late_fee_rule(&self) -> Option<&LateFeeRule> {
+        match &self.late_fee {
+            LateFeePolicy::None => None,
+            LateFeePolicy::Charge(rule) => Some(rule),
+        }
+    }
+
+    /// End of the grace period; the bill is overdue after this time.
+    pub fn grace_ends_at(&self) -> u64 {
+        let grace_days = self.late_fee_rule().map_or(0, |rule| rule.grace_days);
+        self.due_date
+            .saturating_add((grace_days as u64) * SECONDS_PER_DAY)
+    }
+
+    /// Late fee accrued by `now` and not yet paid.
+    pub fn late_fee_due(&self, now: u64) -> i128 {
+        let accrued = self
+            .late_fee_rule()
+            .map_or(0, |rule| rule.accrued(self.amount, self.due_date, now));
+        (accrued - self.late_fee_paid).max(0)
+    }
+
+    /// Remaining balance plus unpaid late fees at `now`.
+    pub fn amount_due_now(&self, now: u64) -> i128 {
+        self.remaining().saturating_add(self.late_fee_due(now))
+    }
+}
+
+/// One payment made towards a bill.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BillPayment {
+    pub payer: Address,
+    pub amount: i128,
+    /// Token transferred to the payee, or `None` if settled off-chain.
+    pub token: Option<Address>,
+    pub paid_at: u64,
+}
+
+/// How much of a split bill one participant owes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SharePortion {
+    /// A fixed amount.
+    Amount(i128),
+    /// Basis points of the bill amount; 10_000 is all of it.
+    Bps(u32),
+}
+
+/// A participant and their portion, as given to `split_bill`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareSpec {
+    pub participant: Address,
+    pub portion: SharePortion,
+}
+
+/// One participant's share of a split bill.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillShare {
+    pub participant: Address,
+    pub amount: i128,
+    pub paid: bool,
+    pub paid_at: Option<u64>,
+}
+
+/// Where a bill's dispute stands.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    /// The bill has not been disputed.
+    None,
+    /// Waiting for the payee to accept or reject.
+    Open,
+    /// The payee rejected the dispute and escrowed the disputed amount; the
+    /// disputant may escalate it.
+    Rejected,
+    /// Waiting for the admin's ruling.
+    Escalated,
+    /// The admin ruled for the disputant of a dispute the payee never
+    /// answered, so nothing is in escrow. Closes the dispute; the payee may
+    /// still refund until the bill is disputed again.
+    Upheld,
+    /// The admin ruled for the payee. Final.
+    Dismissed,
+    /// The disputed amount was refunded to the payers. Final.
+    Refunded,
+}
+
+impl DisputeStatus {
+    /// Whether the dispute still waits for the payee or the admin. A bill
+    /// can't be disputed again until its last dispute is closed.
+    pub fn is_pending(self) -> bool {
+        matches!(self, Self::Open | Self::Rejected | Self::Escalated)
+    }
+
+    /// Whether the payee can still settle the dispute with a refund.
+    pub fn is_refundable(self) -> bool {
+        self.is_pending() || self == Self::Upheld
+    }
+}
+
+/// A dispute over a paid bill. A bill can be disputed again once its last
+/// dispute is closed; the closed dispute is kept in the bill's history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub bill_id: u32,
+    /// Owner or payer who opened the dispute.
+    pub opened_by: Address,
+    /// Address that was paid and owes the refund.
+    pub payee: Address,
+    pub token: Address,
+    /// Amount asked back.
+    pub amount: i128,
+    /// Who gets `amount` back: the bill's payers pro rata to what they
+    /// transferred, or only the disputant if they are not the owner.
+    pub refunds: Map<Address, i128>,
+    /// What earlier disputes over the bill refunded to each payer.
+    pub refunded: Map<Address, i128>,
+    /// Whether the contract holds `amount` from the payee, deposited when
+    /// they rejected the dispute.
+    pub escrowed: bool,
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub opened_at: u64,
+    /// When the status last changed.
+    pub updated_at: u64,
+    /// When the dispute was closed as `Upheld`, `Dismissed` or `Refunded`.
+    pub resolved_at: Option<u64>,
+}
+
+/// Outcome of one `execute_due_bills` run.
+#[contracttype]
+#[derive(Clone)]
+pub struct AutoPayResult {
+    /// Bills paid in this run
+    pub paid: Vec<u32>,
+    /// Bills that could not be paid; they stay unpaid and are retried
+    pub failed: Vec<u32>,
+    /// The ID to pass as `cursor` for the next run. 0 means the scan finished.
+    pub next_cursor: u32,
+}
+
+/// Outcome of one `emit_due_reminders` run.
+#[contracttype]
+#[derive(Clone)]
+pub struct ReminderResult {
+    /// Bills a reminder was emitted for in this run
+    pub reminded: Vec<u32>,
+    /// The ID to pass as `cursor` for the next run. 0 means the scan finished.
+    pub next_cursor: u32,
 }
 
 /// Paginated result for bill queries
@@ -50,6 +454,66 @@ pub struct BillPage {
     pub count: u32,
 }
 
+/// Verification state of a registered biller.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BillerStatus {
+    /// Registered but not yet checked; bills cannot reference it.
+    Pending,
+    Verified,
+    /// Temporarily blocked; existing bills are unaffected.
+    Suspended,
+}
+
+/// Characters allowed in an external reference after its prefix.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefCharset {
+    Any,
+    Numeric,
+    Alphanumeric,
+}
+
+/// Shape of the customer reference a biller expects (meter number,
+/// student ID, account number, ...).
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RefFormat {
+    /// Required leading characters, matched exactly. May be empty.
+    pub prefix: String,
+    /// Minimum total length, including the prefix.
+    pub min_len: u32,
+    /// Maximum total length, including the prefix (at most `MAX_REF_LEN`).
+    pub max_len: u32,
+    pub charset: RefCharset,
+}
+
+/// An admin-curated payee that bills can reference.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Biller {
+    pub id: u32,
+    /// Display name, e.g. "NEPA" or "Lagos State University".
+    pub name: String,
+    /// Address that receives payments for this biller's bills.
+    pub payee: Address,
+    /// Accepted currency codes mapped to the token contract used to pay in them.
+    pub currencies: Map<String, Address>,
+    pub ref_format: RefFormat,
+    pub status: BillerStatus,
+    pub created_at: u64,
+}
+
+/// Paginated result for biller queries
+#[contracttype]
+#[derive(Clone)]
+pub struct BillerPage {
+    pub items: Vec<Biller>,
+    /// 0 means no more pages
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
 pub mod pause_functions {
     use soroban_sdk::symbol_short;
     pub const CREATE_BILL: soroban_sdk::Symbol = symbol_short!("crt_bill");
@@ -57,9 +521,99 @@ pub mod pause_functions {
     pub const CANCEL_BILL: soroban_sdk::Symbol = symbol_short!("can_bill");
     pub const ARCHIVE: soroban_sdk::Symbol = symbol_short!("archive");
     pub const RESTORE: soroban_sdk::Symbol = symbol_short!("restore");
+    pub const POOL: soroban_sdk::Symbol = symbol_short!("pool");
+    pub const DISPUTE: soroban_sdk::Symbol = symbol_short!("dispute");
+}
+
+/// Gregorian calendar arithmetic on days since 1970-01-01 (UTC).
+mod calendar {
+    /// Weekday of `day`, 0 = Monday ... 6 = Sunday. 1970-01-01 was a Thursday.
+    pub fn weekday(day: u64) -> u64 {
+        (day + 3) % 7
+    }
+
+    pub fn is_leap_year(year: u64) -> bool {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    }
+
+    pub fn days_in_month(year: u64, month: u32) -> u32 {
+        match month {
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// `(year, month, day)` of `day`. Months and days start at 1.
+    pub fn civil_from_days(day: u64) -> (u64, u32, u32) {
+        // Days are shifted to a year starting 1 March 0000 so that leap days
+        // fall at the end of the year.
+        let z = day + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = era * 400 + year_of_era + u64::from(month <= 2);
+        (year, month as u32, day_of_month as u32)
+    }
+
+    /// Days since 1970-01-01 of a date on or after it.
+    pub fn days_from_civil(year: u64, month: u32, day: u32) -> u64 {
+        let year = year - u64::from(month <= 2);
+        let era = year / 400;
+        let year_of_era = year % 400;
+        let shifted_month = (month as u64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + day as u64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
 }
 
+const STORAGE_BILLERS: Symbol = symbol_short!("BILLERS");
+const STORAGE_NEXT_BILLER_ID: Symbol = symbol_short!("NEXT_BLR");
+const STORAGE_RETENTION: Symbol = symbol_short!("RETENTION");
+/// Legacy instance keys, emptied by `migrate_storage`.
+const STORAGE_BILLS: Symbol = symbol_short!("BILLS");
+const STORAGE_ARCHIVED: Symbol = symbol_short!("ARCH_BILL");
 const STORAGE_UNPAID_TOTALS: Symbol = symbol_short!("UNPD_TOT");
+const STORAGE_PAYMENTS: Symbol = symbol_short!("PAYMENTS");
+
+/// Width of the time buckets in the due-date and archive-date indexes.
+pub const INDEX_BUCKET_SECONDS: u64 = 86_400;
+
+/// Most index entries a single call visits, matching or not. A call that
+/// runs out returns a cursor to resume from.
+pub const MAX_SCAN_IDS: u32 = 200;
+
+/// Longest external reference a biller format may require.
+pub const MAX_REF_LEN: u32 = 64;
+
+/// Most addresses a bill's payer allowlist may hold.
+pub const MAX_ALLOWED_PAYERS: u32 = 10;
+
+/// Days after a bill is paid during which it can be disputed.
+pub const DISPUTE_WINDOW_DAYS: u64 = 30;
+
+/// Days the payee has to answer a dispute before the disputant may escalate.
+pub const DISPUTE_RESPONSE_DAYS: u64 = 7;
+
+/// Most reminders an owner may configure per bill.
+pub const MAX_REMINDERS: u32 = 5;
+
+/// Furthest ahead of a due date a reminder may be sent, in days.
+pub const MAX_REMINDER_DAYS: u32 = 30;
+
+/// Most participants a bill may be split between.
+pub const MAX_SHARES: u32 = 10;
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -80,6 +634,49 @@ pub enum Error {
     InvalidTag = 13,
     EmptyTags = 14,
     InvalidCurrency = 15,
+    /// The payer's token balance does not cover the bill.
+    InsufficientFunds = 16,
+    /// The token transfer to the payee failed.
+    TransferFailed = 17,
+    /// The payee cannot be the bill owner.
+    InvalidPayee = 18,
+    BillerNotFound = 19,
+    /// The biller is pending verification or suspended.
+    BillerNotVerified = 20,
+    /// The external reference does not match the biller's format.
+    InvalidExternalRef = 21,
+    /// The biller does not accept the bill's currency.
+    CurrencyNotAccepted = 22,
+    /// The biller's reference format or currency list is malformed.
+    InvalidBillerConfig = 23,
+    /// Auto-pay and third-party payers need a payee and token to settle the bill on-chain.
+    PayeeNotSet = 24,
+    /// A payment larger than the bill's remaining balance.
+    Overpayment = 25,
+    /// The payer allowlist is empty or longer than `MAX_ALLOWED_PAYERS`.
+    InvalidPayerPolicy = 26,
+    /// Late-fee terms are malformed (zero period, negative fee, rate above 100%).
+    InvalidLateFee = 27,
+    /// The bill's due date has passed, so its amount can no longer change.
+    DueDatePassed = 28,
+    /// The bill was paid more than `DISPUTE_WINDOW_DAYS` ago.
+    DisputeWindowClosed = 29,
+    /// The bill's last dispute is still pending.
+    DisputeExists = 30,
+    DisputeNotFound = 31,
+    /// The dispute is not in a state that allows this step.
+    InvalidDisputeState = 32,
+    /// Only bills paid on-chain to a payee can be disputed.
+    NotDisputable = 33,
+    /// Reminder days must be 1 to `MAX_REMINDER_DAYS`, at most `MAX_REMINDERS` of them.
+    InvalidReminderConfig = 34,
+    /// Shares are repeated, not positive or don't add up to the bill amount,
+    /// or the bill can no longer be split.
+    InvalidShares = 35,
+    /// The bill is split; participants pay it with `pay_share`.
+    BillIsSplit = 36,
+    /// The biller raised the amount and the owner has not approved it yet.
+    AmountNotApproved = 37,
 }
 
 #[contracttype]
@@ -95,6 +692,49 @@ pub struct ArchivedBill {
     pub tags: Vec<String>,
     /// Intended currency/asset carried over from the originating `Bill`.
     pub currency: String,
+    pub payee: Option<Address>,
+    /// Token transferred to the payee, if the bill was settled on-chain.
+    pub paid_token: Option<Address>,
+    pub biller_id: Option<u32>,
+    pub paid_by: Option<Address>,
+    pub late_fee_paid: i128,
+}
+
+/// A bill as stored in the legacy `BILLS` instance map, before payees and
+/// the fields added with them. Read through `upgrade_legacy_bill`.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub external_ref: Option<String>,
+    pub amount: i128,
+    pub due_date: u64,
+    pub recurring: bool,
+    pub frequency_days: u32,
+    pub paid: bool,
+    pub created_at: u64,
+    pub paid_at: Option<u64>,
+    pub schedule_id: Option<u32>,
+    pub tags: Vec<String>,
+    pub currency: String,
+}
+
+/// An archived bill as stored in the legacy `ARCH_BILL` instance map. Read
+/// through `upgrade_legacy_archived`.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyArchivedBill {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub external_ref: Option<String>,
+    pub amount: i128,
+    pub paid_at: u64,
+    pub archived_at: u64,
+    pub tags: Vec<String>,
+    pub currency: String,
 }
 
 /// Paginated result for archived bill queries
@@ -108,11 +748,21 @@ pub struct ArchivedBillPage {
 }
 
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum BillEvent {
     Created,
     Paid,
     ExternalRefUpdated,
+    AutoPayFailed,
+    DueSoon,
+}
+
+/// How long archived bills are kept before cleanup may delete them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionPolicy {
+    /// Days after archival during which a bill cannot be deleted.
+    pub min_retention_days: u32,
 }
 
 #[contracttype]
@@ -124,6 +774,55 @@ pub struct StorageStats {
     pub last_updated: u64,
 }
 
+/// Persistent storage keys. Each bill lives in its own entry and is found
+/// through ascending ID indexes, so a call only pays for the bills it touches.
+#[contracttype]
+pub enum DataKey {
+    /// An active bill, paid or not.
+    Bill(u32),
+    /// IDs of an owner's active bills, paid or not.
+    OwnerBills(Address),
+    /// IDs of an owner's unpaid bills.
+    OwnerUnpaid(Address),
+    /// IDs of unpaid bills due within this `INDEX_BUCKET_SECONDS`-wide bucket.
+    DueBills(u64),
+    /// Buckets with at least one entry in `DueBills`, ascending.
+    DueBuckets,
+    /// Principal still due on an owner's bills.
+    UnpaidTotal(Address),
+    /// Payments made towards a bill, oldest first.
+    Payments(u32),
+    /// IDs of active bills a payer has paid towards.
+    PayerBills(Address),
+    /// A bill moved out of the active set by `archive_paid_bills`.
+    ArchivedBill(u32),
+    /// IDs of an owner's archived bills.
+    OwnerArchived(Address),
+    /// IDs of bills archived within this `INDEX_BUCKET_SECONDS`-wide bucket.
+    ArchivedOn(u64),
+    /// Buckets with at least one entry in `ArchivedOn`, ascending.
+    ArchiveBuckets,
+    /// The dispute over a bill, kept after the bill is archived.
+    Dispute(u32),
+    /// A bill's earlier dispute by sequence number, oldest first.
+    PastDispute(u32, u32),
+    /// Number of `PastDispute` entries for a bill.
+    PastDisputeCount(u32),
+    /// Days before a due date at which an owner is reminded, descending.
+    ReminderDays(Address),
+    /// IDs of others' active bills in which an address has an unpaid share.
+    ShareBills(Address),
+    /// IDs of unpaid auto-pay bills due within this `INDEX_BUCKET_SECONDS`-wide
+    /// bucket.
+    AutoPayDue(u64),
+    /// Buckets with at least one entry in `AutoPayDue`, ascending.
+    AutoPayBuckets,
+    /// An owner's prepaid bills balance in a token.
+    Pool(Address, Address),
+    /// Set while an owner has auto-pay from the bills pool turned on.
+    AutoPay(Address),
+}
+
 #[contract]
 pub struct BillPayments;
 
@@ -570,2615 +1269,8863 @@ impl BillPayments {
     ) -> Result<u32, Error> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
-
-        let current_time = env.ledger().timestamp();
-        if due_date == 0 || due_date < current_time {
-            return Err(Error::InvalidDueDate);
-        }
-
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-        if recurring && (frequency_days == 0 || frequency_days > MAX_FREQUENCY_DAYS) {
-            return Err(Error::InvalidFrequency);
-        }
+        Self::validate_bill_terms(&env, amount, due_date, recurring, frequency_days)?;
 
         // Validate and normalize currency
         Self::validate_currency(&currency)?;
         let resolved_currency = Self::normalize_currency(&env, &currency);
 
-        Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let next_id = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32)
-            + 1;
-
-        let current_time = env.ledger().timestamp();
         let bill = Bill {
-            id: next_id,
-            owner: owner.clone(),
-            name: name.clone(),
+            id: 0,
+            owner,
+            name,
             external_ref,
             amount,
             due_date,
             recurring,
             frequency_days,
             paid: false,
-            created_at: current_time,
+            created_at: env.ledger().timestamp(),
             paid_at: None,
             schedule_id: None,
             tags: Vec::new(&env),
             currency: resolved_currency,
+            payee: None,
+            token: None,
+            amount_paid: 0,
+            paid_token: None,
+            biller_id: None,
+            auto_pay: false,
+            auto_pay_failures: 0,
+            payable_by: PayerPolicy::Owner,
+            paid_by: None,
+            late_fee: LateFeePolicy::None,
+            late_fee_paid: 0,
+            recurrence: RecurrenceRule::from_frequency(recurring, frequency_days),
+            occurrence: 1,
+            amount_kind: AmountKind::Fixed,
+            approved_amount: amount,
+            dispute: DisputeStatus::None,
+            reminded_days: None,
+            shares: Vec::new(&env),
         };
-
-        let bill_owner = bill.owner.clone();
-        bills.set(next_id, bill);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &next_id);
-        Self::adjust_unpaid_total(&env, &bill_owner, amount);
-
-        // Emit event for audit trail
-        RemitwiseEvents::emit(
-            &env,
-            EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("created"),
-            (next_id, bill_owner, amount, due_date),
-        );
-
-        Ok(next_id)
+        Ok(Self::store_new_bill(&env, bill))
     }
 
-    pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
-
-        Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
-
-        if bill.owner != caller {
-            return Err(Error::Unauthorized);
-        }
-        if bill.paid {
-            return Err(Error::BillAlreadyPaid);
+    /// Create a bill payable to a verified biller from the registry.
+    ///
+    /// The bill takes its name, payee and settlement token from the biller,
+    /// and `external_ref` must match the biller's reference format.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the bill owner (must authorize)
+    /// * `biller_id` - ID of a verified biller
+    /// * `amount` - Payment amount (must be positive)
+    /// * `due_date` - Due date as Unix timestamp
+    /// * `recurring` - Whether this is a recurring bill
+    /// * `frequency_days` - Frequency in days for recurring bills
+    /// * `external_ref` - Customer reference at the biller (meter number, account number, ...)
+    /// * `currency` - Currency code; must be accepted by the biller
+    ///
+    /// # Errors
+    /// * `BillerNotFound` - If the biller does not exist
+    /// * `BillerNotVerified` - If the biller is pending or suspended
+    /// * `CurrencyNotAccepted` - If the biller does not accept `currency`
+    /// * `InvalidExternalRef` - If `external_ref` does not match the biller's format
+    /// * `InvalidAmount` / `InvalidFrequency` / `InvalidDueDate` - As for `create_bill`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_bill_for_biller(
+        env: Env,
+        owner: Address,
+        biller_id: u32,
+        amount: i128,
+        due_date: u64,
+        recurring: bool,
+        frequency_days: u32,
+        external_ref: String,
+        currency: String,
+    ) -> Result<u32, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::CREATE_BILL)?;
+        Self::validate_bill_terms(&env, amount, due_date, recurring, frequency_days)?;
+
+        let biller = Self::load_billers(&env)
+            .get(biller_id)
+            .ok_or(Error::BillerNotFound)?;
+        if biller.status != BillerStatus::Verified {
+            return Err(Error::BillerNotVerified);
         }
+        Self::validate_currency(&currency)?;
+        let resolved_currency = Self::normalize_currency(&env, &currency);
+        let token = biller
+            .currencies
+            .get(resolved_currency.clone())
+            .ok_or(Error::CurrencyNotAccepted)?;
+        Self::check_external_ref(&biller.ref_format, &external_ref)?;
 
-        let current_time = env.ledger().timestamp();
-        bill.paid = true;
-        bill.paid_at = Some(current_time);
+        let bill = Bill {
+            id: 0,
+            owner,
+            name: biller.name,
+            external_ref: Some(external_ref),
+            amount,
+            due_date,
+            recurring,
+            frequency_days,
+            paid: false,
+            created_at: env.ledger().timestamp(),
+            paid_at: None,
+            schedule_id: None,
+            tags: Vec::new(&env),
+            currency: resolved_currency,
+            payee: Some(biller.payee),
+            token: Some(token),
+            amount_paid: 0,
+            paid_token: None,
+            biller_id: Some(biller_id),
+            auto_pay: false,
+            auto_pay_failures: 0,
+            payable_by: PayerPolicy::Owner,
+            paid_by: None,
+            late_fee: LateFeePolicy::None,
+            late_fee_paid: 0,
+            recurrence: RecurrenceRule::from_frequency(recurring, frequency_days),
+            occurrence: 1,
+            amount_kind: AmountKind::Fixed,
+            approved_amount: amount,
+            dispute: DisputeStatus::None,
+            reminded_days: None,
+            shares: Vec::new(&env),
+        };
+        Ok(Self::store_new_bill(&env, bill))
+    }
 
-        if bill.recurring {
-            let next_due_date = bill.due_date
-                .checked_add(
-                    (bill.frequency_days as u64)
-                        .checked_mul(SECONDS_PER_DAY)
-                        .ok_or(Error::InvalidFrequency)?
-                )
-                .ok_or(Error::InvalidDueDate)?;
-            let next_id = env
-                .storage()
-                .instance()
-                .get(&symbol_short!("NEXT_ID"))
-                .unwrap_or(0u32)
-                + 1;
-
-            let next_bill = Bill {
-                id: next_id,
-                owner: bill.owner.clone(),
-                name: bill.name.clone(),
-                external_ref: bill.external_ref.clone(),
-                amount: bill.amount,
-                due_date: next_due_date,
-                recurring: true,
-                frequency_days: bill.frequency_days,
-                paid: false,
-                created_at: current_time,
-                paid_at: None,
-                schedule_id: bill.schedule_id,
-                tags: bill.tags.clone(),
-                currency: bill.currency.clone(),
-            };
-            bills.set(next_id, next_bill);
-            env.storage()
-                .instance()
-                .set(&symbol_short!("NEXT_ID"), &next_id);
+    fn validate_bill_terms(
+        env: &Env,
+        amount: i128,
+        due_date: u64,
+        recurring: bool,
+        frequency_days: u32,
+    ) -> Result<(), Error> {
+        let current_time = env.ledger().timestamp();
+        if due_date == 0 || due_date < current_time {
+            return Err(Error::InvalidDueDate);
         }
-
-        let paid_amount = bill.amount;
-        let was_recurring = bill.recurring;
-        bills.set(bill_id, bill);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if !was_recurring {
-            Self::adjust_unpaid_total(&env, &caller, -paid_amount);
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if recurring && (frequency_days == 0 || frequency_days > MAX_FREQUENCY_DAYS) {
+            return Err(Error::InvalidFrequency);
         }
+        Ok(())
+    }
+
+    /// Assign the next bill ID, store the bill and emit `created`.
+    fn store_new_bill(env: &Env, mut bill: Bill) -> u32 {
+        Self::extend_instance_ttl(env);
+        let next_id = Self::allocate_bill_id(env);
+        bill.id = next_id;
+        Self::insert_bill(env, &bill);
+        Self::adjust_unpaid_total(env, &bill.owner, bill.amount);
+        Self::adjust_storage_stats(env, 1, 0, 0, 0);
 
         // Emit event for audit trail
         RemitwiseEvents::emit(
-            &env,
-            EventCategory::Transaction,
-            EventPriority::High,
-            symbol_short!("paid"),
-            (bill_id, caller, paid_amount),
+            env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("created"),
+            (next_id, bill.owner, bill.amount, bill.due_date),
         );
 
-        Ok(())
+        next_id
     }
 
-    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
-        let bills: Map<u32, Bill> = env
+    fn allocate_bill_id(env: &Env) -> u32 {
+        let next_id = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        bills.get(bill_id)
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0u32)
+            .saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_ID"), &next_id);
+        next_id
     }
 
-    // -----------------------------------------------------------------------
-    // PAGINATED LIST QUERIES
-    // -----------------------------------------------------------------------
-
-    /// Get a page of unpaid bills for `owner`.
+    /// Pay a bill.
     ///
-    /// # Arguments
-    /// * `owner`  – whose bills to return
-    /// * `cursor` – start after this bill ID (pass 0 for the first page)
-    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// Pays whatever remains of `amount` after earlier partial payments, plus
+    /// any late fee accrued under the bill's `late_fee` terms. If the bill has
+    /// a payee, that sum is transferred in the bill's token from the caller to
+    /// the payee; otherwise the bill is only marked paid.
     ///
-    /// # Returns
-    /// `BillPage { items, next_cursor, count }`.
-    /// When `next_cursor == 0` there are no more pages.
-    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
-
-        Self::build_page(&env, staging, limit)
-    }
-
-    /// Get a page of ALL bills (paid + unpaid) for `owner`.
+    /// The caller may be the owner or, per the bill's `payable_by` policy,
+    /// someone paying on the owner's behalf. Only the owner may mark a bill
+    /// without a payee paid, and only the owner's own payments draw on the
+    /// owner's bills pool.
     ///
-    /// Same cursor/limit semantics as `get_unpaid_bills`.
-    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
-        owner.require_auth();
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller may not pay the bill
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If the bill is split between participants
+    /// * `PayeeNotSet` - If someone other than the owner pays a bill without a payee
+    /// * `AmountNotApproved` - If someone other than the owner pays an
+    ///   amount the biller raised and the owner has not approved
+    /// * `InsufficientFunds` - If the caller's token balance does not cover the bill
+    /// * `TransferFailed` - If the token transfer to the payee fails
+    ///
+    /// # Events
+    /// - `paid` with `(bill_id, owner, amount, payer)`
+    /// - `settled` with `(bill_id, payee, token, amount)` when funds were transferred
+    pub fn pay_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+
+        if !Self::can_pay(&bill, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
         }
 
-        Self::build_page(&env, staging, limit)
-    }
+        let paid_amount = bill.amount_due_now(env.ledger().timestamp());
+        let paid_token = Self::settle_payment(&env, &caller, &bill, paid_amount)?;
+        let payment = BillPayment {
+            payer: caller.clone(),
+            amount: paid_amount,
+            token: paid_token,
+            paid_at: env.ledger().timestamp(),
+        };
+        let unpaid_delta = Self::apply_payment(&env, &mut bill, payment)?;
 
-    /// Get a page of overdue (unpaid + past due_date) bills across all owners.
-    ///
-    /// Same cursor/limit semantics.
-    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
-        let limit = clamp_limit(limit);
-        let current_time = env.ledger().timestamp();
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &bill.owner, unpaid_delta);
 
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.paid || bill.due_date >= current_time {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
+        // Emit event for audit trail
+        Self::emit_paid(&env, bill_id, &bill.owner, paid_amount, &caller);
 
-        Self::build_page(&env, staging, limit)
+        Ok(())
     }
 
-    /// Admin-only: get ALL bills (any owner), paginated.
-    pub fn get_all_bills(
+    /// Pay part of a bill.
+    ///
+    /// Settles `amount` the same way as `pay_bill` and adds it to the bill's
+    /// `amount_paid` and payment history. The bill becomes paid when its
+    /// remaining balance reaches zero. Anyone allowed to pay the bill may
+    /// contribute. Payments cover accrued late fees first.
+    ///
+    /// # Returns
+    /// Amount still due after this payment, late fees included
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is zero or negative
+    /// * `Overpayment` - If amount exceeds the amount due now
+    /// * Otherwise as for `pay_bill`
+    pub fn pay_bill_partial(
         env: Env,
         caller: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> Result<BillPage, Error> {
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<i128, Error> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
-        if admin != caller {
-            return Err(Error::Unauthorized);
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        let limit = clamp_limit(limit);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if !Self::can_pay(&bill, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
+        }
+        let now = env.ledger().timestamp();
+        if amount > bill.amount_due_now(now) {
+            return Err(Error::Overpayment);
         }
 
-        Ok(Self::build_page(&env, staging, limit))
-    }
-
-    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
-    /// `next_cursor` is set to the last *returned* item's ID so the next call's
-    /// `id <= cursor` filter correctly skips past it.
-    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
-        let n = staging.len();
-        let has_next = n > limit;
-        let mut items = Vec::new(env);
-        let mut next_cursor: u32 = 0;
+        let paid_token = Self::settle_payment(&env, &caller, &bill, amount)?;
+        let payment = BillPayment {
+            payer: caller.clone(),
+            amount,
+            token: paid_token,
+            paid_at: now,
+        };
+        let unpaid_delta = Self::apply_payment(&env, &mut bill, payment)?;
 
-        // Emit all items, or all-but-last if there is a next page
-        let take = if has_next { n - 1 } else { n };
+        let remaining = bill.amount_due_now(now);
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &bill.owner, unpaid_delta);
 
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("part_paid"),
+            (bill_id, caller.clone(), amount, remaining),
+        );
+        if bill.paid {
+            Self::emit_paid(&env, bill_id, &bill.owner, amount, &caller);
         }
 
-        // next_cursor = last returned item's ID (NOT the first skipped item)
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
-            }
-        }
+        Ok(remaining)
+    }
 
-        let count = items.len();
-        BillPage {
-            items,
-            next_cursor,
-            count,
+    /// Get a page of active bills that `payer` has paid towards.
+    ///
+    /// Includes bills paid in part and bills `payer` paid on someone else's
+    /// behalf. Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_bills_paid_by(env: Env, payer: Address, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        let mut ids = Self::load_list(&env, &DataKey::PayerBills(payer.clone()));
+        // Payments made before the storage migration are not indexed yet.
+        if let Some(legacy) = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, Vec<BillPayment>>>(&STORAGE_PAYMENTS)
+        {
+            for (id, payments) in legacy.iter() {
+                if payments.iter().any(|payment| payment.payer == payer) {
+                    Self::insert_sorted(&mut ids, id);
+                }
+            }
         }
+        Self::page_from_ids(&env, &ids, cursor, limit, |_| true)
     }
 
-    /// Set or clear an external reference ID for a bill
-    ///
-    /// # Arguments
-    /// * `caller` - Address of the caller (must be the bill owner)
-    /// * `bill_id` - ID of the bill to update
-    /// * `external_ref` - Optional external system reference ID
+    /// Replace the recurrence rule of an unpaid bill.
     ///
-    /// # Returns
-    /// Ok(()) if update was successful
+    /// Calendar rules keep bills on a fixed day, e.g. `MonthlyOnDay(1)` for
+    /// rent due on the 1st, where `frequency_days` would drift. The series
+    /// stops after `max_occurrences` bills or before a due date past
+    /// `ends_at`. `Recurrence::None` makes the bill one-off.
     ///
     /// # Errors
     /// * `BillNotFound` - If bill with given ID doesn't exist
     /// * `Unauthorized` - If caller is not the bill owner
-    pub fn set_external_ref(
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `InvalidFrequency` - If the rule is malformed or `max_occurrences` is 0
+    /// * `InvalidDueDate` - If `ends_at` is before the bill's due date
+    pub fn set_bill_recurrence(
         env: Env,
         caller: Address,
         bill_id: u32,
-        external_ref: Option<String>,
+        rule: RecurrenceRule,
     ) -> Result<(), Error> {
         caller.require_auth();
 
         Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
         if bill.owner != caller {
             return Err(Error::Unauthorized);
         }
-
-        bill.external_ref = external_ref.clone();
-        bills.set(bill_id, bill);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        rule.validate(bill.due_date)?;
+
+        bill.recurring = rule.is_recurring();
+        bill.frequency_days = rule.nominal_days();
+        bill.recurrence = rule.clone();
+        Self::save_bill(&env, &bill);
 
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("ext_ref"),
-            (bill_id, caller, external_ref),
+            EventPriority::Low,
+            symbol_short!("recur"),
+            (bill_id, rule),
         );
-
         Ok(())
     }
 
-
-    // -----------------------------------------------------------------------
-    // Backward-compat helpers
-    // -----------------------------------------------------------------------
-
-    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
-    /// Only safe for owners with a small number of bills. Prefer the
-    /// paginated `get_unpaid_bills` for production use.
-    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut result = Vec::new(&env);
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                result.push_back(bill);
-            }
-        }
-        result
-    }
-
-    // -----------------------------------------------------------------------
-    // Archived bill queries (paginated)
-    // -----------------------------------------------------------------------
-
-    /// Get a page of archived bills for `owner`.
-    pub fn get_archived_bills(
+    /// Mark an unpaid bill as variable-amount, or back to fixed.
+    ///
+    /// Each later occurrence of a variable recurring bill is created with the
+    /// previous amount as an estimate, to be confirmed with
+    /// `update_bill_amount`. The bill's current amount counts as actual.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    pub fn set_variable_amount(
         env: Env,
-        owner: Address,
-        cursor: u32,
-        limit: u32,
-    ) -> ArchivedBillPage {
-        let limit = clamp_limit(limit);
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
-        for (id, bill) in archived.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
-            }
-        }
-
-        let has_next = staging.len() > limit;
-        let mut items = Vec::new(&env);
-        let mut next_cursor: u32 = 0;
-        let take = if has_next {
-            staging.len() - 1
-        } else {
-            staging.len()
-        };
-
-        for i in 0..take {
-            if let Some((_, bill)) = staging.get(i) {
-                items.push_back(bill);
-            }
-        }
-        if has_next {
-            if let Some((id, _)) = staging.get(take - 1) {
-                next_cursor = id;
-            }
-        }
-
-        let count = items.len();
-        ArchivedBillPage {
-            items,
-            next_cursor,
-            count,
-        }
-    }
-
-    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        archived.get(bill_id)
-    }
-
-    // -----------------------------------------------------------------------
-    // Remaining operations
-    // -----------------------------------------------------------------------
-
-    pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller: Address,
+        bill_id: u32,
+        variable: bool,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let bill = bills.get(bill_id).ok_or(Error::BillNotFound)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
         if bill.owner != caller {
             return Err(Error::Unauthorized);
         }
-        let removed_unpaid_amount = if bill.paid { 0 } else { bill.amount };
-        bills.remove(bill_id);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        if removed_unpaid_amount > 0 {
-            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
         }
+
+        bill.amount_kind = match (variable, bill.amount_kind) {
+            (false, _) => AmountKind::Fixed,
+            (true, AmountKind::Fixed) => AmountKind::Actual,
+            (true, kind) => kind,
+        };
+        Self::save_bill(&env, &bill);
+
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
-            EventPriority::Medium,
-            symbol_short!("canceled"),
-            bill_id,
+            EventPriority::Low,
+            symbol_short!("var_amt"),
+            (bill_id, variable),
         );
         Ok(())
     }
 
-    pub fn archive_paid_bills(
+    /// Set the actual amount of an unpaid bill before its due date.
+    ///
+    /// Callable by the bill owner or, for variable bills linked to a verified
+    /// biller, by the biller's payee address. An estimated amount becomes
+    /// actual. An amount set by the owner counts as approved; a biller's
+    /// increase must be approved with `approve_bill_amount` before auto-pay
+    /// or anyone but the owner pays it.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is neither the owner nor the bill's
+    ///   biller, or the biller updates a fixed-amount bill
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If the bill is split; its shares fix the amount
+    /// * `DueDatePassed` - If the due date has passed
+    /// * `InvalidAmount` - If amount is not above what has already been paid
+    pub fn update_bill_amount(
         env: Env,
         caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
+        bill_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
 
-        let current_time = env.ledger().timestamp();
-        let mut archived_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
-
-        for (id, bill) in bills.iter() {
-            if let Some(paid_at) = bill.paid_at {
-                if bill.paid && paid_at < before_timestamp {
-                    let archived_bill = ArchivedBill {
-                        id: bill.id,
-                        owner: bill.owner.clone(),
-                        name: bill.name.clone(),
-                        external_ref: bill.external_ref.clone(),
-                        amount: bill.amount,
-                        paid_at,
-                        archived_at: current_time,
-                        tags: bill.tags.clone(),
-                        currency: bill.currency.clone(),
-                    };
-                    archived.set(id, archived_bill);
-                    to_remove.push_back(id);
-                    archived_count += 1;
-                }
-            }
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        let is_biller = bill
+            .biller_id
+            .and_then(|biller_id| Self::load_billers(&env).get(biller_id))
+            .is_some_and(|biller| {
+                biller.payee == caller && biller.status == BillerStatus::Verified
+            });
+        if bill.owner != caller && (!is_biller || bill.amount_kind == AmountKind::Fixed) {
+            return Err(Error::Unauthorized);
         }
-
-        for id in to_remove.iter() {
-            bills.remove(id);
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
+        }
+        if env.ledger().timestamp() > bill.due_date {
+            return Err(Error::DueDatePassed);
+        }
+        if amount <= 0 || amount <= bill.amount_paid {
+            return Err(Error::InvalidAmount);
         }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-
-        Self::extend_archive_ttl(&env);
-        Self::update_storage_stats(&env);
+        let old_amount = bill.amount;
+        let owner = bill.owner.clone();
+        bill.amount = amount;
+        if bill.amount_kind == AmountKind::Estimated {
+            bill.amount_kind = AmountKind::Actual;
+        }
+        if caller == owner {
+            bill.approved_amount = amount;
+        }
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &owner, amount - old_amount);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("archived"),
-            archived_count,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("amt_upd"),
+            (bill_id, old_amount, amount, caller),
         );
-
-        Ok(archived_count)
+        Ok(())
     }
 
-    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::RESTORE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let archived_bill = archived.get(bill_id).ok_or(Error::BillNotFound)?;
+    /// Approve a bill's current amount, e.g. after its biller raised it, so
+    /// auto-pay pays it again.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    pub fn approve_bill_amount(env: Env, owner: Address, bill_id: u32) -> Result<(), Error> {
+        owner.require_auth();
 
-        if archived_bill.owner != caller {
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != owner {
             return Err(Error::Unauthorized);
         }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
 
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let restored_bill = Bill {
-            id: archived_bill.id,
-            owner: archived_bill.owner.clone(),
-            name: archived_bill.name.clone(),
-            external_ref: archived_bill.external_ref.clone(),
-            amount: archived_bill.amount,
-            external_ref: None,
-            due_date: env.ledger().timestamp() + 2592000,
-            recurring: false,
-            frequency_days: 0,
-            paid: true,
-            created_at: archived_bill.paid_at,
-            paid_at: Some(archived_bill.paid_at),
-            schedule_id: None,
-            tags: archived_bill.tags.clone(),
-            currency: archived_bill.currency.clone(),
-        };
-
-        bills.set(bill_id, restored_bill);
-        archived.remove(bill_id);
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("BILLS"), &bills);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-
-        Self::update_storage_stats(&env);
+        bill.approved_amount = bill.amount;
+        Self::save_bill(&env, &bill);
 
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
             EventPriority::Medium,
-            symbol_short!("restored"),
-            bill_id,
+            symbol_short!("amt_appr"),
+            (bill_id, bill.amount),
         );
         Ok(())
     }
 
-    pub fn bulk_cleanup_bills(
+    /// Set or clear the grace period and late-fee terms of an unpaid bill.
+    ///
+    /// Fees accrue from the time the grace period ends and are charged by
+    /// `pay_bill` on top of the remaining balance. Recurring bills pass the
+    /// terms on to their next occurrence.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If the bill is split between participants
+    /// * `InvalidLateFee` - If the terms are malformed
+    pub fn set_late_fee(
         env: Env,
         caller: Address,
-        before_timestamp: u64,
-    ) -> Result<u32, Error> {
+        bill_id: u32,
+        policy: LateFeePolicy,
+    ) -> Result<(), Error> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
-        Self::extend_instance_ttl(&env);
-
-        let mut archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut deleted_count = 0u32;
-        let mut to_remove: Vec<u32> = Vec::new(&env);
-
-        for (id, bill) in archived.iter() {
-            if bill.archived_at < before_timestamp {
-                to_remove.push_back(id);
-                deleted_count += 1;
-            }
+        if let LateFeePolicy::Charge(rule) = &policy {
+            Self::validate_late_fee(rule)?;
         }
 
-        for id in to_remove.iter() {
-            archived.remove(id);
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
         }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("ARCH_BILL"), &archived);
-        Self::update_storage_stats(&env);
+        bill.late_fee = policy.clone();
+        Self::save_bill(&env, &bill);
 
-        RemitwiseEvents::emit_batch(
+        RemitwiseEvents::emit(
             &env,
-            EventCategory::System,
-            symbol_short!("cleaned"),
-            deleted_count,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("late_fee"),
+            (bill_id, policy),
         );
-        Ok(deleted_count)
+        Ok(())
     }
 
-    /// Pay multiple bills in a single batch.
-    ///
-    /// # Semantics: Partial Success
-    /// This function implements deterministic partial result reporting. If a bill in the batch
-    /// is invalid (e.g., not found, unauthorized, or already paid), it will be skipped,
-    /// and an error event will be emitted. Other valid bills in the same batch will still be processed.
-    ///
-    /// # Arguments
-    /// * `env` - The Soroban environment
-    /// * `caller` - Address of the bill owner (must authorize)
-    /// * `bill_ids` - Vector of bill IDs to pay
-    ///
-    /// # Returns
-    /// The number of successfully paid bills.
-    ///
-    /// # Events
-    /// - `paid`: Emitted for each successful payment.
-    /// - `bill_pay_failed`: Emitted for each failed payment with (bill_id, error_code).
-    /// - `batch_pay_summary`: Emitted at the end with (success_count, failure_count).
-    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
-        caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
-
-        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap_or(u32::MAX) {
-            return Err(Error::BatchTooLarge);
-        }
-
-        Self::extend_instance_ttl(&env);
-        let mut bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let current_time = env.ledger().timestamp();
-        let mut next_id: u32 = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NEXT_ID"))
-            .unwrap_or(0u32);
-
-        let mut paid_count = 0u32;
-        let mut failed_count = 0u32;
-        let mut unpaid_delta = 0i128;
-
-        for id in bill_ids.iter() {
-            let bill_result = bills.get(id);
-
-            // Validation logic for each bill
-            let mut bill = match bill_result {
-                Some(b) => b,
-                None => {
-                    failed_count += 1;
-                    RemitwiseEvents::emit(
-                        &env,
-                        EventCategory::Transaction,
-                        EventPriority::Medium,
-                        symbol_short!("f_pay_id"), // fail_pay_id
-                        (id, Error::BillNotFound as u32),
-                    );
-                    continue;
-                }
-            };
-
-            if bill.owner != caller {
-                failed_count += 1;
-                RemitwiseEvents::emit(
-                    &env,
-                    EventCategory::Transaction,
-                    EventPriority::Medium,
-                    symbol_short!("fpay_auth"), // fail_pay_auth
-                    (id, Error::Unauthorized as u32),
-                );
-                continue;
-            }
-
-            if bill.paid {
-                failed_count += 1;
-                RemitwiseEvents::emit(
-                    &env,
-                    EventCategory::Transaction,
-                    EventPriority::Medium,
-                    symbol_short!("f_pay_pd"), // fail_pay_paid
-                    (id, Error::BillAlreadyPaid as u32),
-                );
-                continue;
-            }
-
-            // Process payment
-            let amount = bill.amount;
-            bill.paid = true;
-            bill.paid_at = Some(current_time);
-
-            if bill.recurring {
-                next_id = next_id.saturating_add(1);
-                let next_due_date = bill.due_date
-                    .checked_add(
-                        (bill.frequency_days as u64)
-                            .checked_mul(SECONDS_PER_DAY)
-                            .ok_or(Error::InvalidFrequency)?
-                    )
-                    .ok_or(Error::InvalidDueDate)?;
-                let next_bill = Bill {
-                    id: next_id,
-                    owner: bill.owner.clone(),
-                    name: bill.name.clone(),
-                    external_ref: bill.external_ref.clone(),
-                    amount: bill.amount,
-                    due_date: next_due_date,
-                    recurring: true,
-                    frequency_days: bill.frequency_days,
-                    paid: false,
-                    created_at: current_time,
-                    paid_at: None,
-                    schedule_id: bill.schedule_id,
-                    tags: bill.tags.clone(),
-                    currency: bill.currency.clone(),
-                };
-                bills.set(next_id, next_bill);
-            } else {
-                unpaid_delta = unpaid_delta.saturating_sub(amount);
-            }
-
-            bills.set(id, bill);
-            paid_count += 1;
-
-            RemitwiseEvents::emit(
-                &env,
-                EventCategory::Transaction,
-                EventPriority::High,
-                symbol_short!("paid"),
-                (id, caller.clone(), amount),
-            );
-        }
-
-        // Final storage updates
-        if paid_count > 0 || failed_count > 0 {
-            env.storage()
-                .instance()
-                .set(&symbol_short!("NEXT_ID"), &next_id);
-            env.storage()
-                .instance()
-                .set(&symbol_short!("BILLS"), &bills);
-
-            if unpaid_delta != 0 {
-                Self::adjust_unpaid_total(&env, &caller, unpaid_delta);
-            }
-            Self::update_storage_stats(&env);
+    /// Amount `pay_bill` would charge for a bill right now: the remaining
+    /// balance plus unpaid late fees. 0 for paid bills.
+    pub fn get_amount_due_now(env: Env, bill_id: u32) -> Result<i128, Error> {
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.paid {
+            return Ok(0);
         }
-
-        RemitwiseEvents::emit(
-            &env,
-            EventCategory::System,
-            EventPriority::Medium,
-            symbol_short!("batch_res"), // batch_result
-            (paid_count, failed_count),
-        );
-
-        Ok(paid_count)
+        Ok(bill.amount_due_now(env.ledger().timestamp()))
     }
 
-    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_unpaid_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
+    fn validate_late_fee(rule: &LateFeeRule) -> Result<(), Error> {
+        let charges_fee = rule.flat_fee > 0 || rule.rate_bps > 0;
+        if rule.period_days == 0
+            || rule.period_days > MAX_FREQUENCY_DAYS
+            || rule.grace_days > MAX_FREQUENCY_DAYS
+            || rule.flat_fee < 0
+            || rule.rate_bps > 10_000
+            || rule.max_fee <= 0
+            || !charges_fee
+        {
+            return Err(Error::InvalidLateFee);
         }
+        Ok(())
+    }
 
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner {
-                total += bill.amount;
-            }
-        }
-        total
+    /// Get the payments made towards a bill, oldest first.
+    pub fn get_bill_payments(env: Env, bill_id: u32) -> Vec<BillPayment> {
+        Self::load_payments(&env, bill_id)
     }
 
-    pub fn get_storage_stats(env: Env) -> StorageStats {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("STOR_STAT"))
-            .unwrap_or(StorageStats {
-                active_bills: 0,
-                archived_bills: 0,
-                total_unpaid_amount: 0,
-                total_archived_amount: 0,
-                last_updated: 0,
-            })
+    pub fn get_bill(env: Env, bill_id: u32) -> Option<Bill> {
+        Self::load_bill(&env, bill_id)
     }
 
     // -----------------------------------------------------------------------
-    // Currency-filter helper queries
+    // PAGINATED LIST QUERIES
     // -----------------------------------------------------------------------
 
-    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
+    /// Get a page of unpaid bills for `owner`.
     ///
     /// # Arguments
-    /// * `owner`    – Address of the bill owner
-    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
-    /// * `cursor`   – Start after this bill ID (pass 0 for the first page)
-    /// * `limit`    – Max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    /// * `owner`  – whose bills to return
+    /// * `cursor` – start after this bill ID (pass 0 for the first page)
+    /// * `limit`  – max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
     ///
     /// # Returns
-    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
-    ///
-    /// # Currency Comparison
-    /// Currency comparison is case-insensitive and whitespace-insensitive:
-    /// - "usdc", "USDC", "UsDc", " usdc " all match
-    /// - Empty currency defaults to "XLM" for comparison
+    /// `BillPage { items, next_cursor, count }`.
+    /// When `next_cursor == 0` there are no more pages.
+    pub fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        let ids = Self::owner_bill_ids(&env, &owner, true);
+        Self::page_from_ids(&env, &ids, cursor, limit, |bill| !bill.paid)
+    }
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner`.
     ///
-    /// # Examples
-    /// ```rust,ignore
-    /// // Get all USDC bills for owner
-    /// let page = client.get_bills_by_currency(&owner, &"USDC".into(), &0, &10);
-    /// ```
-    pub fn get_bills_by_currency(
-        env: Env,
-        owner: Address,
-        currency: String,
-        cursor: u32,
-        limit: u32,
-    ) -> BillPage {
+    /// Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage {
+        owner.require_auth();
         let limit = clamp_limit(limit);
-        let normalized_currency = Self::normalize_currency(&env, &currency);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let ids = Self::owner_bill_ids(&env, &owner, false);
+        Self::page_from_ids(&env, &ids, cursor, limit, |_| true)
+    }
 
+    /// Get a page of overdue bills across all owners: unpaid and past the due
+    /// date plus any grace period in the bill's `late_fee` terms.
+    ///
+    /// Bills come earliest due day first, by ID within a day. Pass the
+    /// previous page's `next_cursor` to continue; `next_cursor == 0` means no
+    /// more pages. A page visits at most `MAX_SCAN_IDS` index entries, so it
+    /// may hold fewer than `limit` bills and still have a `next_cursor`.
+    pub fn get_overdue_bills(env: Env, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        let current_time = env.ledger().timestamp();
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.currency != normalized_currency {
-                continue;
-            }
-            staging.push_back((id, bill));
-            if staging.len() > limit {
-                break;
+        let stopped_at = Self::walk_due_index(
+            &env,
+            &DataKey::DueBuckets,
+            DataKey::DueBills,
+            Self::legacy_bills(&env),
+            0,
+            current_time,
+            cursor,
+            |bill| {
+                if !bill.paid && bill.grace_ends_at() < current_time {
+                    staging.push_back((bill.id, bill));
+                }
+                staging.len() <= limit
+            },
+        );
+        let mut page = Self::build_page(&env, staging, limit);
+        if let Some(last) = stopped_at {
+            if page.next_cursor == 0 {
+                page.next_cursor = last;
             }
         }
-
-        Self::build_page(&env, staging, limit)
+        page
     }
 
-    /// Get a page of **unpaid** bills for `owner` that match `currency`.
-    ///
-    /// # Arguments
-    /// * `owner`    – Address of the bill owner
-    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
-    /// * `cursor`   – Start after this bill ID (pass 0 for the first page)
-    /// * `limit`    – Max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
-    ///
-    /// # Returns
-    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
-    ///
-    /// # Currency Comparison
-    /// Currency comparison is case-insensitive and whitespace-insensitive:
-    /// - "usdc", "USDC", "UsDc", " usdc " all match
-    /// - Empty currency defaults to "XLM" for comparison
+    /// Get a page of `owner`'s unpaid bills due between `from` and `to`,
+    /// inclusive.
     ///
-    /// # Examples
-    /// ```rust,ignore
-    /// // Get unpaid USDC bills for owner
-    /// let page = client.get_unpaid_bills_by_currency(&owner, &"USDC".into(), &0, &10);
-    /// ```
-    pub fn get_unpaid_bills_by_currency(
+    /// Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_bills_due_between(
         env: Env,
         owner: Address,
-        currency: String,
+        from: u64,
+        to: u64,
         cursor: u32,
         limit: u32,
     ) -> BillPage {
         let limit = clamp_limit(limit);
-        let normalized_currency = Self::normalize_currency(&env, &currency);
-        let bills: Map<u32, Bill> = env
+        let ids = Self::owner_bill_ids(&env, &owner, true);
+        Self::page_from_ids(&env, &ids, cursor, limit, |bill| {
+            !bill.paid && bill.due_date >= from && bill.due_date <= to
+        })
+    }
+
+    /// Admin-only: get ALL bills (any owner), paginated.
+    ///
+    /// A page probes at most `MAX_SCAN_IDS` bill IDs, so it may hold fewer
+    /// than `limit` bills and still have a `next_cursor`.
+    pub fn get_all_bills(
+        env: Env,
+        caller: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<BillPage, Error> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(Error::Unauthorized)?;
+        if admin != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let limit = clamp_limit(limit);
+        // There is no index of all bills: probe the IDs after `cursor`,
+        // skipping those of archived and cancelled bills, for at most
+        // `MAX_SCAN_IDS` IDs per page.
+        let last_id: u32 = env
             .storage()
             .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-
+            .get(&symbol_short!("NEXT_ID"))
+            .unwrap_or(0);
+        let first = cursor.saturating_add(1);
+        let scan_end = first.saturating_add(MAX_SCAN_IDS - 1).min(last_id);
         let mut staging: Vec<(u32, Bill)> = Vec::new(&env);
-        for (id, bill) in bills.iter() {
-            if id <= cursor {
-                continue;
-            }
-            if bill.owner != owner || bill.paid || bill.currency != normalized_currency {
+        for id in first..=scan_end {
+            let Some(bill) = Self::load_bill(&env, id) else {
                 continue;
-            }
+            };
             staging.push_back((id, bill));
             if staging.len() > limit {
                 break;
             }
         }
 
-        Self::build_page(&env, staging, limit)
+        let mut page = Self::build_page(&env, staging, limit);
+        if page.next_cursor == 0 && scan_end < last_id {
+            page.next_cursor = scan_end;
+        }
+        Ok(page)
     }
 
-    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
-    ///
-    /// # Arguments
-    /// * `owner`    – Address of the bill owner
-    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
-    ///
-    /// # Returns
-    /// Total unpaid amount in the specified currency
-    ///
-    /// # Currency Comparison
-    /// Currency comparison is case-insensitive and whitespace-insensitive:
-    /// - "usdc", "USDC", "UsDc", " usdc " all match
-    /// - Empty currency defaults to "XLM" for comparison
+    /// Build a `BillPage` from a staging buffer of up to `limit+1` matching items.
+    /// `next_cursor` is set to the last *returned* item's ID so the next call's
+    /// `id <= cursor` filter correctly skips past it.
+    fn build_page(env: &Env, staging: Vec<(u32, Bill)>, limit: u32) -> BillPage {
+        let n = staging.len();
+        let has_next = n > limit;
+        let mut items = Vec::new(env);
+        let mut next_cursor: u32 = 0;
+
+        // Emit all items, or all-but-last if there is a next page
+        let take = if has_next { n - 1 } else { n };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+
+        // next_cursor = last returned item's ID (NOT the first skipped item)
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        BillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    /// Set or clear an external reference ID for a bill
     ///
-    /// # Examples
-    /// ```rust,ignore
-    /// // Get total unpaid amount in USDC
-    /// let total_usdc = client.get_total_unpaid_by_currency(&owner, &"USDC".into());
-    /// // Get total unpaid amount in XLM
-    /// let total_xlm = client.get_total_unpaid_by_currency(&owner, &"XLM".into());
-    /// ```
-    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
-        let normalized_currency = Self::normalize_currency(&env, &currency);
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(&env));
-        let mut total = 0i128;
-        for (_, bill) in bills.iter() {
-            if !bill.paid && bill.owner == owner && bill.currency == normalized_currency {
-                total += bill.amount;
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the bill owner)
+    /// * `bill_id` - ID of the bill to update
+    /// * `external_ref` - Optional external system reference ID
+    ///
+    /// # Returns
+    /// Ok(()) if update was successful
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `InvalidExternalRef` - If the bill is linked to a biller and the
+    ///   reference is cleared or does not match the biller's format
+    pub fn set_external_ref(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        external_ref: Option<String>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if let Some(biller_id) = bill.biller_id {
+            // Biller bills always carry a reference in the biller's format.
+            let external_ref = external_ref.as_ref().ok_or(Error::InvalidExternalRef)?;
+            let biller = Self::load_billers(&env)
+                .get(biller_id)
+                .ok_or(Error::BillerNotFound)?;
+            Self::check_external_ref(&biller.ref_format, external_ref)?;
+        }
+
+        bill.external_ref = external_ref.clone();
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("ext_ref"),
+            (bill_id, caller, external_ref),
+        );
+
+        Ok(())
+    }
+
+    /// Choose who may pay an unpaid bill on the owner's behalf.
+    ///
+    /// Payments by others are settled from the payer's own wallet and are
+    /// recorded in the bill's payment history and `paid_by`. Recurring bills
+    /// pass the policy on to their next occurrence.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `InvalidPayerPolicy` - If an allowlist is empty or longer than `MAX_ALLOWED_PAYERS`
+    pub fn set_bill_payers(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        policy: PayerPolicy,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if let PayerPolicy::Allowlist(payers) = &policy {
+            if payers.is_empty() || payers.len() > MAX_ALLOWED_PAYERS {
+                return Err(Error::InvalidPayerPolicy);
             }
         }
-        total
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        bill.payable_by = policy.clone();
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("payers"),
+            (bill_id, policy),
+        );
+        Ok(())
     }
 
-    // -----------------------------------------------------------------------
-    // Internal helpers
-    // -----------------------------------------------------------------------
+    /// Set the payee and settlement token for an unpaid bill.
+    ///
+    /// Once set, paying the bill transfers its `amount` in `token` from the
+    /// owner to `payee`. Recurring bills carry the payee over to the next bill.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the caller (must be the bill owner)
+    /// * `bill_id` - ID of the bill to update
+    /// * `payee` - Address that receives the payment
+    /// * `token` - Token contract the payment is made in
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `InvalidPayee` - If `payee` is the bill owner
+    pub fn set_bill_payee(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        payee: Address,
+        token: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if payee == caller {
+            return Err(Error::InvalidPayee);
+        }
+        Self::update_payee(&env, &caller, bill_id, Some(payee), Some(token))
+    }
 
-    fn extend_instance_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    /// Remove the payee from an unpaid bill so it is settled off-chain again.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If the bill is split; its shares are paid on-chain
+    ///
+    /// Also turns off auto-pay for the bill.
+    pub fn clear_bill_payee(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::update_payee(&env, &caller, bill_id, None, None)
     }
 
-    fn extend_archive_ttl(env: &Env) {
-        env.storage()
-            .instance()
-            .extend_ttl(ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    fn update_payee(
+        env: &Env,
+        caller: &Address,
+        bill_id: u32,
+        payee: Option<Address>,
+        token: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::extend_instance_ttl(env);
+        let mut bill = Self::load_bill(env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != *caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if payee.is_none() && !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
+        }
+
+        bill.payee = payee.clone();
+        bill.token = token.clone();
+        if payee.is_none() && bill.auto_pay {
+            Self::index_auto_pay(env, &bill, false);
+            bill.auto_pay = false;
+        }
+        Self::save_bill(env, &bill);
+
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("payee"),
+            (bill_id, payee, token),
+        );
+
+        Ok(())
     }
 
-    fn update_storage_stats(env: &Env) {
-        let bills: Map<u32, Bill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("BILLS"))
-            .unwrap_or_else(|| Map::new(env));
-        let archived: Map<u32, ArchivedBill> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("ARCH_BILL"))
-            .unwrap_or_else(|| Map::new(env));
+    /// Transfer `amount` towards the bill to the bill's payee.
+    ///
+    /// When the owner pays and their bills pool holds enough of the bill's
+    /// token, the payment is drawn from the pool; otherwise it comes from
+    /// `payer`'s wallet. Returns the token transferred, or `None` for bills
+    /// without a payee.
+    ///
+    /// Only the owner may pay an amount the biller raised before approving it.
+    fn settle_payment(
+        env: &Env,
+        payer: &Address,
+        bill: &Bill,
+        amount: i128,
+    ) -> Result<Option<Address>, Error> {
+        if *payer != bill.owner && bill.amount > bill.approved_amount {
+            return Err(Error::AmountNotApproved);
+        }
+        let (payee, token) = match (&bill.payee, &bill.token) {
+            (Some(payee), Some(token)) => (payee, token),
+            // Only the owner can vouch for a payment made off-chain.
+            _ if *payer != bill.owner => return Err(Error::PayeeNotSet),
+            _ => return Ok(None),
+        };
+        let client = TokenClient::new(env, token);
+        let from_pool =
+            *payer == bill.owner && Self::draw_from_pool(env, &client, bill, payee, token, amount)?;
+        if !from_pool {
+            if client.balance(payer) < amount {
+                return Err(Error::InsufficientFunds);
+            }
+            Self::transfer(&client, payer, payee, amount)?;
+        }
+        Self::emit_settled(env, bill.id, payee, token, amount);
+        Ok(Some(token.clone()))
+    }
 
-        let mut active_count = 0u32;
-        let mut unpaid_amount = 0i128;
-        for (_, bill) in bills.iter() {
-            active_count += 1;
-            if !bill.paid {
-                unpaid_amount = unpaid_amount.saturating_add(bill.amount);
+    /// Settle a bill without the owner's signature, for `execute_due_bills`.
+    ///
+    /// Draws on the owner's bills pool first, then on the allowance the
+    /// owner granted this contract in the bill's token.
+    fn settle_auto_payment(env: &Env, bill: &Bill, amount: i128) -> Result<Address, Error> {
+        if bill.amount > bill.approved_amount {
+            return Err(Error::AmountNotApproved);
+        }
+        let (payee, token) = match (&bill.payee, &bill.token) {
+            (Some(payee), Some(token)) => (payee, token),
+            _ => return Err(Error::PayeeNotSet),
+        };
+        let client = TokenClient::new(env, token);
+        if !Self::draw_from_pool(env, &client, bill, payee, token, amount)? {
+            let spender = env.current_contract_address();
+            if client.balance(&bill.owner) < amount
+                || client.allowance(&bill.owner, &spender) < amount
+            {
+                return Err(Error::InsufficientFunds);
+            }
+            match client.try_transfer_from(&spender, &bill.owner, payee, &amount) {
+                Ok(Ok(())) => {}
+                _ => return Err(Error::TransferFailed),
             }
         }
+        Self::emit_settled(env, bill.id, payee, token, amount);
+        Ok(token.clone())
+    }
 
-        let mut archived_count = 0u32;
-        let mut archived_amount = 0i128;
-        for (_, bill) in archived.iter() {
-            archived_count += 1;
-            archived_amount = archived_amount.saturating_add(bill.amount);
+    /// Pay `amount` towards the bill from the owner's pool if it holds enough.
+    ///
+    /// Returns `false`, without moving funds, when the pool is short.
+    fn draw_from_pool(
+        env: &Env,
+        client: &TokenClient,
+        bill: &Bill,
+        payee: &Address,
+        token: &Address,
+        amount: i128,
+    ) -> Result<bool, Error> {
+        let pool_balance = Self::pool_balance(env, &bill.owner, token);
+        if pool_balance < amount {
+            return Ok(false);
         }
+        Self::transfer(client, &env.current_contract_address(), payee, amount)?;
+        Self::set_pool_balance(env, &bill.owner, token, pool_balance - amount);
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("pool_draw"),
+            (bill.id, bill.owner.clone(), token.clone(), amount),
+        );
+        Ok(true)
+    }
 
-        let stats = StorageStats {
-            active_bills: active_count,
-            archived_bills: archived_count,
-            total_unpaid_amount: unpaid_amount,
-            total_archived_amount: archived_amount,
-            last_updated: env.ledger().timestamp(),
-        };
+    fn emit_settled(env: &Env, bill_id: u32, payee: &Address, token: &Address, amount: i128) {
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("settled"),
+            (bill_id, payee.clone(), token.clone(), amount),
+        );
+    }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("STOR_STAT"), &stats);
+    fn transfer(
+        client: &TokenClient,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        match client.try_transfer(from, to, &amount) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(Error::TransferFailed),
+        }
     }
-    fn get_unpaid_totals_map(env: &Env) -> Option<Map<Address, i128>> {
-        env.storage().instance().get(&STORAGE_UNPAID_TOTALS)
+
+    /// Record a settled payment against `bill` in its payment history.
+    ///
+    /// Once nothing remains due, the bill is marked paid and, if it is
+    /// recurring, its next occurrence is stored under the next bill ID.
+    /// Returns the change to the owner's unpaid total.
+    fn apply_payment(env: &Env, bill: &mut Bill, payment: BillPayment) -> Result<i128, Error> {
+        // Late fees are settled first and never count towards `amount_paid`
+        // or the owner's unpaid total.
+        let fee = bill.late_fee_due(payment.paid_at).min(payment.amount);
+        bill.late_fee_paid += fee;
+        let principal = payment.amount - fee;
+        let mut delta = -principal;
+        bill.amount_paid += principal;
+        let paid_token = payment.token.clone();
+        let payer = payment.payer.clone();
+        Self::record_payment(env, bill.id, payment);
+
+        if bill.remaining() == 0 {
+            bill.paid_by = Some(payer);
+            delta += Self::mark_paid(env, bill, paid_token)?;
+        }
+        Ok(delta)
     }
 
-    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
-        if delta == 0 {
-            return;
+    /// Whether `payer` may pay `bill` under its `payable_by` policy.
+    fn can_pay(bill: &Bill, payer: &Address) -> bool {
+        if bill.owner == *payer {
+            return true;
         }
-        let mut totals: Map<Address, i128> = env
-            .storage()
-            .instance()
-            .get(&STORAGE_UNPAID_TOTALS)
-            .unwrap_or_else(|| Map::new(env));
-        let current = totals.get(owner.clone()).unwrap_or(0);
-        let next = current.checked_add(delta).expect("overflow");
-        totals.set(owner.clone(), next);
-        env.storage()
-            .instance()
-            .set(&STORAGE_UNPAID_TOTALS, &totals);
+        match &bill.payable_by {
+            PayerPolicy::Owner => false,
+            PayerPolicy::Anyone => true,
+            PayerPolicy::Allowlist(payers) => payers.contains(payer),
+        }
+    }
+
+    fn emit_paid(env: &Env, bill_id: u32, owner: &Address, amount: i128, payer: &Address) {
+        RemitwiseEvents::emit(
+            env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("paid"),
+            (bill_id, owner.clone(), amount, payer.clone()),
+        );
+    }
+
+    /// Mark `bill` paid and, unless its recurrence has ended, store its next
+    /// occurrence under the next bill ID. Returns the amount the next occurrence adds to
+    /// the owner's unpaid total.
+    fn mark_paid(env: &Env, bill: &mut Bill, paid_token: Option<Address>) -> Result<i128, Error> {
+        let current_time = env.ledger().timestamp();
+        let next_due_date = bill
+            .recurrence
+            .next_due_date(bill.due_date, bill.occurrence)?;
+        bill.paid = true;
+        bill.paid_at = Some(current_time);
+        bill.paid_token = paid_token;
+        Self::unindex_unpaid(env, bill);
+
+        let Some(next_due_date) = next_due_date else {
+            return Ok(0);
+        };
+        let next_bill = Bill {
+            id: Self::allocate_bill_id(env),
+            owner: bill.owner.clone(),
+            name: bill.name.clone(),
+            external_ref: bill.external_ref.clone(),
+            amount: bill.amount,
+            due_date: next_due_date,
+            recurring: true,
+            frequency_days: bill.frequency_days,
+            paid: false,
+            created_at: current_time,
+            paid_at: None,
+            schedule_id: bill.schedule_id,
+            tags: bill.tags.clone(),
+            currency: bill.currency.clone(),
+            payee: bill.payee.clone(),
+            token: bill.token.clone(),
+            amount_paid: 0,
+            paid_token: None,
+            biller_id: bill.biller_id,
+            auto_pay: bill.auto_pay,
+            auto_pay_failures: 0,
+            payable_by: bill.payable_by.clone(),
+            paid_by: None,
+            late_fee: bill.late_fee.clone(),
+            late_fee_paid: 0,
+            recurrence: bill.recurrence.clone(),
+            occurrence: bill.occurrence.saturating_add(1),
+            // Variable bills start each cycle from the last amount as an estimate.
+            amount_kind: match bill.amount_kind {
+                AmountKind::Fixed => AmountKind::Fixed,
+                _ => AmountKind::Estimated,
+            },
+            approved_amount: bill.approved_amount,
+            dispute: DisputeStatus::None,
+            reminded_days: None,
+            shares: Self::unpaid_shares(env, &bill.shares),
+        };
+        Self::insert_bill(env, &next_bill);
+        Self::index_shares(env, &next_bill, true);
+        Self::adjust_storage_stats(env, 1, 0, 0, 0);
+        Ok(next_bill.amount)
+    }
+
+    // -----------------------------------------------------------------------
+    // Bills pool
+    // -----------------------------------------------------------------------
+
+    /// Add funds to `owner`'s prepaid bills pool.
+    ///
+    /// `from` transfers `amount` of `token` into this contract. It may be the
+    /// owner or any other funder, such as the orchestrator forwarding the
+    /// bills share of a remittance. `pay_bill` draws on the pool before the
+    /// owner's wallet.
+    ///
+    /// If the owner has auto-pay enabled, due bills payable in `token` are
+    /// then paid from the pool in due-date order (at most `MAX_BATCH_SIZE`
+    /// per top-up, found within `MAX_SCAN_IDS` due-index entries), stopping
+    /// at the first bill the pool cannot cover.
+    ///
+    /// # Returns
+    /// Number of bills auto-paid
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is zero or negative, or the pool would overflow
+    /// * `InsufficientFunds` - If `from` does not hold `amount`
+    /// * `TransferFailed` - If the transfer into the contract fails
+    pub fn top_up_pool(
+        env: Env,
+        from: Address,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<u32, Error> {
+        from.require_auth();
+        Self::require_not_paused(&env, pause_functions::POOL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let client = TokenClient::new(&env, &token);
+        if client.balance(&from) < amount {
+            return Err(Error::InsufficientFunds);
+        }
+        Self::transfer(&client, &from, &env.current_contract_address(), amount)?;
+
+        Self::extend_instance_ttl(&env);
+        let balance = Self::pool_balance(&env, &owner, &token)
+            .checked_add(amount)
+            .ok_or(Error::InvalidAmount)?;
+        Self::set_pool_balance(&env, &owner, &token, balance);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("pool_in"),
+            (owner.clone(), token.clone(), amount, from),
+        );
+
+        if !Self::is_auto_pay_enabled(env.clone(), owner.clone())
+            || Self::require_not_paused(&env, pause_functions::PAY_BILL).is_err()
+        {
+            return Ok(0);
+        }
+        Self::auto_pay_from_pool(&env, &owner, &token)
+    }
+
+    /// Withdraw unused funds from the caller's bills pool back to their wallet.
+    ///
+    /// # Returns
+    /// Remaining pool balance in `token`
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount is zero or negative
+    /// * `InsufficientFunds` - If the pool holds less than `amount`
+    /// * `TransferFailed` - If the transfer out of the contract fails
+    pub fn withdraw_from_pool(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::POOL)?;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let balance = Self::pool_balance(&env, &owner, &token);
+        if balance < amount {
+            return Err(Error::InsufficientFunds);
+        }
+        let client = TokenClient::new(&env, &token);
+        Self::transfer(&client, &env.current_contract_address(), &owner, amount)?;
+        let remaining = balance - amount;
+        Self::set_pool_balance(&env, &owner, &token, remaining);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("pool_out"),
+            (owner, token, amount),
+        );
+        Ok(remaining)
+    }
+
+    /// Get `owner`'s prepaid bills balance in `token`.
+    pub fn get_pool_balance(env: Env, owner: Address, token: Address) -> i128 {
+        Self::pool_balance(&env, &owner, &token)
+    }
+
+    /// Turn auto-pay from the bills pool on or off for the caller.
+    pub fn set_auto_pay(env: Env, owner: Address, enabled: bool) -> Result<(), Error> {
+        owner.require_auth();
+        Self::require_not_paused(&env, pause_functions::POOL)?;
+
+        Self::extend_instance_ttl(&env);
+        let key = DataKey::AutoPay(owner.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("auto_pay"),
+            (owner, enabled),
+        );
+        Ok(())
+    }
+
+    pub fn is_auto_pay_enabled(env: Env, owner: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AutoPay(owner))
+            .unwrap_or(false)
+    }
+
+    /// Pay `owner`'s due bills in `token` from their pool, earliest due first.
+    /// Bills whose raised amount the owner has not approved are left alone.
+    ///
+    /// Walks the due-date index, visiting at most `MAX_SCAN_IDS` entries and
+    /// paying at most `MAX_BATCH_SIZE` bills; the next top-up goes on from
+    /// there. Legacy bills have no payee, so they are not looked at.
+    fn auto_pay_from_pool(env: &Env, owner: &Address, token: &Address) -> Result<u32, Error> {
+        let now = env.ledger().timestamp();
+        let mut due: StdVec<(u64, u32)> = StdVec::new();
+        let mut last_bucket: Option<u64> = None;
+        Self::walk_due_index(
+            env,
+            &DataKey::DueBuckets,
+            DataKey::DueBills,
+            None,
+            0,
+            now,
+            0,
+            |bill| {
+                // Finish the bucket of the last bill that fits so the batch
+                // can be put in due-date order within it.
+                let bucket = bill.due_date / INDEX_BUCKET_SECONDS;
+                if last_bucket.is_some_and(|last| bucket > last) {
+                    return false;
+                }
+                if bill.owner == *owner
+                    && !bill.paid
+                    && bill.due_date <= now
+                    && bill.payee.is_some()
+                    && bill.token.as_ref() == Some(token)
+                    && bill.shares.is_empty()
+                    && bill.amount <= bill.approved_amount
+                {
+                    due.push((bill.due_date, bill.id));
+                    if due.len() == MAX_BATCH_SIZE as usize {
+                        last_bucket = Some(bucket);
+                    }
+                }
+                true
+            },
+        );
+        due.sort_unstable();
+
+        let mut paid_count = 0u32;
+        let mut unpaid_delta = 0i128;
+        for &(_, id) in due.iter().take(MAX_BATCH_SIZE as usize) {
+            let Some(mut bill) = Self::load_bill(env, id) else {
+                continue;
+            };
+            // Strict due-date order: a later bill never jumps an unfunded one.
+            let amount = bill.amount_due_now(now);
+            if Self::pool_balance(env, owner, token) < amount {
+                break;
+            }
+            let paid_token = match Self::settle_payment(env, owner, &bill, amount) {
+                Ok(paid_token) => paid_token,
+                Err(err) => {
+                    RemitwiseEvents::emit(
+                        env,
+                        EventCategory::Transaction,
+                        EventPriority::Medium,
+                        symbol_short!("f_pay_xfr"), // fail_pay_transfer
+                        (id, err as u32),
+                    );
+                    break;
+                }
+            };
+            let payment = BillPayment {
+                payer: owner.clone(),
+                amount,
+                token: paid_token,
+                paid_at: now,
+            };
+            let delta = Self::apply_payment(env, &mut bill, payment)?;
+            unpaid_delta = unpaid_delta.saturating_add(delta);
+            Self::save_bill(env, &bill);
+            paid_count += 1;
+
+            Self::emit_paid(env, id, owner, amount, owner);
+        }
+
+        if paid_count > 0 {
+            Self::adjust_unpaid_total(env, owner, unpaid_delta);
+            RemitwiseEvents::emit(
+                env,
+                EventCategory::System,
+                EventPriority::Medium,
+                symbol_short!("auto_paid"),
+                (owner.clone(), token.clone(), paid_count),
+            );
+        }
+        Ok(paid_count)
+    }
+
+    fn pool_balance(env: &Env, owner: &Address, token: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Pool(owner.clone(), token.clone()))
+            .unwrap_or(0)
+    }
+
+    fn set_pool_balance(env: &Env, owner: &Address, token: &Address, balance: i128) {
+        let key = DataKey::Pool(owner.clone(), token.clone());
+        if balance == 0 {
+            env.storage().persistent().remove(&key);
+            return;
+        }
+        env.storage().persistent().set(&key, &balance);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Scheduled auto-pay
+    // -----------------------------------------------------------------------
+
+    /// Opt a bill in or out of scheduled auto-pay.
+    ///
+    /// Opted-in bills are paid by `execute_due_bills` once their due date
+    /// arrives, from the owner's bills pool or, failing that, from the
+    /// allowance the owner granted this contract on the bill's token.
+    /// Recurring bills pass the setting on to their next occurrence.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `BillIsSplit` - If enabling on a bill split between participants
+    /// * `PayeeNotSet` - If enabling on a bill without a payee and token
+    pub fn set_bill_auto_pay(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if enabled && !bill.shares.is_empty() {
+            return Err(Error::BillIsSplit);
+        }
+        if enabled && (bill.payee.is_none() || bill.token.is_none()) {
+            return Err(Error::PayeeNotSet);
+        }
+
+        if bill.auto_pay != enabled {
+            Self::index_auto_pay(&env, &bill, enabled);
+        }
+        bill.auto_pay = enabled;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("bill_auto"),
+            (bill_id, enabled),
+        );
+        Ok(())
+    }
+
+    /// Pay due auto-pay bills (public, callable by anyone - keeper pattern).
+    ///
+    /// Walks the auto-pay due index, earliest due day first, resuming after
+    /// `cursor`, and attempts at most `limit` payments (0 or anything above
+    /// `MAX_BATCH_SIZE` means `MAX_BATCH_SIZE`). Bills without auto-pay are
+    /// never visited. A bill that cannot be paid, e.g. for lack of
+    /// funds or because its biller raised the amount without the owner's
+    /// approval, stays unpaid: its `auto_pay_failures` count is bumped, an
+    /// `AutoPayFailed` event is emitted, and the next run retries it.
+    ///
+    /// # Errors
+    /// * `ContractPaused` / `FunctionPaused` - If bill payments are paused
+    pub fn execute_due_bills(env: Env, cursor: u32, limit: u32) -> Result<AutoPayResult, Error> {
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+        let limit = if limit == 0 || limit > MAX_BATCH_SIZE {
+            MAX_BATCH_SIZE
+        } else {
+            limit
+        };
+
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+
+        // Collect the due bills first: paying a recurring bill adds its next
+        // occurrence to the index. One more than `limit` tells whether another
+        // page follows. Legacy bills predate auto-pay.
+        let mut due = Vec::new(&env);
+        let stopped_at = Self::walk_due_index(
+            &env,
+            &DataKey::AutoPayBuckets,
+            DataKey::AutoPayDue,
+            None,
+            0,
+            now,
+            cursor,
+            |bill| {
+                if bill.auto_pay && !bill.paid && bill.due_date <= now {
+                    due.push_back(bill.id);
+                }
+                due.len() <= limit
+            },
+        );
+
+        let mut paid = Vec::new(&env);
+        let mut failed = Vec::new(&env);
+        let mut next_cursor = if due.len() > limit {
+            0
+        } else {
+            stopped_at.unwrap_or(0)
+        };
+        for (attempts, id) in due.iter().enumerate() {
+            if attempts as u32 == limit {
+                next_cursor = due.get(limit - 1).unwrap_or(0);
+                break;
+            }
+            let Some(mut bill) = Self::load_bill(&env, id) else {
+                continue;
+            };
+
+            let amount = bill.amount_due_now(now);
+            match Self::settle_auto_payment(&env, &bill, amount) {
+                Ok(token) => {
+                    let owner = bill.owner.clone();
+                    let payment = BillPayment {
+                        payer: owner.clone(),
+                        amount,
+                        token: Some(token),
+                        paid_at: now,
+                    };
+                    let delta = Self::apply_payment(&env, &mut bill, payment)?;
+                    bill.auto_pay_failures = 0;
+                    Self::save_bill(&env, &bill);
+                    Self::adjust_unpaid_total(&env, &owner, delta);
+                    paid.push_back(id);
+
+                    Self::emit_paid(&env, id, &owner, amount, &owner);
+                }
+                Err(err) => {
+                    bill.auto_pay_failures = bill.auto_pay_failures.saturating_add(1);
+                    let owner = bill.owner.clone();
+                    let failures = bill.auto_pay_failures;
+                    Self::save_bill(&env, &bill);
+                    failed.push_back(id);
+
+                    env.events().publish(
+                        (symbol_short!("bill"), BillEvent::AutoPayFailed),
+                        (id, owner, err as u32, failures),
+                    );
+                }
+            }
+        }
+
+        Ok(AutoPayResult {
+            paid,
+            failed,
+            next_cursor,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Due reminders
+    // -----------------------------------------------------------------------
+
+    /// Set how many days before a due date the owner is reminded of each
+    /// unpaid bill, e.g. `[3, 1]`. An empty list turns reminders off.
+    ///
+    /// # Errors
+    /// * `InvalidReminderConfig` - If a day is outside 1 to `MAX_REMINDER_DAYS`
+    ///   or more than `MAX_REMINDERS` are given
+    pub fn set_reminder_days(env: Env, owner: Address, days: Vec<u32>) -> Result<(), Error> {
+        owner.require_auth();
+        if days.len() > MAX_REMINDERS {
+            return Err(Error::InvalidReminderConfig);
+        }
+        let mut sorted: Vec<u32> = Vec::new(&env);
+        for day in days.iter() {
+            if day == 0 || day > MAX_REMINDER_DAYS {
+                return Err(Error::InvalidReminderConfig);
+            }
+            Self::insert_sorted(&mut sorted, day);
+        }
+        // Stored furthest first, the order reminders go out in.
+        let mut descending = Vec::new(&env);
+        for day in sorted.iter().rev() {
+            descending.push_back(day);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let key = DataKey::ReminderDays(owner.clone());
+        if descending.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            Self::save_list(&env, &key, &descending);
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("reminders"),
+            (owner, descending),
+        );
+        Ok(())
+    }
+
+    /// Get the owner's reminder days, furthest first. Empty if reminders
+    /// are off.
+    pub fn get_reminder_days(env: Env, owner: Address) -> Vec<u32> {
+        Self::load_list(&env, &DataKey::ReminderDays(owner))
+    }
+
+    /// Emit reminders for bills coming due (public, callable by anyone -
+    /// keeper pattern).
+    ///
+    /// Walks the due-date index from today to `MAX_REMINDER_DAYS` ahead,
+    /// resuming after `cursor`, so overdue bills are never visited. For each
+    /// unpaid bill not yet due it emits a `("bill", BillEvent::DueSoon)` event with
+    /// `(bill_id, owner, due_date, days_before)` when one of the owner's
+    /// reminder days is reached. Each reminder goes out once per bill; a run
+    /// that is late for several only sends the nearest. At most `limit`
+    /// reminders are emitted (0 or anything above `MAX_BATCH_SIZE` means
+    /// `MAX_BATCH_SIZE`).
+    pub fn emit_due_reminders(env: Env, cursor: u32, limit: u32) -> ReminderResult {
+        let limit = if limit == 0 || limit > MAX_BATCH_SIZE {
+            MAX_BATCH_SIZE
+        } else {
+            limit
+        };
+
+        Self::extend_instance_ttl(&env);
+        let now = env.ledger().timestamp();
+        let horizon = now.saturating_add(MAX_REMINDER_DAYS as u64 * SECONDS_PER_DAY);
+        let mut reminder_days: Map<Address, Vec<u32>> = Map::new(&env);
+        let mut reminded = Vec::new(&env);
+        let mut next_cursor = 0u32;
+
+        let stopped_at = Self::walk_due_index(
+            &env,
+            &DataKey::DueBuckets,
+            DataKey::DueBills,
+            Self::legacy_bills(&env),
+            now,
+            horizon,
+            cursor,
+            |mut bill| {
+                if bill.paid || bill.due_date <= now {
+                    return true;
+                }
+                let days = match reminder_days.get(bill.owner.clone()) {
+                    Some(days) => days,
+                    None => {
+                        let days = Self::get_reminder_days(env.clone(), bill.owner.clone());
+                        reminder_days.set(bill.owner.clone(), days.clone());
+                        days
+                    }
+                };
+                let Some(days_before) = Self::reminder_due(&bill, &days, now) else {
+                    return true;
+                };
+                if reminded.len() == limit {
+                    next_cursor = reminded.last().unwrap_or(0);
+                    return false;
+                }
+
+                bill.reminded_days = Some(days_before);
+                Self::save_bill(&env, &bill);
+                reminded.push_back(bill.id);
+
+                env.events().publish(
+                    (symbol_short!("bill"), BillEvent::DueSoon),
+                    (bill.id, bill.owner, bill.due_date, days_before),
+                );
+                true
+            },
+        );
+        if let Some(last) = stopped_at {
+            next_cursor = last;
+        }
+
+        ReminderResult {
+            reminded,
+            next_cursor,
+        }
+    }
+
+    /// The nearest of `days` (descending) whose reminder for `bill` is due
+    /// at `now` and has not been sent yet.
+    fn reminder_due(bill: &Bill, days: &Vec<u32>, now: u64) -> Option<u32> {
+        let until_due = bill.due_date.saturating_sub(now);
+        let reached = days
+            .iter()
+            .rev()
+            .find(|day| until_due <= *day as u64 * SECONDS_PER_DAY)?;
+        match bill.reminded_days {
+            Some(sent) if sent <= reached => None,
+            _ => Some(reached),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Bill splitting
+    // -----------------------------------------------------------------------
+
+    /// Split an unpaid bill between participants, or pass no shares to undo
+    /// the split.
+    ///
+    /// Each share is a fixed amount or basis points of the bill amount, and
+    /// the shares must add up to the amount. Percentages are rounded down;
+    /// the units lost to rounding go to the first of them. The owner may be
+    /// a participant. Once split, the bill is paid share by share with
+    /// `pay_share` and is marked paid when every share is. Recurring bills
+    /// pass the shares on to their next occurrence.
+    ///
+    /// Only a bill with a payee can be split, so that every share is paid
+    /// on-chain.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If caller is not the bill owner
+    /// * `BillAlreadyPaid` - If the bill is already paid
+    /// * `PayeeNotSet` - If splitting a bill without a payee and token
+    /// * `InvalidShares` - If the shares are malformed, or the bill has
+    ///   payments, auto-pay or late-fee terms
+    ///
+    /// # Events
+    /// - `split` with `(bill_id, shares)`
+    pub fn split_bill(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        shares: Vec<ShareSpec>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        if !shares.is_empty() && (bill.payee.is_none() || bill.token.is_none()) {
+            return Err(Error::PayeeNotSet);
+        }
+        if bill.amount_paid > 0 || bill.auto_pay || bill.late_fee != LateFeePolicy::None {
+            return Err(Error::InvalidShares);
+        }
+        let resolved = Self::resolve_shares(&env, bill.amount, &shares)?;
+
+        Self::index_shares(&env, &bill, false);
+        bill.shares = resolved;
+        Self::index_shares(&env, &bill, true);
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("split"),
+            (bill_id, bill.shares),
+        );
+        Ok(())
+    }
+
+    /// Pay the caller's share of a split bill, settled like `pay_bill`.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If bill with given ID doesn't exist
+    /// * `Unauthorized` - If the caller has no share in the bill
+    /// * `BillAlreadyPaid` - If the bill or the caller's share is already paid
+    /// * `InsufficientFunds` - If the caller's token balance does not cover the share
+    /// * `TransferFailed` - If the token transfer to the payee fails
+    ///
+    /// # Events
+    /// - `share_pd` with `(bill_id, participant, amount, remaining)`
+    /// - `paid` with `(bill_id, owner, amount, payer)` once every share is paid
+    pub fn pay_share(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+        let index = bill
+            .shares
+            .iter()
+            .position(|share| share.participant == caller)
+            .ok_or(Error::Unauthorized)? as u32;
+        let mut share = bill.shares.get(index).ok_or(Error::Unauthorized)?;
+        if share.paid {
+            return Err(Error::BillAlreadyPaid);
+        }
+
+        let now = env.ledger().timestamp();
+        let paid_token = Self::settle_payment(&env, &caller, &bill, share.amount)?;
+        share.paid = true;
+        share.paid_at = Some(now);
+        bill.shares.set(index, share.clone());
+        if caller != bill.owner {
+            Self::remove_from_list(&env, &DataKey::ShareBills(caller.clone()), bill_id);
+        }
+        let payment = BillPayment {
+            payer: caller.clone(),
+            amount: share.amount,
+            token: paid_token,
+            paid_at: now,
+        };
+        let unpaid_delta = Self::apply_payment(&env, &mut bill, payment)?;
+
+        Self::save_bill(&env, &bill);
+        Self::adjust_unpaid_total(&env, &bill.owner, unpaid_delta);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::Medium,
+            symbol_short!("share_pd"),
+            (bill_id, caller.clone(), share.amount, bill.remaining()),
+        );
+        if bill.paid {
+            Self::emit_paid(&env, bill_id, &bill.owner, share.amount, &caller);
+        }
+        Ok(())
+    }
+
+    /// Get a page of other owners' bills in which `participant` still owes
+    /// their share.
+    ///
+    /// Same cursor/limit semantics as `get_unpaid_bills`.
+    pub fn get_shares_owed(env: Env, participant: Address, cursor: u32, limit: u32) -> BillPage {
+        let limit = clamp_limit(limit);
+        let ids = Self::load_list(&env, &DataKey::ShareBills(participant.clone()));
+        Self::page_from_ids(&env, &ids, cursor, limit, |bill| {
+            Self::share_owed(bill, &participant) > 0
+        })
+    }
+
+    /// Total `participant` still owes across their shares of other owners'
+    /// bills.
+    pub fn get_total_share_owed(env: Env, participant: Address) -> i128 {
+        let ids: Vec<u32> = Self::load_list(&env, &DataKey::ShareBills(participant.clone()));
+        ids.iter()
+            .filter_map(|id| Self::load_bill(&env, id))
+            .map(|bill| Self::share_owed(&bill, &participant))
+            .fold(0i128, |total, owed| total.saturating_add(owed))
+    }
+
+    /// Resolve `specs` against a bill `amount` into unpaid shares.
+    fn resolve_shares(
+        env: &Env,
+        amount: i128,
+        specs: &Vec<ShareSpec>,
+    ) -> Result<Vec<BillShare>, Error> {
+        let mut shares: Vec<BillShare> = Vec::new(env);
+        if specs.is_empty() {
+            return Ok(shares);
+        }
+        if specs.len() > MAX_SHARES {
+            return Err(Error::InvalidShares);
+        }
+
+        let mut total = 0i128;
+        let mut percent_shares = 0i128;
+        let mut first_percent: Option<u32> = None;
+        for spec in specs.iter() {
+            if shares
+                .iter()
+                .any(|share| share.participant == spec.participant)
+            {
+                return Err(Error::InvalidShares);
+            }
+            let share_amount = match spec.portion {
+                SharePortion::Amount(share_amount) => share_amount,
+                SharePortion::Bps(bps) if bps <= 10_000 => {
+                    first_percent.get_or_insert(shares.len());
+                    percent_shares += 1;
+                    amount
+                        .checked_mul(bps as i128)
+                        .ok_or(Error::InvalidShares)?
+                        / 10_000
+                }
+                SharePortion::Bps(_) => return Err(Error::InvalidShares),
+            };
+            if share_amount <= 0 {
+                return Err(Error::InvalidShares);
+            }
+            total = total
+                .checked_add(share_amount)
+                .ok_or(Error::InvalidShares)?;
+            shares.push_back(BillShare {
+                participant: spec.participant,
+                amount: share_amount,
+                paid: false,
+                paid_at: None,
+            });
+        }
+
+        // Rounding each percentage down loses less than one unit per share.
+        let shortfall = amount - total;
+        if shortfall < 0 || (shortfall > 0 && shortfall >= percent_shares) {
+            return Err(Error::InvalidShares);
+        }
+        if let Some(i) = first_percent {
+            if let Some(mut share) = shares.get(i) {
+                share.amount += shortfall;
+                shares.set(i, share);
+            }
+        }
+        Ok(shares)
+    }
+
+    /// `shares` with every share unpaid again, for a bill's next occurrence.
+    fn unpaid_shares(env: &Env, shares: &Vec<BillShare>) -> Vec<BillShare> {
+        let mut unpaid = Vec::new(env);
+        for mut share in shares.iter() {
+            share.paid = false;
+            share.paid_at = None;
+            unpaid.push_back(share);
+        }
+        unpaid
+    }
+
+    /// What `participant` still owes on their share of `bill`.
+    fn share_owed(bill: &Bill, participant: &Address) -> i128 {
+        if bill.paid || bill.owner == *participant {
+            return 0;
+        }
+        bill.shares
+            .iter()
+            .find(|share| share.participant == *participant && !share.paid)
+            .map_or(0, |share| share.amount)
+    }
+
+    // -----------------------------------------------------------------------
+    // Disputes
+    // -----------------------------------------------------------------------
+
+    /// Dispute a paid bill, asking its payee to refund `amount`.
+    ///
+    /// The owner may reclaim up to everything transferred for the bill and
+    /// not yet refunded, split between the payers pro rata to what each
+    /// transferred. Any other payer may reclaim up to what they transferred
+    /// themselves, refunded to them alone. The dispute must be opened within
+    /// `DISPUTE_WINDOW_DAYS` of payment.
+    ///
+    /// # Errors
+    /// * `BillNotFound` - If the bill doesn't exist or was archived
+    /// * `Unauthorized` - If the caller neither owns nor paid towards the bill
+    /// * `NotDisputable` - If the bill is unpaid or was settled off-chain
+    /// * `DisputeWindowClosed` - If the bill was paid too long ago
+    /// * `DisputeExists` - If the bill's last dispute is not closed yet
+    /// * `InvalidAmount` - If amount is not positive or exceeds what the caller
+    ///   may reclaim
+    ///
+    /// # Events
+    /// - `disp_open` with `(bill_id, caller, amount)`
+    pub fn open_dispute(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        amount: i128,
+        reason: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::DISPUTE)?;
+
+        let mut bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        let payments = Self::load_payments(&env, bill_id);
+        let is_payer = payments.iter().any(|payment| payment.payer == caller);
+        if bill.owner != caller && !is_payer {
+            return Err(Error::Unauthorized);
+        }
+        let (true, Some(payee), Some(token), Some(paid_at)) = (
+            bill.paid,
+            bill.payee.clone(),
+            bill.paid_token.clone(),
+            bill.paid_at,
+        ) else {
+            return Err(Error::NotDisputable);
+        };
+        let now = env.ledger().timestamp();
+        if now > paid_at.saturating_add(DISPUTE_WINDOW_DAYS * SECONDS_PER_DAY) {
+            return Err(Error::DisputeWindowClosed);
+        }
+        let prior = Self::load_dispute(&env, bill_id);
+        let refunded = match &prior {
+            Some(prior) if prior.status.is_pending() => return Err(Error::DisputeExists),
+            Some(prior) if prior.status == DisputeStatus::Refunded => {
+                Self::merge_amounts(&prior.refunded, &prior.refunds)
+            }
+            Some(prior) => prior.refunded.clone(),
+            None => Map::new(&env),
+        };
+        let mut paid_in: Map<Address, i128> = Map::new(&env);
+        for payment in payments.iter() {
+            if payment.token.is_some() && (bill.owner == caller || payment.payer == caller) {
+                let total = paid_in.get(payment.payer.clone()).unwrap_or(0);
+                paid_in.set(payment.payer, total.saturating_add(payment.amount));
+            }
+        }
+        let mut reclaimable = 0i128;
+        for (payer, total) in paid_in.iter() {
+            let left = total.saturating_sub(refunded.get(payer.clone()).unwrap_or(0));
+            if left > 0 {
+                paid_in.set(payer, left);
+                reclaimable = reclaimable.saturating_add(left);
+            } else {
+                paid_in.remove(payer);
+            }
+        }
+        if amount <= 0 || amount > reclaimable {
+            return Err(Error::InvalidAmount);
+        }
+        let refunds = Self::split_pro_rata(&env, amount, &paid_in, reclaimable)?;
+
+        Self::extend_instance_ttl(&env);
+        if let Some(prior) = prior {
+            Self::push_past_dispute(&env, &prior);
+        }
+        Self::save_dispute(
+            &env,
+            &Dispute {
+                bill_id,
+                opened_by: caller.clone(),
+                payee,
+                token,
+                amount,
+                refunds,
+                refunded,
+                escrowed: false,
+                reason,
+                status: DisputeStatus::Open,
+                opened_at: now,
+                updated_at: now,
+                resolved_at: None,
+            },
+        );
+        bill.dispute = DisputeStatus::Open;
+        Self::save_bill(&env, &bill);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("disp_open"),
+            (bill_id, caller, amount),
+        );
+        Ok(())
+    }
+
+    /// Accept a dispute, refunding the disputed amount to the payers in
+    /// `refunds`. The payee may accept while the dispute is pending or after
+    /// the admin upheld it. The refund comes out of escrow once the payee has
+    /// rejected the dispute, and out of the payee's wallet otherwise.
+    ///
+    /// # Errors
+    /// * `DisputeNotFound` - If the bill has no dispute
+    /// * `Unauthorized` - If the caller is not the dispute's payee
+    /// * `InvalidDisputeState` - If the dispute was dismissed or refunded
+    /// * `InsufficientFunds` - If nothing is in escrow and the payee does not
+    ///   hold the disputed amount
+    /// * `TransferFailed` - If a refund transfer fails
+    ///
+    /// # Events
+    /// - `disp_acc` with `(bill_id, payee, opened_by, amount)`
+    pub fn accept_dispute(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::DISPUTE)?;
+
+        let mut dispute = Self::load_dispute(&env, bill_id).ok_or(Error::DisputeNotFound)?;
+        if dispute.payee != caller {
+            return Err(Error::Unauthorized);
+        }
+        if !dispute.status.is_refundable() {
+            return Err(Error::InvalidDisputeState);
+        }
+        let from = if dispute.escrowed {
+            env.current_contract_address()
+        } else {
+            let client = TokenClient::new(&env, &dispute.token);
+            if client.balance(&caller) < dispute.amount {
+                return Err(Error::InsufficientFunds);
+            }
+            caller.clone()
+        };
+        Self::pay_refunds(&env, &dispute, &from)?;
+        dispute.escrowed = false;
+
+        Self::extend_instance_ttl(&env);
+        Self::set_dispute_status(&env, &mut dispute, DisputeStatus::Refunded);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::Transaction,
+            EventPriority::High,
+            symbol_short!("disp_acc"),
+            (bill_id, caller, dispute.opened_by, dispute.amount),
+        );
+        Ok(())
+    }
+
+    /// Reject an open dispute. The payee deposits the disputed amount with
+    /// the contract, which holds it until the dispute is closed. The
+    /// disputant may then escalate it.
+    ///
+    /// # Errors
+    /// * `DisputeNotFound` - If the bill has no dispute
+    /// * `Unauthorized` - If the caller is not the dispute's payee
+    /// * `InvalidDisputeState` - If the dispute is not open
+    /// * `InsufficientFunds` - If the payee does not hold the disputed amount
+    /// * `TransferFailed` - If the deposit transfer fails
+    ///
+    /// # Events
+    /// - `disp_rej` with `(bill_id, payee)`
+    pub fn reject_dispute(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::DISPUTE)?;
+
+        let mut dispute = Self::load_dispute(&env, bill_id).ok_or(Error::DisputeNotFound)?;
+        if dispute.payee != caller {
+            return Err(Error::Unauthorized);
+        }
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::InvalidDisputeState);
+        }
+        let client = TokenClient::new(&env, &dispute.token);
+        if client.balance(&caller) < dispute.amount {
+            return Err(Error::InsufficientFunds);
+        }
+        Self::transfer(
+            &client,
+            &caller,
+            &env.current_contract_address(),
+            dispute.amount,
+        )?;
+        dispute.escrowed = true;
+
+        Self::extend_instance_ttl(&env);
+        Self::set_dispute_status(&env, &mut dispute, DisputeStatus::Rejected);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("disp_rej"),
+            (bill_id, caller),
+        );
+        Ok(())
+    }
+
+    /// Escalate a dispute to the admin. Allowed once the payee has rejected
+    /// it, or has left it unanswered for `DISPUTE_RESPONSE_DAYS`.
+    ///
+    /// # Errors
+    /// * `DisputeNotFound` - If the bill has no dispute
+    /// * `Unauthorized` - If the caller did not open the dispute
+    /// * `InvalidDisputeState` - If the payee can still respond, or the dispute
+    ///   is already escalated or closed
+    ///
+    /// # Events
+    /// - `disp_esc` with `(bill_id, opened_by)`
+    pub fn escalate_dispute(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::DISPUTE)?;
+
+        let mut dispute = Self::load_dispute(&env, bill_id).ok_or(Error::DisputeNotFound)?;
+        if dispute.opened_by != caller {
+            return Err(Error::Unauthorized);
+        }
+        let response_due = dispute
+            .opened_at
+            .saturating_add(DISPUTE_RESPONSE_DAYS * SECONDS_PER_DAY);
+        let unanswered =
+            dispute.status == DisputeStatus::Open && env.ledger().timestamp() >= response_due;
+        if dispute.status != DisputeStatus::Rejected && !unanswered {
+            return Err(Error::InvalidDisputeState);
+        }
+
+        Self::extend_instance_ttl(&env);
+        Self::set_dispute_status(&env, &mut dispute, DisputeStatus::Escalated);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::High,
+            symbol_short!("disp_esc"),
+            (bill_id, caller),
+        );
+        Ok(())
+    }
+
+    /// Rule on an escalated dispute. Only the admin can arbitrate.
+    ///
+    /// Ruling for the disputant (`refund`) pays the refunds out of escrow and
+    /// closes the dispute as `Refunded`. If the payee never answered, nothing
+    /// is in escrow: the dispute is closed as `Upheld`, the payee may still
+    /// refund with `accept_dispute`, and the disputant may open a new dispute
+    /// if they don't. Ruling for the payee returns the escrow to the payee and
+    /// closes the dispute as `Dismissed`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin
+    /// * `DisputeNotFound` - If the bill has no dispute
+    /// * `InvalidDisputeState` - If the dispute is not escalated
+    /// * `TransferFailed` - If paying out the escrow fails
+    ///
+    /// # Events
+    /// - `disp_res` with `(bill_id, refund)`
+    pub fn resolve_dispute(
+        env: Env,
+        caller: Address,
+        bill_id: u32,
+        refund: bool,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let mut dispute = Self::load_dispute(&env, bill_id).ok_or(Error::DisputeNotFound)?;
+        if dispute.status != DisputeStatus::Escalated {
+            return Err(Error::InvalidDisputeState);
+        }
+
+        let escrow = env.current_contract_address();
+        let status = match (refund, dispute.escrowed) {
+            (true, true) => {
+                Self::pay_refunds(&env, &dispute, &escrow)?;
+                DisputeStatus::Refunded
+            }
+            (true, false) => DisputeStatus::Upheld,
+            (false, true) => {
+                let client = TokenClient::new(&env, &dispute.token);
+                Self::transfer(&client, &escrow, &dispute.payee, dispute.amount)?;
+                DisputeStatus::Dismissed
+            }
+            (false, false) => DisputeStatus::Dismissed,
+        };
+        dispute.escrowed = false;
+
+        Self::extend_instance_ttl(&env);
+        Self::set_dispute_status(&env, &mut dispute, status);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::High,
+            symbol_short!("disp_res"),
+            (bill_id, refund),
+        );
+        Ok(())
+    }
+
+    /// Get the dispute over a bill, if any. Disputes outlive archival.
+    pub fn get_dispute(env: Env, bill_id: u32) -> Option<Dispute> {
+        Self::load_dispute(&env, bill_id)
+    }
+
+    /// Get a bill's earlier, closed disputes, oldest first, starting at
+    /// sequence number `cursor`. The current dispute is in `get_dispute`.
+    ///
+    /// `limit` is clamped like page limits; a shorter result means there
+    /// are no more.
+    pub fn get_past_disputes(env: Env, bill_id: u32, cursor: u32, limit: u32) -> Vec<Dispute> {
+        let limit = clamp_limit(limit);
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PastDisputeCount(bill_id))
+            .unwrap_or(0);
+        let mut disputes = Vec::new(&env);
+        for seq in cursor..count.min(cursor.saturating_add(limit)) {
+            if let Some(dispute) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PastDispute(bill_id, seq))
+            {
+                disputes.push_back(dispute);
+            }
+        }
+        disputes
+    }
+
+    // -----------------------------------------------------------------------
+    // Biller registry
+    // -----------------------------------------------------------------------
+
+    /// Register a new biller. Only the upgrade admin can curate the registry.
+    ///
+    /// The biller starts as `Pending` and must be verified with
+    /// `set_biller_status` before bills can reference it. Currency codes are
+    /// normalized the same way as bill currencies.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `InvalidBillerConfig` - If the name is empty, no currency is given,
+    ///   or the reference format is inconsistent
+    /// * `InvalidCurrency` - If a currency code is malformed
+    /// * `InvalidPayee` - If `payee` is the admin itself
+    pub fn register_biller(
+        env: Env,
+        caller: Address,
+        name: String,
+        payee: Address,
+        currencies: Map<String, Address>,
+        ref_format: RefFormat,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        if payee == caller {
+            return Err(Error::InvalidPayee);
+        }
+        let currencies = Self::validate_biller_config(&env, &name, &currencies, &ref_format)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut billers = Self::load_billers(&env);
+        let next_id = env
+            .storage()
+            .instance()
+            .get(&STORAGE_NEXT_BILLER_ID)
+            .unwrap_or(0u32)
+            + 1;
+
+        let biller = Biller {
+            id: next_id,
+            name,
+            payee,
+            currencies,
+            ref_format,
+            status: BillerStatus::Pending,
+            created_at: env.ledger().timestamp(),
+        };
+        billers.set(next_id, biller);
+        env.storage().instance().set(&STORAGE_BILLERS, &billers);
+        env.storage()
+            .instance()
+            .set(&STORAGE_NEXT_BILLER_ID, &next_id);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("blr_reg"),
+            next_id,
+        );
+        Ok(next_id)
+    }
+
+    /// Replace a biller's details.
+    ///
+    /// Changing the payee address sends a verified biller back to `Pending`,
+    /// so a new payout address is always re-checked. Bills already created
+    /// keep the payee and token they were created with.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `BillerNotFound` - If the biller does not exist
+    /// * `InvalidBillerConfig` / `InvalidCurrency` / `InvalidPayee` - As for `register_biller`
+    pub fn update_biller(
+        env: Env,
+        caller: Address,
+        biller_id: u32,
+        name: String,
+        payee: Address,
+        currencies: Map<String, Address>,
+        ref_format: RefFormat,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        if payee == caller {
+            return Err(Error::InvalidPayee);
+        }
+        let currencies = Self::validate_biller_config(&env, &name, &currencies, &ref_format)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut billers = Self::load_billers(&env);
+        let mut biller = billers.get(biller_id).ok_or(Error::BillerNotFound)?;
+        if biller.payee != payee {
+            biller.status = BillerStatus::Pending;
+        }
+        biller.name = name;
+        biller.payee = payee;
+        biller.currencies = currencies;
+        biller.ref_format = ref_format;
+        let status = biller.status;
+        billers.set(biller_id, biller);
+        env.storage().instance().set(&STORAGE_BILLERS, &billers);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("blr_upd"),
+            (biller_id, status),
+        );
+        Ok(())
+    }
+
+    /// Verify, suspend or reset a biller.
+    ///
+    /// Only `Verified` billers can be used for new bills or appear in
+    /// `search_billers`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    /// * `BillerNotFound` - If the biller does not exist
+    pub fn set_biller_status(
+        env: Env,
+        caller: Address,
+        biller_id: u32,
+        status: BillerStatus,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        Self::extend_instance_ttl(&env);
+        let mut billers = Self::load_billers(&env);
+        let mut biller = billers.get(biller_id).ok_or(Error::BillerNotFound)?;
+        biller.status = status;
+        billers.set(biller_id, biller);
+        env.storage().instance().set(&STORAGE_BILLERS, &billers);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::High,
+            symbol_short!("blr_stat"),
+            (biller_id, status),
+        );
+        Ok(())
+    }
+
+    /// Get a biller by ID, whatever its status.
+    pub fn get_biller(env: Env, biller_id: u32) -> Option<Biller> {
+        Self::load_billers(&env).get(biller_id)
+    }
+
+    /// Search verified billers by name.
+    ///
+    /// Matches `query` as a case-insensitive substring of the biller name; an
+    /// empty query lists every verified biller. Same cursor/limit semantics
+    /// as `get_unpaid_bills`, with biller IDs as the cursor.
+    pub fn search_billers(env: Env, query: String, cursor: u32, limit: u32) -> BillerPage {
+        let limit = clamp_limit(limit);
+        let needle = Self::lowercase_bytes(&query);
+        let billers = Self::load_billers(&env);
+
+        let mut items = Vec::new(&env);
+        let mut next_cursor = 0u32;
+        let mut last_id = 0u32;
+        for (id, biller) in billers.iter() {
+            if id <= cursor || biller.status != BillerStatus::Verified {
+                continue;
+            }
+            let haystack = Self::lowercase_bytes(&biller.name);
+            if !needle.is_empty()
+                && !haystack
+                    .windows(needle.len())
+                    .any(|w| w == needle.as_slice())
+            {
+                continue;
+            }
+            if items.len() == limit {
+                // Another match exists past this page.
+                next_cursor = last_id;
+                break;
+            }
+            last_id = id;
+            items.push_back(biller);
+        }
+
+        let count = items.len();
+        BillerPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    /// Admin for the biller registry, retention policy and contract-wide
+    /// cleanup: the upgrade admin.
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin = Self::get_upgrade_admin(env).ok_or(Error::Unauthorized)?;
+        if admin != *caller {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn load_billers(env: &Env) -> Map<u32, Biller> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_BILLERS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Check a biller's name, currencies and reference format, returning the
+    /// currency map with normalized codes.
+    fn validate_biller_config(
+        env: &Env,
+        name: &String,
+        currencies: &Map<String, Address>,
+        ref_format: &RefFormat,
+    ) -> Result<Map<String, Address>, Error> {
+        if name.is_empty() || currencies.is_empty() {
+            return Err(Error::InvalidBillerConfig);
+        }
+        if ref_format.max_len == 0
+            || ref_format.max_len > MAX_REF_LEN
+            || ref_format.min_len > ref_format.max_len
+            || ref_format.prefix.len() > ref_format.min_len
+        {
+            return Err(Error::InvalidBillerConfig);
+        }
+
+        let mut normalized = Map::new(env);
+        for (code, token) in currencies.iter() {
+            if code.is_empty() {
+                return Err(Error::InvalidCurrency);
+            }
+            Self::validate_currency(&code)?;
+            normalized.set(Self::normalize_currency(env, &code), token);
+        }
+        Ok(normalized)
+    }
+
+    /// Check `external_ref` against a biller's reference format.
+    fn check_external_ref(format: &RefFormat, external_ref: &String) -> Result<(), Error> {
+        let len = external_ref.len();
+        if len < format.min_len || len > format.max_len {
+            return Err(Error::InvalidExternalRef);
+        }
+
+        // max_len is capped at MAX_REF_LEN, so both fit on the stack.
+        let mut buf = [0u8; MAX_REF_LEN as usize];
+        external_ref.copy_into_slice(&mut buf[..len as usize]);
+        let mut prefix = [0u8; MAX_REF_LEN as usize];
+        let prefix_len = format.prefix.len() as usize;
+        format.prefix.copy_into_slice(&mut prefix[..prefix_len]);
+
+        if buf[..prefix_len] != prefix[..prefix_len] {
+            return Err(Error::InvalidExternalRef);
+        }
+        let valid = buf[prefix_len..len as usize]
+            .iter()
+            .all(|b| match format.charset {
+                RefCharset::Any => b.is_ascii_graphic(),
+                RefCharset::Numeric => b.is_ascii_digit(),
+                RefCharset::Alphanumeric => b.is_ascii_alphanumeric(),
+            });
+        if !valid {
+            return Err(Error::InvalidExternalRef);
+        }
+        Ok(())
+    }
+
+    fn lowercase_bytes(value: &String) -> StdVec<u8> {
+        let mut bytes = alloc::vec![0u8; value.len() as usize];
+        value.copy_into_slice(&mut bytes);
+        bytes.make_ascii_lowercase();
+        bytes
+    }
+
+    // -----------------------------------------------------------------------
+    // Backward-compat helpers
+    // -----------------------------------------------------------------------
+
+    /// Legacy helper: returns ALL unpaid bills for owner in one Vec.
+    /// Only safe for owners with a small number of bills. Prefer the
+    /// paginated `get_unpaid_bills` for production use.
+    pub fn get_all_unpaid_bills_legacy(env: Env, owner: Address) -> Vec<Bill> {
+        Self::owner_unpaid_bills(&env, &owner)
+    }
+
+    // -----------------------------------------------------------------------
+    // Archived bill queries (paginated)
+    // -----------------------------------------------------------------------
+
+    /// Get a page of archived bills for `owner`.
+    pub fn get_archived_bills(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> ArchivedBillPage {
+        let limit = clamp_limit(limit);
+        let ids = Self::owner_archived_ids(&env, &owner);
+
+        let mut staging: Vec<(u32, ArchivedBill)> = Vec::new(&env);
+        for id in ids.slice(Self::position_after(&ids, cursor)..).iter() {
+            let Some(bill) = Self::load_archived(&env, id) else {
+                continue;
+            };
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+
+        let has_next = staging.len() > limit;
+        let mut items = Vec::new(&env);
+        let mut next_cursor: u32 = 0;
+        let take = if has_next {
+            staging.len() - 1
+        } else {
+            staging.len()
+        };
+
+        for i in 0..take {
+            if let Some((_, bill)) = staging.get(i) {
+                items.push_back(bill);
+            }
+        }
+        if has_next {
+            if let Some((id, _)) = staging.get(take - 1) {
+                next_cursor = id;
+            }
+        }
+
+        let count = items.len();
+        ArchivedBillPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
+    pub fn get_archived_bill(env: Env, bill_id: u32) -> Option<ArchivedBill> {
+        Self::load_archived(&env, bill_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // Remaining operations
+    // -----------------------------------------------------------------------
+
+    pub fn cancel_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CANCEL_BILL)?;
+        let bill = Self::load_bill(&env, bill_id).ok_or(Error::BillNotFound)?;
+        if bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+        let removed_unpaid_amount = if bill.paid { 0 } else { bill.remaining() };
+        Self::remove_bill(&env, &bill);
+        Self::adjust_storage_stats(&env, -1, 0, 0, 0);
+        if removed_unpaid_amount > 0 {
+            Self::adjust_unpaid_total(&env, &caller, -removed_unpaid_amount);
+        }
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("canceled"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    /// Move the caller's bills paid before `before_timestamp` to the archive.
+    ///
+    /// # Returns
+    /// Number of bills archived
+    pub fn archive_paid_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let current_time = env.ledger().timestamp();
+        let mut archived_ids: Vec<u32> = Vec::new(&env);
+        let mut payer_ids: Map<Address, Vec<u32>> = Map::new(&env);
+        let mut archived_amount = 0i128;
+
+        for id in Self::owner_bill_ids(&env, &caller, false).iter() {
+            let Some(bill) = Self::load_bill(&env, id) else {
+                continue;
+            };
+            if let Some(paid_at) = bill.paid_at {
+                if bill.paid && paid_at < before_timestamp {
+                    let archived_bill = ArchivedBill {
+                        id: bill.id,
+                        owner: bill.owner.clone(),
+                        name: bill.name.clone(),
+                        external_ref: bill.external_ref.clone(),
+                        amount: bill.amount,
+                        paid_at,
+                        archived_at: current_time,
+                        tags: bill.tags.clone(),
+                        currency: bill.currency.clone(),
+                        payee: bill.payee.clone(),
+                        paid_token: bill.paid_token.clone(),
+                        biller_id: bill.biller_id,
+                        paid_by: bill.paid_by.clone(),
+                        late_fee_paid: bill.late_fee_paid,
+                    };
+                    Self::delete_bill_entry(&env, id);
+                    Self::save_archived(&env, &archived_bill);
+                    for payment in Self::load_payments(&env, id).iter() {
+                        Self::push_grouped(&env, &mut payer_ids, payment.payer, id);
+                    }
+                    archived_ids.push_back(id);
+                    archived_amount = archived_amount.saturating_add(bill.amount);
+                }
+            }
+        }
+
+        // Update each index once for the whole batch.
+        Self::remove_all_from_list(&env, &DataKey::OwnerBills(caller.clone()), &archived_ids);
+        for (payer, ids) in payer_ids.iter() {
+            Self::remove_all_from_list(&env, &DataKey::PayerBills(payer), &ids);
+        }
+        Self::add_all_to_list(&env, &DataKey::OwnerArchived(caller.clone()), &archived_ids);
+        Self::add_to_bucket(
+            &env,
+            &DataKey::ArchiveBuckets,
+            DataKey::ArchivedOn,
+            current_time / INDEX_BUCKET_SECONDS,
+            &archived_ids,
+        );
+
+        let archived_count = archived_ids.len();
+        let moved = archived_count as i32;
+        Self::adjust_storage_stats(&env, -moved, moved, 0, archived_amount);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("archived"),
+            archived_count,
+        );
+
+        Ok(archived_count)
+    }
+
+    pub fn restore_bill(env: Env, caller: Address, bill_id: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::RESTORE)?;
+        Self::extend_instance_ttl(&env);
+
+        let archived_bill = Self::load_archived(&env, bill_id).ok_or(Error::BillNotFound)?;
+
+        if archived_bill.owner != caller {
+            return Err(Error::Unauthorized);
+        }
+
+        let restored_bill = Bill {
+            id: archived_bill.id,
+            owner: archived_bill.owner.clone(),
+            name: archived_bill.name.clone(),
+            external_ref: archived_bill.external_ref.clone(),
+            amount: archived_bill.amount,
+            due_date: env.ledger().timestamp() + 2592000,
+            recurring: false,
+            frequency_days: 0,
+            paid: true,
+            created_at: archived_bill.paid_at,
+            paid_at: Some(archived_bill.paid_at),
+            schedule_id: None,
+            tags: archived_bill.tags.clone(),
+            currency: archived_bill.currency.clone(),
+            payee: archived_bill.payee.clone(),
+            token: archived_bill.paid_token.clone(),
+            amount_paid: archived_bill.amount,
+            paid_token: archived_bill.paid_token.clone(),
+            biller_id: archived_bill.biller_id,
+            auto_pay: false,
+            auto_pay_failures: 0,
+            payable_by: PayerPolicy::Owner,
+            paid_by: archived_bill.paid_by.clone(),
+            late_fee: LateFeePolicy::None,
+            late_fee_paid: archived_bill.late_fee_paid,
+            recurrence: RecurrenceRule::from_frequency(false, 0),
+            occurrence: 1,
+            amount_kind: AmountKind::Fixed,
+            approved_amount: archived_bill.amount,
+            dispute: Self::load_dispute(&env, bill_id)
+                .map(|dispute| dispute.status)
+                .unwrap_or(DisputeStatus::None),
+            reminded_days: None,
+            shares: Vec::new(&env),
+        };
+
+        Self::remove_archived(&env, &archived_bill);
+        Self::insert_bill(&env, &restored_bill);
+        Self::index_payers(&env, bill_id, true);
+        Self::adjust_storage_stats(&env, 1, -1, 0, -archived_bill.amount);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::State,
+            EventPriority::Medium,
+            symbol_short!("restored"),
+            bill_id,
+        );
+        Ok(())
+    }
+
+    /// Delete the caller's archived bills archived before `before_timestamp`
+    /// and past the retention period.
+    ///
+    /// # Returns
+    /// Number of archived bills deleted
+    pub fn bulk_cleanup_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let (deleted_count, _) = Self::cleanup_archived(&env, Some(&caller), before_timestamp);
+
+        RemitwiseEvents::emit_batch(
+            &env,
+            EventCategory::System,
+            symbol_short!("cleaned"),
+            deleted_count,
+        );
+        Ok(deleted_count)
+    }
+
+    /// Admin-only: delete archived bills of every owner archived before
+    /// `before_timestamp` and past the retention period.
+    ///
+    /// # Returns
+    /// Number of archived bills deleted
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the admin
+    ///
+    /// # Events
+    /// - `adm_clean` with `(deleted_count, owners)`, listing each affected owner once
+    pub fn admin_cleanup_bills(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let (deleted_count, owners) = Self::cleanup_archived(&env, None, before_timestamp);
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::High,
+            symbol_short!("adm_clean"),
+            (deleted_count, owners),
+        );
+        Ok(deleted_count)
+    }
+
+    /// Set how long archived bills must be kept before cleanup. Admin only.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the admin
+    pub fn set_retention_policy(
+        env: Env,
+        caller: Address,
+        policy: RetentionPolicy,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        env.storage().instance().set(&STORAGE_RETENTION, &policy);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("retention"),
+            policy.min_retention_days,
+        );
+        Ok(())
+    }
+
+    /// Get the retention policy. Archived bills may be deleted straight
+    /// away until one is set.
+    pub fn get_retention_policy(env: Env) -> RetentionPolicy {
+        env.storage()
+            .instance()
+            .get(&STORAGE_RETENTION)
+            .unwrap_or(RetentionPolicy {
+                min_retention_days: 0,
+            })
+    }
+
+    /// Delete archived bills archived before `before_timestamp` whose
+    /// retention period has passed, only `owner`'s if given. Returns the
+    /// number deleted and the distinct owners affected.
+    fn cleanup_archived(
+        env: &Env,
+        owner: Option<&Address>,
+        before_timestamp: u64,
+    ) -> (u32, Vec<Address>) {
+        let retention = (Self::get_retention_policy(env.clone()).min_retention_days as u64)
+            .saturating_mul(SECONDS_PER_DAY);
+        let now = env.ledger().timestamp();
+        let candidates = match owner {
+            Some(owner) => Self::owner_archived_ids(env, owner),
+            // Only bills archived before both cut-offs can qualify.
+            None => Self::archived_ids_before(
+                env,
+                before_timestamp.min((now + 1).saturating_sub(retention)),
+            ),
+        };
+
+        let mut deleted_count = 0u32;
+        let mut deleted_amount = 0i128;
+        let mut owners: Vec<Address> = Vec::new(env);
+        let mut owner_ids: Map<Address, Vec<u32>> = Map::new(env);
+        let mut bucket_ids: Map<u64, Vec<u32>> = Map::new(env);
+        for id in candidates.iter() {
+            let Some(bill) = Self::load_archived(env, id) else {
+                continue;
+            };
+            let retained_until = bill.archived_at.saturating_add(retention);
+            if bill.archived_at < before_timestamp && retained_until <= now {
+                if !owners.contains(&bill.owner) {
+                    owners.push_back(bill.owner.clone());
+                }
+                Self::delete_archived_entry(env, id);
+                Self::push_grouped(env, &mut owner_ids, bill.owner.clone(), id);
+                let bucket = bill.archived_at / INDEX_BUCKET_SECONDS;
+                Self::push_grouped(env, &mut bucket_ids, bucket, id);
+                deleted_count += 1;
+                deleted_amount = deleted_amount.saturating_add(bill.amount);
+            }
+        }
+
+        // Update each index once for the whole batch.
+        for (owner, ids) in owner_ids.iter() {
+            Self::remove_all_from_list(env, &DataKey::OwnerArchived(owner), &ids);
+        }
+        for (bucket, ids) in bucket_ids.iter() {
+            Self::remove_from_bucket(
+                env,
+                &DataKey::ArchiveBuckets,
+                DataKey::ArchivedOn,
+                bucket,
+                &ids,
+            );
+        }
+
+        Self::adjust_storage_stats(env, 0, -(deleted_count as i32), 0, -deleted_amount);
+        (deleted_count, owners)
+    }
+
+    /// Pay multiple bills in a single batch.
+    ///
+    /// # Semantics: Partial Success
+    /// This function implements deterministic partial result reporting. If a bill in the batch
+    /// is invalid (e.g., not found, unauthorized, or already paid), it will be skipped,
+    /// and an error event will be emitted. Other valid bills in the same batch will still be processed.
+    ///
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - Address paying the bills (must authorize; must be allowed
+    ///   to pay each bill, as in `pay_bill`)
+    /// * `bill_ids` - Vector of bill IDs to pay
+    ///
+    /// # Returns
+    /// The number of successfully paid bills.
+    ///
+    /// Bills with a payee are settled by token transfer exactly as in `pay_bill`;
+    /// a bill whose transfer cannot be made is skipped like any other invalid bill.
+    ///
+    /// # Events
+    /// - `paid`: Emitted for each successful payment.
+    /// - `settled`: Emitted for each payment transferred to a payee.
+    /// - `bill_pay_failed`: Emitted for each failed payment with (bill_id, error_code).
+    /// - `batch_pay_summary`: Emitted at the end with (success_count, failure_count).
+    pub fn batch_pay_bills(env: Env, caller: Address, bill_ids: Vec<u32>) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_BILL)?;
+
+        if bill_ids.len() > (MAX_BATCH_SIZE as usize).try_into().unwrap_or(u32::MAX) {
+            return Err(Error::BatchTooLarge);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut paid_count = 0u32;
+        let mut failed_count = 0u32;
+
+        for id in bill_ids.iter() {
+            let bill_result = Self::load_bill(&env, id);
+
+            // Validation logic for each bill
+            let mut bill = match bill_result {
+                Some(b) => b,
+                None => {
+                    failed_count += 1;
+                    RemitwiseEvents::emit(
+                        &env,
+                        EventCategory::Transaction,
+                        EventPriority::Medium,
+                        symbol_short!("f_pay_id"), // fail_pay_id
+                        (id, Error::BillNotFound as u32),
+                    );
+                    continue;
+                }
+            };
+
+            if !Self::can_pay(&bill, &caller) {
+                failed_count += 1;
+                RemitwiseEvents::emit(
+                    &env,
+                    EventCategory::Transaction,
+                    EventPriority::Medium,
+                    symbol_short!("fpay_auth"), // fail_pay_auth
+                    (id, Error::Unauthorized as u32),
+                );
+                continue;
+            }
+
+            if bill.paid {
+                failed_count += 1;
+                RemitwiseEvents::emit(
+                    &env,
+                    EventCategory::Transaction,
+                    EventPriority::Medium,
+                    symbol_short!("f_pay_pd"), // fail_pay_paid
+                    (id, Error::BillAlreadyPaid as u32),
+                );
+                continue;
+            }
+
+            if !bill.shares.is_empty() {
+                failed_count += 1;
+                RemitwiseEvents::emit(
+                    &env,
+                    EventCategory::Transaction,
+                    EventPriority::Medium,
+                    symbol_short!("f_pay_spl"), // fail_pay_split
+                    (id, Error::BillIsSplit as u32),
+                );
+                continue;
+            }
+
+            let amount = bill.amount_due_now(env.ledger().timestamp());
+            let paid_token = match Self::settle_payment(&env, &caller, &bill, amount) {
+                Ok(token) => token,
+                Err(err) => {
+                    failed_count += 1;
+                    RemitwiseEvents::emit(
+                        &env,
+                        EventCategory::Transaction,
+                        EventPriority::Medium,
+                        symbol_short!("f_pay_xfr"), // fail_pay_transfer
+                        (id, err as u32),
+                    );
+                    continue;
+                }
+            };
+
+            // Process payment
+            let payment = BillPayment {
+                payer: caller.clone(),
+                amount,
+                token: paid_token,
+                paid_at: env.ledger().timestamp(),
+            };
+            let delta = Self::apply_payment(&env, &mut bill, payment)?;
+            Self::adjust_unpaid_total(&env, &bill.owner, delta);
+
+            Self::save_bill(&env, &bill);
+            paid_count += 1;
+
+            Self::emit_paid(&env, id, &bill.owner, amount, &caller);
+        }
+
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("batch_res"), // batch_result
+            (paid_count, failed_count),
+        );
+
+        Ok(paid_count)
+    }
+
+    pub fn get_total_unpaid(env: Env, owner: Address) -> i128 {
+        if let Some(total) = Self::stored_unpaid_total(&env, &owner) {
+            return total;
+        }
+
+        let mut total = 0i128;
+        for bill in Self::owner_unpaid_bills(&env, &owner).iter() {
+            total += bill.remaining();
+        }
+        total
+    }
+
+    /// Get `owner`'s unpaid total split into confirmed and estimated amounts.
+    ///
+    /// The two parts add up to `get_total_unpaid`.
+    pub fn get_unpaid_breakdown(env: Env, owner: Address) -> UnpaidBreakdown {
+        let mut breakdown = UnpaidBreakdown {
+            actual: 0,
+            estimated: 0,
+        };
+        for bill in Self::owner_unpaid_bills(&env, &owner).iter() {
+            if bill.amount_kind == AmountKind::Estimated {
+                breakdown.estimated += bill.remaining();
+            } else {
+                breakdown.actual += bill.remaining();
+            }
+        }
+        breakdown
+    }
+
+    pub fn get_storage_stats(env: Env) -> StorageStats {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STOR_STAT"))
+            .unwrap_or(StorageStats {
+                active_bills: 0,
+                archived_bills: 0,
+                total_unpaid_amount: 0,
+                total_archived_amount: 0,
+                last_updated: 0,
+            })
+    }
+
+    /// Moves bills, archived bills, payment histories and unpaid totals from
+    /// the legacy instance maps (`BILLS`, `ARCH_BILL`, `PAYMENTS`,
+    /// `UNPD_TOT`) into per-entry persistent storage and builds their indexes.
+    ///
+    /// Works in batches of at most `max_items` entries (0 -> DEFAULT_PAGE_LIMIT,
+    /// capped at MAX_PAGE_LIMIT) so large deployments can migrate across
+    /// several transactions. Until migration finishes, reads fall back to the
+    /// legacy maps, so the contract stays usable throughout. A legacy entry
+    /// already shadowed by a persistent one (written since the upgrade) is
+    /// dropped rather than copied.
+    ///
+    /// # Returns
+    /// Number of legacy entries still waiting to be migrated; 0 when done.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the upgrade admin
+    ///
+    /// # Events
+    /// - Emits a System `migrated` event with `(moved, remaining)`.
+    pub fn migrate_storage(env: Env, caller: Address, max_items: u32) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let budget = clamp_limit(max_items);
+        let mut moved = 0u32;
+        // The legacy layout recounted `STOR_STAT` from the maps, so migrated
+        // entries are already counted there.
+
+        let mut legacy_bills = Self::legacy_bills(&env).unwrap_or_else(|| Map::new(&env));
+        for bill_id in legacy_bills.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            if let Some(legacy) = legacy_bills.get(bill_id) {
+                let bill = match env
+                    .storage()
+                    .persistent()
+                    .get::<_, Bill>(&DataKey::Bill(bill_id))
+                {
+                    Some(existing) => existing,
+                    None => {
+                        let bill = Self::upgrade_legacy_bill(&env, legacy);
+                        Self::save_bill(&env, &bill);
+                        bill
+                    }
+                };
+                Self::index_bill(&env, &bill);
+            }
+            legacy_bills.remove(bill_id);
+            moved += 1;
+        }
+        Self::put_legacy(&env, &STORAGE_BILLS, &legacy_bills);
+
+        let mut legacy_archived = Self::legacy_archived(&env).unwrap_or_else(|| Map::new(&env));
+        for bill_id in legacy_archived.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            let shadowed = env
+                .storage()
+                .persistent()
+                .has(&DataKey::ArchivedBill(bill_id));
+            if let Some(legacy) = legacy_archived.get(bill_id).filter(|_| !shadowed) {
+                Self::insert_archived(&env, &Self::upgrade_legacy_archived(legacy));
+            }
+            legacy_archived.remove(bill_id);
+            moved += 1;
+        }
+        Self::put_legacy(&env, &STORAGE_ARCHIVED, &legacy_archived);
+
+        let mut legacy_payments: Map<u32, Vec<BillPayment>> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_PAYMENTS)
+            .unwrap_or_else(|| Map::new(&env));
+        for bill_id in legacy_payments.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            let key = DataKey::Payments(bill_id);
+            if !env.storage().persistent().has(&key) {
+                if let Some(payments) = legacy_payments.get(bill_id) {
+                    Self::save_list(&env, &key, &payments);
+                }
+            }
+            // Only payments towards active bills are in the payer index.
+            if Self::load_bill(&env, bill_id).is_some() {
+                Self::index_payers(&env, bill_id, true);
+            }
+            legacy_payments.remove(bill_id);
+            moved += 1;
+        }
+        Self::put_legacy(&env, &STORAGE_PAYMENTS, &legacy_payments);
+
+        let mut legacy_totals: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_UNPAID_TOTALS)
+            .unwrap_or_else(|| Map::new(&env));
+        for owner in legacy_totals.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            let key = DataKey::UnpaidTotal(owner.clone());
+            if !env.storage().persistent().has(&key) {
+                let total = legacy_totals.get(owner.clone()).unwrap_or(0);
+                env.storage().persistent().set(&key, &total);
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+            }
+            legacy_totals.remove(owner);
+            moved += 1;
+        }
+        Self::put_legacy(&env, &STORAGE_UNPAID_TOTALS, &legacy_totals);
+
+        let remaining = legacy_bills.len()
+            + legacy_archived.len()
+            + legacy_payments.len()
+            + legacy_totals.len();
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("migrated"),
+            (moved, remaining),
+        );
+        Ok(remaining)
+    }
+
+    // -----------------------------------------------------------------------
+    // Currency-filter helper queries
+    // -----------------------------------------------------------------------
+
+    /// Get a page of ALL bills (paid + unpaid) for `owner` that match `currency`.
+    ///
+    /// # Arguments
+    /// * `owner`    – Address of the bill owner
+    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
+    /// * `cursor`   – Start after this bill ID (pass 0 for the first page)
+    /// * `limit`    – Max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
+    ///
+    /// # Currency Comparison
+    /// Currency comparison is case-insensitive and whitespace-insensitive:
+    /// - "usdc", "USDC", "UsDc", " usdc " all match
+    /// - Empty currency defaults to "XLM" for comparison
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// // Get all USDC bills for owner
+    /// let page = client.get_bills_by_currency(&owner, &"USDC".into(), &0, &10);
+    /// ```
+    pub fn get_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = clamp_limit(limit);
+        let normalized_currency = Self::normalize_currency(&env, &currency);
+        let ids = Self::owner_bill_ids(&env, &owner, false);
+        Self::page_from_ids(&env, &ids, cursor, limit, |bill| {
+            bill.currency == normalized_currency
+        })
+    }
+
+    /// Get a page of **unpaid** bills for `owner` that match `currency`.
+    ///
+    /// # Arguments
+    /// * `owner`    – Address of the bill owner
+    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
+    /// * `cursor`   – Start after this bill ID (pass 0 for the first page)
+    /// * `limit`    – Max items per page (0 → DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Returns
+    /// `BillPage { items, next_cursor, count }`. `next_cursor == 0` means no more pages.
+    ///
+    /// # Currency Comparison
+    /// Currency comparison is case-insensitive and whitespace-insensitive:
+    /// - "usdc", "USDC", "UsDc", " usdc " all match
+    /// - Empty currency defaults to "XLM" for comparison
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// // Get unpaid USDC bills for owner
+    /// let page = client.get_unpaid_bills_by_currency(&owner, &"USDC".into(), &0, &10);
+    /// ```
+    pub fn get_unpaid_bills_by_currency(
+        env: Env,
+        owner: Address,
+        currency: String,
+        cursor: u32,
+        limit: u32,
+    ) -> BillPage {
+        let limit = clamp_limit(limit);
+        let normalized_currency = Self::normalize_currency(&env, &currency);
+        let ids = Self::owner_bill_ids(&env, &owner, true);
+        Self::page_from_ids(&env, &ids, cursor, limit, |bill| {
+            !bill.paid && bill.currency == normalized_currency
+        })
+    }
+
+    /// Sum of all **unpaid** bill amounts for `owner` denominated in `currency`.
+    ///
+    /// # Arguments
+    /// * `owner`    – Address of the bill owner
+    /// * `currency` – Currency code to filter by, e.g. `"USDC"`, `"XLM"`
+    ///
+    /// # Returns
+    /// Total unpaid amount in the specified currency
+    ///
+    /// # Currency Comparison
+    /// Currency comparison is case-insensitive and whitespace-insensitive:
+    /// - "usdc", "USDC", "UsDc", " usdc " all match
+    /// - Empty currency defaults to "XLM" for comparison
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// // Get total unpaid amount in USDC
+    /// let total_usdc = client.get_total_unpaid_by_currency(&owner, &"USDC".into());
+    /// // Get total unpaid amount in XLM
+    /// let total_xlm = client.get_total_unpaid_by_currency(&owner, &"XLM".into());
+    /// ```
+    pub fn get_total_unpaid_by_currency(env: Env, owner: Address, currency: String) -> i128 {
+        let normalized_currency = Self::normalize_currency(&env, &currency);
+        let mut total = 0i128;
+        for bill in Self::owner_unpaid_bills(&env, &owner).iter() {
+            if bill.currency == normalized_currency {
+                total += bill.remaining();
+            }
+        }
+        total
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Applies deltas to the running `StorageStats`.
+    fn adjust_storage_stats(
+        env: &Env,
+        active_delta: i32,
+        archived_delta: i32,
+        unpaid_delta: i128,
+        archived_amount_delta: i128,
+    ) {
+        let mut stats = Self::get_storage_stats(env.clone());
+        stats.active_bills = stats.active_bills.saturating_add_signed(active_delta);
+        stats.archived_bills = stats.archived_bills.saturating_add_signed(archived_delta);
+        stats.total_unpaid_amount = stats.total_unpaid_amount.saturating_add(unpaid_delta);
+        stats.total_archived_amount = stats
+            .total_archived_amount
+            .saturating_add(archived_amount_delta);
+        stats.last_updated = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STOR_STAT"), &stats);
+    }
+
+    fn stored_unpaid_total(env: &Env, owner: &Address) -> Option<i128> {
+        let total: Option<i128> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UnpaidTotal(owner.clone()));
+        if total.is_some() {
+            return total;
+        }
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, i128>>(&STORAGE_UNPAID_TOTALS)
+            .and_then(|legacy| legacy.get(owner.clone()))
+    }
+
+    fn adjust_unpaid_total(env: &Env, owner: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        let current = Self::stored_unpaid_total(env, owner).unwrap_or(0);
+        let next = current.checked_add(delta).expect("overflow");
+        let key = DataKey::UnpaidTotal(owner.clone());
+        env.storage().persistent().set(&key, &next);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::adjust_storage_stats(env, 0, 0, delta, 0);
+    }
+
+    // -----------------------------------------------------------------------
+    // Per-entry storage
+    // -----------------------------------------------------------------------
+
+    /// Loads a bill from its persistent entry, falling back to the legacy
+    /// instance map for bills `migrate_storage` has not moved yet.
+    fn load_bill(env: &Env, bill_id: u32) -> Option<Bill> {
+        let bill: Option<Bill> = env.storage().persistent().get(&DataKey::Bill(bill_id));
+        if bill.is_some() {
+            return bill;
+        }
+        Self::legacy_bills(env)
+            .and_then(|legacy| legacy.get(bill_id))
+            .map(|legacy| Self::upgrade_legacy_bill(env, legacy))
+    }
+
+    fn save_bill(env: &Env, bill: &Bill) {
+        let key = DataKey::Bill(bill.id);
+        env.storage().persistent().set(&key, bill);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn legacy_bills(env: &Env) -> Option<Map<u32, LegacyBill>> {
+        env.storage().instance().get(&STORAGE_BILLS)
+    }
+
+    /// Fills the fields a legacy bill predates with the values of a bill
+    /// that was created, and if paid, paid in full by its owner off-chain.
+    fn upgrade_legacy_bill(env: &Env, legacy: LegacyBill) -> Bill {
+        Bill {
+            id: legacy.id,
+            owner: legacy.owner.clone(),
+            name: legacy.name,
+            external_ref: legacy.external_ref,
+            amount: legacy.amount,
+            due_date: legacy.due_date,
+            recurring: legacy.recurring,
+            frequency_days: legacy.frequency_days,
+            paid: legacy.paid,
+            created_at: legacy.created_at,
+            paid_at: legacy.paid_at,
+            schedule_id: legacy.schedule_id,
+            tags: legacy.tags,
+            currency: legacy.currency,
+            payee: None,
+            token: None,
+            amount_paid: if legacy.paid { legacy.amount } else { 0 },
+            paid_token: None,
+            biller_id: None,
+            auto_pay: false,
+            auto_pay_failures: 0,
+            payable_by: PayerPolicy::Owner,
+            paid_by: legacy.paid.then_some(legacy.owner),
+            late_fee: LateFeePolicy::None,
+            late_fee_paid: 0,
+            recurrence: RecurrenceRule::from_frequency(legacy.recurring, legacy.frequency_days),
+            occurrence: 1,
+            amount_kind: AmountKind::Fixed,
+            approved_amount: legacy.amount,
+            dispute: DisputeStatus::None,
+            reminded_days: None,
+            shares: Vec::new(env),
+        }
+    }
+
+    /// Stores a bill that is not in the indexes yet and indexes it.
+    fn insert_bill(env: &Env, bill: &Bill) {
+        Self::save_bill(env, bill);
+        Self::index_bill(env, bill);
+    }
+
+    fn index_bill(env: &Env, bill: &Bill) {
+        Self::add_to_list(env, &DataKey::OwnerBills(bill.owner.clone()), bill.id);
+        if !bill.paid {
+            Self::add_to_list(env, &DataKey::OwnerUnpaid(bill.owner.clone()), bill.id);
+            Self::add_to_bucket(
+                env,
+                &DataKey::DueBuckets,
+                DataKey::DueBills,
+                bill.due_date / INDEX_BUCKET_SECONDS,
+                &Vec::from_array(env, [bill.id]),
+            );
+            if bill.auto_pay {
+                Self::index_auto_pay(env, bill, true);
+            }
+        }
+    }
+
+    /// Drops a bill from the unpaid and due-date indexes.
+    fn unindex_unpaid(env: &Env, bill: &Bill) {
+        Self::remove_from_list(env, &DataKey::OwnerUnpaid(bill.owner.clone()), bill.id);
+        Self::remove_from_bucket(
+            env,
+            &DataKey::DueBuckets,
+            DataKey::DueBills,
+            bill.due_date / INDEX_BUCKET_SECONDS,
+            &Vec::from_array(env, [bill.id]),
+        );
+        if bill.auto_pay {
+            Self::index_auto_pay(env, bill, false);
+        }
+    }
+
+    /// Adds an unpaid bill to, or drops it from, the auto-pay due-date index.
+    fn index_auto_pay(env: &Env, bill: &Bill, add: bool) {
+        let bucket = bill.due_date / INDEX_BUCKET_SECONDS;
+        let ids = Vec::from_array(env, [bill.id]);
+        if add {
+            Self::add_to_bucket(
+                env,
+                &DataKey::AutoPayBuckets,
+                DataKey::AutoPayDue,
+                bucket,
+                &ids,
+            );
+        } else {
+            Self::remove_from_bucket(
+                env,
+                &DataKey::AutoPayBuckets,
+                DataKey::AutoPayDue,
+                bucket,
+                &ids,
+            );
+        }
+    }
+
+    /// Deletes an active bill, including any legacy copy, and its index
+    /// entries. The payment history is kept.
+    fn remove_bill(env: &Env, bill: &Bill) {
+        Self::delete_bill_entry(env, bill.id);
+        Self::remove_from_list(env, &DataKey::OwnerBills(bill.owner.clone()), bill.id);
+        if !bill.paid {
+            Self::unindex_unpaid(env, bill);
+        }
+        Self::index_payers(env, bill.id, false);
+        Self::index_shares(env, bill, false);
+    }
+
+    /// Deletes a bill's entry and any legacy copy, leaving the indexes alone.
+    fn delete_bill_entry(env: &Env, bill_id: u32) {
+        env.storage().persistent().remove(&DataKey::Bill(bill_id));
+        if let Some(mut legacy) = Self::legacy_bills(env) {
+            if legacy.remove(bill_id).is_some() {
+                Self::put_legacy(env, &STORAGE_BILLS, &legacy);
+            }
+        }
+    }
+
+    /// IDs of an owner's bills, or only the unpaid ones, merged with legacy
+    /// bills not migrated yet. A legacy copy may be stale, so callers
+    /// re-check the loaded bill.
+    fn owner_bill_ids(env: &Env, owner: &Address, unpaid_only: bool) -> Vec<u32> {
+        let key = if unpaid_only {
+            DataKey::OwnerUnpaid(owner.clone())
+        } else {
+            DataKey::OwnerBills(owner.clone())
+        };
+        let mut ids = Self::load_list(env, &key);
+        if let Some(legacy) = Self::legacy_bills(env) {
+            for (id, bill) in legacy.iter() {
+                if bill.owner == *owner && !(unpaid_only && bill.paid) {
+                    Self::insert_sorted(&mut ids, id);
+                }
+            }
+        }
+        ids
+    }
+
+    fn owner_unpaid_bills(env: &Env, owner: &Address) -> Vec<Bill> {
+        let mut bills = Vec::new(env);
+        for id in Self::owner_bill_ids(env, owner, true).iter() {
+            if let Some(bill) = Self::load_bill(env, id) {
+                if !bill.paid {
+                    bills.push_back(bill);
+                }
+            }
+        }
+        bills
+    }
+
+    /// Visits the bills in a due-date bucket index from the bucket holding
+    /// `from` to the one holding `until`: earliest bucket first, ascending by
+    /// ID within a bucket. Unpaid bills in `legacy` are merged in by due date.
+    ///
+    /// A non-zero `cursor` resumes the walk right after that bill in its due
+    /// date's bucket; if the bill has since been deleted the walk starts over.
+    /// `visit` returns false to stop. Returns the last visited ID if
+    /// `MAX_SCAN_IDS` entries were visited before the walk was done.
+    #[allow(clippy::too_many_arguments)]
+    fn walk_due_index(
+        env: &Env,
+        buckets: &DataKey,
+        entry: fn(u64) -> DataKey,
+        legacy: Option<Map<u32, LegacyBill>>,
+        from: u64,
+        until: u64,
+        cursor: u32,
+        mut visit: impl FnMut(Bill) -> bool,
+    ) -> Option<u32> {
+        let last_bucket = until / INDEX_BUCKET_SECONDS;
+        let resume = if cursor == 0 {
+            None
+        } else {
+            Self::load_bill(env, cursor).map(|bill| bill.due_date / INDEX_BUCKET_SECONDS)
+        };
+        let first_bucket = (from / INDEX_BUCKET_SECONDS).max(resume.unwrap_or(0));
+
+        let mut bucket_list: Vec<u64> = Self::load_list(env, buckets);
+        let mut legacy_ids: Map<u64, Vec<u32>> = Map::new(env);
+        if let Some(legacy) = legacy {
+            for (id, bill) in legacy.iter() {
+                let bucket = bill.due_date / INDEX_BUCKET_SECONDS;
+                if !bill.paid && bucket >= first_bucket && bucket <= last_bucket {
+                    Self::insert_sorted(&mut bucket_list, bucket);
+                    Self::push_grouped(env, &mut legacy_ids, bucket, id);
+                }
+            }
+        }
+
+        let start = match bucket_list.binary_search(first_bucket) {
+            Ok(i) | Err(i) => i,
+        };
+        let mut scanned = 0u32;
+        let mut last_visited = 0u32;
+        for bucket in bucket_list.slice(start..).iter() {
+            if bucket > last_bucket {
+                break;
+            }
+            let mut ids: Vec<u32> = Self::load_list(env, &entry(bucket));
+            for id in legacy_ids
+                .get(bucket)
+                .unwrap_or_else(|| Vec::new(env))
+                .iter()
+            {
+                Self::insert_sorted(&mut ids, id);
+            }
+            let skip = if resume == Some(bucket) {
+                Self::position_after(&ids, cursor)
+            } else {
+                0
+            };
+            for id in ids.slice(skip..).iter() {
+                if scanned == MAX_SCAN_IDS {
+                    return Some(last_visited);
+                }
+                scanned += 1;
+                last_visited = id;
+                let Some(bill) = Self::load_bill(env, id) else {
+                    continue;
+                };
+                if !visit(bill) {
+                    return None;
+                }
+            }
+        }
+        None
+    }
+
+    /// Index of the first entry in ascending `ids` greater than `cursor`.
+    fn position_after(ids: &Vec<u32>, cursor: u32) -> u32 {
+        match ids.binary_search(cursor) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        }
+    }
+
+    /// Pages through the bills in ascending `ids` after `cursor`, keeping
+    /// those that match `filter`.
+    fn page_from_ids(
+        env: &Env,
+        ids: &Vec<u32>,
+        cursor: u32,
+        limit: u32,
+        filter: impl Fn(&Bill) -> bool,
+    ) -> BillPage {
+        let mut staging: Vec<(u32, Bill)> = Vec::new(env);
+        for id in ids.slice(Self::position_after(ids, cursor)..).iter() {
+            let Some(bill) = Self::load_bill(env, id) else {
+                continue;
+            };
+            if !filter(&bill) {
+                continue;
+            }
+            staging.push_back((id, bill));
+            if staging.len() > limit {
+                break;
+            }
+        }
+        Self::build_page(env, staging, limit)
+    }
+
+    fn load_payments(env: &Env, bill_id: u32) -> Vec<BillPayment> {
+        let payments: Option<Vec<BillPayment>> =
+            env.storage().persistent().get(&DataKey::Payments(bill_id));
+        if let Some(payments) = payments {
+            return payments;
+        }
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Vec<BillPayment>>>(&STORAGE_PAYMENTS)
+            .and_then(|legacy| legacy.get(bill_id))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn record_payment(env: &Env, bill_id: u32, payment: BillPayment) {
+        Self::add_to_list(env, &DataKey::PayerBills(payment.payer.clone()), bill_id);
+        let mut payments = Self::load_payments(env, bill_id);
+        payments.push_back(payment);
+        Self::save_list(env, &DataKey::Payments(bill_id), &payments);
+    }
+
+    /// Adds or removes a bill in the `PayerBills` index of everyone who has
+    /// paid towards it.
+    fn index_payers(env: &Env, bill_id: u32, present: bool) {
+        for payment in Self::load_payments(env, bill_id).iter() {
+            let key = DataKey::PayerBills(payment.payer);
+            if present {
+                Self::add_to_list(env, &key, bill_id);
+            } else {
+                Self::remove_from_list(env, &key, bill_id);
+            }
+        }
+    }
+
+    /// Adds or removes a bill in the `ShareBills` index of every participant
+    /// other than the owner whose share is unpaid.
+    fn index_shares(env: &Env, bill: &Bill, present: bool) {
+        for share in bill.shares.iter() {
+            if share.paid || share.participant == bill.owner {
+                continue;
+            }
+            let key = DataKey::ShareBills(share.participant);
+            if present {
+                Self::add_to_list(env, &key, bill.id);
+            } else {
+                Self::remove_from_list(env, &key, bill.id);
+            }
+        }
+    }
+
+    fn load_dispute(env: &Env, bill_id: u32) -> Option<Dispute> {
+        env.storage().persistent().get(&DataKey::Dispute(bill_id))
+    }
+
+    fn save_dispute(env: &Env, dispute: &Dispute) {
+        let key = DataKey::Dispute(dispute.bill_id);
+        env.storage().persistent().set(&key, dispute);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Appends a closed dispute to its bill's history before a new dispute
+    /// replaces it.
+    fn push_past_dispute(env: &Env, dispute: &Dispute) {
+        let count_key = DataKey::PastDisputeCount(dispute.bill_id);
+        let seq: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let key = DataKey::PastDispute(dispute.bill_id, seq);
+        env.storage().persistent().set(&key, dispute);
+        env.storage().persistent().set(&count_key, &(seq + 1));
+        for key in [key, count_key] {
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+    }
+
+    /// Moves a dispute to `status` and mirrors it onto the bill while the
+    /// bill is still active.
+    fn set_dispute_status(env: &Env, dispute: &mut Dispute, status: DisputeStatus) {
+        let now = env.ledger().timestamp();
+        dispute.status = status;
+        dispute.updated_at = now;
+        if !status.is_pending() {
+            dispute.resolved_at = Some(now);
+        }
+        Self::save_dispute(env, dispute);
+        if let Some(mut bill) = Self::load_bill(env, dispute.bill_id) {
+            bill.dispute = status;
+            Self::save_bill(env, &bill);
+        }
+    }
+
+    /// Transfers each of a dispute's refunds from `from`, the payee or the
+    /// contract holding the escrow.
+    fn pay_refunds(env: &Env, dispute: &Dispute, from: &Address) -> Result<(), Error> {
+        let client = TokenClient::new(env, &dispute.token);
+        for (payer, amount) in dispute.refunds.iter() {
+            Self::transfer(&client, from, &payer, amount)?;
+        }
+        Ok(())
+    }
+
+    /// Splits `amount` between `shares`, which add up to `total`, pro rata.
+    /// The rounding remainder goes to the last payer.
+    fn split_pro_rata(
+        env: &Env,
+        amount: i128,
+        shares: &Map<Address, i128>,
+        total: i128,
+    ) -> Result<Map<Address, i128>, Error> {
+        let mut split = Map::new(env);
+        let mut left = amount;
+        for (i, (payer, share)) in shares.iter().enumerate() {
+            let part = if i as u32 + 1 == shares.len() {
+                left
+            } else {
+                amount.checked_mul(share).ok_or(Error::InvalidAmount)? / total
+            };
+            if part > 0 {
+                split.set(payer, part);
+            }
+            left -= part;
+        }
+        Ok(split)
+    }
+
+    /// Adds up two per-payer amount maps.
+    fn merge_amounts(a: &Map<Address, i128>, b: &Map<Address, i128>) -> Map<Address, i128> {
+        let mut merged = a.clone();
+        for (payer, amount) in b.iter() {
+            let total = merged.get(payer.clone()).unwrap_or(0);
+            merged.set(payer, total.saturating_add(amount));
+        }
+        merged
+    }
+
+    fn load_archived(env: &Env, bill_id: u32) -> Option<ArchivedBill> {
+        let bill: Option<ArchivedBill> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ArchivedBill(bill_id));
+        if bill.is_some() {
+            return bill;
+        }
+        Self::legacy_archived(env)
+            .and_then(|legacy| legacy.get(bill_id))
+            .map(Self::upgrade_legacy_archived)
+    }
+
+    fn legacy_archived(env: &Env) -> Option<Map<u32, LegacyArchivedBill>> {
+        env.storage().instance().get(&STORAGE_ARCHIVED)
+    }
+
+    fn upgrade_legacy_archived(legacy: LegacyArchivedBill) -> ArchivedBill {
+        ArchivedBill {
+            id: legacy.id,
+            owner: legacy.owner.clone(),
+            name: legacy.name,
+            external_ref: legacy.external_ref,
+            amount: legacy.amount,
+            paid_at: legacy.paid_at,
+            archived_at: legacy.archived_at,
+            tags: legacy.tags,
+            currency: legacy.currency,
+            payee: None,
+            paid_token: None,
+            biller_id: None,
+            paid_by: Some(legacy.owner),
+            late_fee_paid: 0,
+        }
+    }
+
+    /// Stores an archived bill with the archive TTL and indexes it by owner
+    /// and archive date.
+    fn insert_archived(env: &Env, bill: &ArchivedBill) {
+        Self::save_archived(env, bill);
+        Self::add_to_list(env, &DataKey::OwnerArchived(bill.owner.clone()), bill.id);
+        Self::add_to_bucket(
+            env,
+            &DataKey::ArchiveBuckets,
+            DataKey::ArchivedOn,
+            bill.archived_at / INDEX_BUCKET_SECONDS,
+            &Vec::from_array(env, [bill.id]),
+        );
+    }
+
+    fn save_archived(env: &Env, bill: &ArchivedBill) {
+        let key = DataKey::ArchivedBill(bill.id);
+        env.storage().persistent().set(&key, bill);
+        env.storage().persistent().extend_ttl(
+            &key,
+            ARCHIVE_LIFETIME_THRESHOLD,
+            ARCHIVE_BUMP_AMOUNT,
+        );
+    }
+
+    fn remove_archived(env: &Env, bill: &ArchivedBill) {
+        Self::delete_archived_entry(env, bill.id);
+        Self::remove_from_list(env, &DataKey::OwnerArchived(bill.owner.clone()), bill.id);
+        Self::remove_from_bucket(
+            env,
+            &DataKey::ArchiveBuckets,
+            DataKey::ArchivedOn,
+            bill.archived_at / INDEX_BUCKET_SECONDS,
+            &Vec::from_array(env, [bill.id]),
+        );
+    }
+
+    /// Deletes an archived bill's entry and any legacy copy, leaving the
+    /// indexes alone.
+    fn delete_archived_entry(env: &Env, bill_id: u32) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ArchivedBill(bill_id));
+        if let Some(mut legacy) = Self::legacy_archived(env) {
+            if legacy.remove(bill_id).is_some() {
+                Self::put_legacy(env, &STORAGE_ARCHIVED, &legacy);
+            }
+        }
+    }
+
+    fn owner_archived_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        let mut ids = Self::load_list(env, &DataKey::OwnerArchived(owner.clone()));
+        if let Some(legacy) = Self::legacy_archived(env) {
+            for (id, bill) in legacy.iter() {
+                if bill.owner == *owner {
+                    Self::insert_sorted(&mut ids, id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// IDs of bills in the archive-date buckets starting before `cutoff`.
+    fn archived_ids_before(env: &Env, cutoff: u64) -> Vec<u32> {
+        let mut ids = Vec::new(env);
+        let buckets: Vec<u64> = Self::load_list(env, &DataKey::ArchiveBuckets);
+        for bucket in buckets.iter() {
+            if bucket.saturating_mul(INDEX_BUCKET_SECONDS) >= cutoff {
+                break;
+            }
+            let bucket_ids: Vec<u32> = Self::load_list(env, &DataKey::ArchivedOn(bucket));
+            for id in bucket_ids.iter() {
+                Self::insert_sorted(&mut ids, id);
+            }
+        }
+        if let Some(legacy) = Self::legacy_archived(env) {
+            for (id, bill) in legacy.iter() {
+                if bill.archived_at < cutoff {
+                    Self::insert_sorted(&mut ids, id);
+                }
+            }
+        }
+        ids
+    }
+
+    /// Writes back a legacy instance map, dropping the key once it is empty.
+    fn put_legacy<K, V>(env: &Env, key: &Symbol, map: &Map<K, V>)
+    where
+        K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+        V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        if map.is_empty() {
+            env.storage().instance().remove(key);
+        } else {
+            env.storage().instance().set(key, map);
+        }
+    }
+
+    fn load_list<T>(env: &Env, key: &DataKey) -> Vec<T>
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        env.storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Saves a list entry, removing it once the list is empty.
+    fn save_list<T>(env: &Env, key: &DataKey, list: &Vec<T>)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        if list.is_empty() {
+            env.storage().persistent().remove(key);
+            return;
+        }
+        env.storage().persistent().set(key, list);
+        env.storage().persistent().extend_ttl(
+            key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Inserts `item` keeping `list` ascending. Returns false if it was
+    /// already there.
+    fn insert_sorted<T>(list: &mut Vec<T>, item: T) -> bool
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    {
+        match list.binary_search(item.clone()) {
+            Ok(_) => false,
+            Err(i) => {
+                list.insert(i, item);
+                true
+            }
+        }
+    }
+
+    fn add_to_list<T>(env: &Env, key: &DataKey, item: T)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    {
+        let mut list = Self::load_list(env, key);
+        if Self::insert_sorted(&mut list, item) {
+            Self::save_list(env, key, &list);
+        }
+    }
+
+    fn remove_from_list<T>(env: &Env, key: &DataKey, item: T)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let mut list: Vec<T> = Self::load_list(env, key);
+        if let Ok(i) = list.binary_search(item) {
+            list.remove(i);
+            Self::save_list(env, key, &list);
+        }
+    }
+
+    /// Inserts `items` into a sorted ID list, saving it once.
+    fn add_all_to_list(env: &Env, key: &DataKey, items: &Vec<u32>) {
+        if items.is_empty() {
+            return;
+        }
+        let mut list = Self::load_list(env, key);
+        let mut changed = false;
+        for item in items.iter() {
+            changed |= Self::insert_sorted(&mut list, item);
+        }
+        if changed {
+            Self::save_list(env, key, &list);
+        }
+    }
+
+    /// Removes `items` from a sorted ID list, saving it once.
+    fn remove_all_from_list(env: &Env, key: &DataKey, items: &Vec<u32>) {
+        if items.is_empty() {
+            return;
+        }
+        let mut list: Vec<u32> = Self::load_list(env, key);
+        let mut changed = false;
+        for item in items.iter() {
+            if let Ok(i) = list.binary_search(item) {
+                list.remove(i);
+                changed = true;
+            }
+        }
+        if changed {
+            Self::save_list(env, key, &list);
+        }
+    }
+
+    /// Appends `id` to the group for `key`, for a later batched index update.
+    fn push_grouped<K>(env: &Env, groups: &mut Map<K, Vec<u32>>, key: K, id: u32)
+    where
+        K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    {
+        let mut ids = groups.get(key.clone()).unwrap_or_else(|| Vec::new(env));
+        ids.push_back(id);
+        groups.set(key, ids);
+    }
+
+    /// Adds `ids` to a time bucket, registering the bucket in `buckets` when
+    /// the bucket is first used.
+    fn add_to_bucket(
+        env: &Env,
+        buckets: &DataKey,
+        entry: fn(u64) -> DataKey,
+        bucket: u64,
+        ids: &Vec<u32>,
+    ) {
+        if ids.is_empty() {
+            return;
+        }
+        let key = entry(bucket);
+        if !env.storage().persistent().has(&key) {
+            Self::add_to_list(env, buckets, bucket);
+        }
+        Self::add_all_to_list(env, &key, ids);
+    }
+
+    /// Removes `ids` from a time bucket, unregistering the bucket once it is
+    /// empty.
+    fn remove_from_bucket(
+        env: &Env,
+        buckets: &DataKey,
+        entry: fn(u64) -> DataKey,
+        bucket: u64,
+        ids: &Vec<u32>,
+    ) {
+        let key = entry(bucket);
+        Self::remove_all_from_list(env, &key, ids);
+        if !env.storage().persistent().has(&key) {
+            Self::remove_from_list(env, buckets, bucket);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Events, Ledger},
+        Env, String,
+    };
+
+    fn make_env() -> Env {
+        Env::default()
+    }
+
+    /// Create `count` bills with a static name. Returns their IDs.
+    /// Due dates are set in the future so they are NOT overdue.
+    fn setup_bills(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        count: u32,
+    ) -> Vec<u32> {
+        let mut ids = Vec::new(env);
+        for i in 0..count {
+            let id = client.create_bill(
+                owner,
+                &String::from_str(env, "Test Bill"),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &None,
+                &String::from_str(env, "XLM"),
+            );
+            ids.push_back(id);
+            // Each call has its own budget on-chain; don't let setup count
+            // against the calls under test.
+            env.budget().reset_default();
+        }
+        ids
+    }
+
+    // --- get_unpaid_bills ---
+
+    #[test]
+    fn test_get_unpaid_bills_empty() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let page = client.get_unpaid_bills(&owner, &0, &0);
+        assert_eq!(page.count, 0);
+        assert_eq!(page.next_cursor, 0);
+        assert_eq!(page.items.len(), 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_single_page() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 5);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+        assert_eq!(page.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_multiple_pages() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 7);
+
+        let page1 = client.get_unpaid_bills(&owner, &0, &3);
+        assert_eq!(page1.count, 3);
+        assert!(page1.next_cursor > 0, "expected a next cursor");
+
+        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
+        assert_eq!(page2.count, 3);
+        assert!(page2.next_cursor > 0);
+
+        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
+        assert_eq!(page3.count, 1);
+        assert_eq!(page3.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 4);
+        let second_id = ids.get(1).unwrap();
+        client.pay_bill(&owner, &second_id);
+
+        let page = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_excludes_other_owner() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        let page = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page.count, 3);
+        for bill in page.items.iter() {
+            assert_eq!(bill.owner, owner_a);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 3);
+
+        // owner_a sees only their own bills
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 2);
+        for bill in page_a.items.iter() {
+            assert_eq!(
+                bill.owner, owner_a,
+                "owner_a page must not contain owner_b bills"
+            );
+        }
+
+        // owner_b sees only their own bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3);
+        for bill in page_b.items.iter() {
+            assert_eq!(
+                bill.owner, owner_b,
+                "owner_b page must not contain owner_a bills"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
+        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 2);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays one of their bills
+        client.pay_bill(&owner_a, &ids_a.get(0).unwrap());
+
+        // owner_a now has 1 unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 1);
+        for bill in page_a.items.iter() {
+            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
+            assert!(!bill.paid, "Should only see unpaid bills");
+        }
+
+        // owner_b still has 2 unpaid — unaffected by owner_a's payment
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
+        // owner_b has bills but owner_a has none — owner_a gets empty page
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        // Only owner_b creates bills
+        setup_bills(&env, &client, &owner_b, 3);
+
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should see no bills");
+        assert_eq!(page_a.next_cursor, 0);
+
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
+        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        let ids_a = setup_bills(&env, &client, &owner_a, 3);
+        setup_bills(&env, &client, &owner_b, 2);
+
+        // owner_a pays all their bills
+        for id in ids_a.iter() {
+            client.pay_bill(&owner_a, &id);
+        }
+
+        // owner_a has zero unpaid
+        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
+        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+
+        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
+        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
+        assert_eq!(page_b.count, 2);
+        for bill in page_b.items.iter() {
+            assert_eq!(bill.owner, owner_b);
+            assert!(!bill.paid);
+        }
+    }
+
+    #[test]
+    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
+        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+
+        let owner_a = Address::generate(&env);
+        let owner_b = Address::generate(&env);
+
+        // Interleave bills: a, b, a, b, a, b ...
+        for i in 0..4u32 {
+            // Added the 'currency' argument at the end to match the new signature
+            client.create_bill(
+                &owner_a,
+                &String::from_str(&env, "Bill A"),
+                &(100i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
+            client.create_bill(
+                &owner_b,
+                &String::from_str(&env, "Bill B"),
+                &(200i128 * (i as i128 + 1)),
+                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+                &false,
+                &0,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
+        }
+
+        // Paginate through owner_a with small page size
+        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
+        let mut cursor = 0u32;
+
+        loop {
+            // Assuming your get_unpaid_bills function returns a struct with 'items' and 'next_cursor'
+            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
+
+            for bill in page.items.iter() {
+                assert_eq!(
+                    bill.owner, owner_a,
+                    "Paginated result must never contain owner_b's bill"
+                );
+                // Verification: ensure the default currency logic worked
+                assert_eq!(bill.currency, String::from_str(&env, "XLM"));
+
+                all_a_bills.push_back(bill);
+            }
+
+            if page.next_cursor == 0 {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(
+            all_a_bills.len(),
+            4,
+            "owner_a should have exactly 4 bills across all pages"
+        );
+    }
+
+    // --- get_overdue_bills ---
+
+    #[test]
+    fn test_get_overdue_bills_not_overdue() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 3);
+        let page = client.get_overdue_bills(&0, &10);
+        assert_eq!(page.count, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 1. Set initial time so create_bill succeeds
+        // The contract requires: due_date >= current_time
+        env.ledger().set_timestamp(10000);
+
+        let due_date = 20000;
+
+        for _ in 0..6u32 {
+            client.create_bill(
+                &owner,
+                &String::from_str(&env, "Overdue Bill"),
+                &100,
+                &due_date, // 20000
+                &false,
+                &0,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
+        }
+
+        // 2. Advance time PAST the due date to make them "Overdue"
+        // current_time (25000) > due_date (20000)
+        env.ledger().set_timestamp(25000);
+
+        // Now get_overdue_bills will actually find the 6 bills
+        let page1 = client.get_overdue_bills(&0, &4);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_earliest_due_day_first() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        env.ledger().set_timestamp(10_000);
+
+        let create = |due_date: u64| {
+            client.create_bill(
+                &owner,
+                &String::from_str(&env, "Bill"),
+                &100,
+                &due_date,
+                &false,
+                &0,
+                &None,
+                &String::from_str(&env, "XLM"),
+            )
+        };
+        let latest = create(200_000);
+        let earliest = create(20_000);
+        let middle = create(100_000);
+        env.ledger().set_timestamp(300_000);
+
+        let page1 = client.get_overdue_bills(&0, &2);
+        assert_eq!(page1.items.get(0).unwrap().id, earliest);
+        assert_eq!(page1.items.get(1).unwrap().id, middle);
+        assert_eq!(page1.next_cursor, middle);
+
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &2);
+        assert_eq!(page2.count, 1);
+        assert_eq!(page2.items.get(0).unwrap().id, latest);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_overdue_bills_stops_after_scan_budget() {
+        let env = make_env();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        env.ledger().set_timestamp(10_000);
+
+        // Bills still in their grace period fill the first scan budget.
+        let rule = late_fee_rule(30, 10, 0, 100);
+        let mut last_in_grace = 0;
+        for _ in 0..MAX_SCAN_IDS {
+            last_in_grace = create_late_fee_bill(&env, &client, &owner, 20_000, &rule);
+        }
+        let overdue = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Overdue"),
+            &100,
+            &20_000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+        env.ledger().set_timestamp(25_000);
+
+        let page1 = client.get_overdue_bills(&0, &10);
+        assert_eq!(page1.count, 0);
+        assert_eq!(page1.next_cursor, last_in_grace);
+
+        let page2 = client.get_overdue_bills(&page1.next_cursor, &10);
+        assert_eq!(page2.count, 1);
+        assert_eq!(page2.items.get(0).unwrap().id, overdue);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    #[test]
+    fn test_get_all_bills_probes_bounded_id_range() {
+        let env = make_env();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_pause_admin(&admin, &admin);
+
+        let total = MAX_SCAN_IDS + 3;
+        for _ in 0..total {
+            create_test_bill(&env, &client, &owner, 100, false);
+        }
+        for id in 1..=MAX_SCAN_IDS + 1 {
+            client.cancel_bill(&owner, &id);
+        }
+
+        let page1 = client.get_all_bills(&admin, &0, &10);
+        assert_eq!(page1.count, 0);
+        assert_eq!(page1.next_cursor, MAX_SCAN_IDS);
+
+        let page2 = client.get_all_bills(&admin, &page1.next_cursor, &10);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.items.get(0).unwrap().id, total - 1);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    // --- get_all_bills_for_owner ---
+
+    #[test]
+    fn test_get_all_bills_for_owner_includes_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let ids = setup_bills(&env, &client, &owner, 5);
+        let first_id = ids.get(0).unwrap();
+        client.pay_bill(&owner, &first_id);
+
+        let page = client.get_all_bills_for_owner(&owner, &0, &10);
+        assert_eq!(page.count, 5);
+    }
+
+    // --- limit clamping ---
+
+    #[test]
+    fn test_limit_zero_uses_default() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 3);
+        let page = client.get_unpaid_bills(&owner, &0, &0);
+        assert_eq!(page.count, 3);
+    }
+
+    #[test]
+    fn test_limit_clamped_to_max() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        setup_bills(&env, &client, &owner, 55);
+        let page = client.get_unpaid_bills(&owner, &0, &9999);
+        assert_eq!(page.count, MAX_PAGE_LIMIT);
+        assert!(page.next_cursor > 0);
+    }
+
+    // --- archived bill pagination ---
+
+    #[test]
+    fn test_get_archived_bills_pagination() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        client.set_pause_admin(&owner, &owner);
+
+        let ids = setup_bills(&env, &client, &owner, 6);
+        for bill_id in ids.iter() {
+            client.pay_bill(&owner, &bill_id);
+        }
+        client.archive_paid_bills(&owner, &u64::MAX);
+
+        let page1 = client.get_archived_bills(&owner, &0, &4);
+        assert_eq!(page1.count, 4);
+        assert!(page1.next_cursor > 0);
+
+        let page2 = client.get_archived_bills(&owner, &page1.next_cursor, &4);
+        assert_eq!(page2.count, 2);
+        assert_eq!(page2.next_cursor, 0);
+    }
+
+    // -----------------------------------------------------------------------
+    // RECURRING BILLS DATE MATH TESTS
+    // -----------------------------------------------------------------------
+    // These tests verify the core date math for recurring bills:
+    // next_due_date = due_date + (frequency_days * 86400)
+    // Ensures paid_at does not affect next bill's due_date calculation.
+
+    #[test]
+    fn test_recurring_date_math_frequency_1_day() {
+        // Test: frequency_days = 1 → next due date is +1 day (86400 seconds)
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Daily Bill"),
+            &100,
+            &base_due_date,
+            &true, // recurring
+            &1,    // frequency_days = 1
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay the bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Verify next bill's due_date = base_due_date + (1 * 86400)
+        let next_bill = client.get_bill(&2).unwrap();
+        assert!(!next_bill.paid, "Next bill should be unpaid");
+        assert_eq!(
+            next_bill.due_date,
+            base_due_date + 86400,
+            "Next due date should be exactly 1 day later"
+        );
+        assert_eq!(next_bill.frequency_days, 1, "Frequency should be preserved");
+    }
+
+    #[test]
+    fn test_recurring_date_math_frequency_30_days() {
+        // Test: frequency_days = 30 → next due date is +30 days (2,592,000 seconds)
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Monthly Bill"),
+            &500,
+            &base_due_date,
+            &true, // recurring
+            &30,   // frequency_days = 30
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay the bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Verify next bill's due_date = base_due_date + (30 * 86400)
+        let next_bill = client.get_bill(&2).unwrap();
+        assert!(!next_bill.paid, "Next bill should be unpaid");
+        let expected_due_date = base_due_date + (30u64 * 86400);
+        assert_eq!(
+            next_bill.due_date, expected_due_date,
+            "Next due date should be exactly 30 days later"
+        );
+        assert_eq!(
+            next_bill.frequency_days, 30,
+            "Frequency should be preserved"
+        );
+    }
+
+    #[test]
+    fn test_recurring_date_math_frequency_365_days() {
+        // Test: frequency_days = 365 → next due date is +365 days (31,536,000 seconds)
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Annual Bill"),
+            &1200,
+            &base_due_date,
+            &true, // recurring
+            &365,  // frequency_days = 365
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay the bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Verify next bill's due_date = base_due_date + (365 * 86400)
+        let next_bill = client.get_bill(&2).unwrap();
+        assert!(!next_bill.paid, "Next bill should be unpaid");
+        let expected_due_date = base_due_date + (365u64 * 86400);
+        assert_eq!(
+            next_bill.due_date, expected_due_date,
+            "Next due date should be exactly 365 days later"
+        );
+        assert_eq!(
+            next_bill.frequency_days, 365,
+            "Frequency should be preserved"
+        );
+    }
+
+    #[test]
+    fn test_recurring_date_math_paid_at_does_not_affect_next_due() {
+        let env = Env::default();
+
+        // FORCE reset to a very small number first
+        env.ledger().set_timestamp(100);
+
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        // Now current_time (100) is definitely < base_due_date (1,000,000)
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Late Payment Test"),
+            &300,
+            &base_due_date,
+            &true,
+            &30,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Warp to late payment time
+        env.ledger().set_timestamp(1_000_500);
+        client.pay_bill(&owner, &bill_id);
+
+        let next_bill = client.get_bill(&2).unwrap();
+        let expected_due_date = base_due_date + (30u64 * 86400);
+        assert_eq!(next_bill.due_date, expected_due_date);
+    }
+
+    #[test]
+    fn test_recurring_date_math_multiple_pay_cycles_2nd_bill() {
+        // Test: Multiple pay cycles - verify 2nd bill's due date advances correctly
+        // Bill 1: due_date=1000000, frequency=30
+        // Bill 2: due_date=1000000 + (30*86400)
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Multi-Cycle Bill"),
+            &250,
+            &base_due_date,
+            &true, // recurring
+            &30,   // frequency_days = 30
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay first bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Verify second bill
+        let bill2 = client.get_bill(&2).unwrap();
+        let expected_bill2_due = base_due_date + (30u64 * 86400);
+        assert_eq!(bill2.due_date, expected_bill2_due);
+        assert!(!bill2.paid);
+
+        // Pay second bill
+        client.pay_bill(&owner, &2);
+
+        // Verify second bill is now paid
+        let bill2_paid = client.get_bill(&2).unwrap();
+        assert!(bill2_paid.paid);
+
+        // Verify third bill was created with correct due_date
+        let bill3 = client.get_bill(&3).unwrap();
+        let expected_bill3_due = expected_bill2_due + (30u64 * 86400);
+        assert_eq!(
+            bill3.due_date, expected_bill3_due,
+            "Bill 3 due_date should be Bill 2 due_date + (30*86400)"
+        );
+        assert!(!bill3.paid);
+    }
+
+    #[test]
+    fn test_recurring_date_math_multiple_pay_cycles_3rd_bill() {
+        // Test: Multiple pay cycles - verify 3rd bill's due date advances correctly
+        // Bill 1: due_date=1000000, frequency=30
+        // Bill 2: due_date=1000000 + (30*86400)
+        // Bill 3: due_date=1000000 + (60*86400)
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Three-Cycle Bill"),
+            &150,
+            &base_due_date,
+            &true, // recurring
+            &30,   // frequency_days = 30
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay first bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Pay second bill
+        client.pay_bill(&owner, &2);
+
+        // Pay third bill
+        client.pay_bill(&owner, &3);
+
+        // Verify third bill is now paid
+        let bill3_paid = client.get_bill(&3).unwrap();
+        assert!(bill3_paid.paid);
+
+        // Verify fourth bill was created with correct due_date
+        let bill4 = client.get_bill(&4).unwrap();
+        let expected_bill4_due = base_due_date + (90u64 * 86400); // 3 * 30 days
+        assert_eq!(
+            bill4.due_date, expected_bill4_due,
+            "Bill 4 due_date should be base + (90*86400)"
+        );
+        assert!(!bill4.paid);
+    }
+
+    #[test]
+    fn test_recurring_date_math_early_payment_does_not_affect_schedule() {
+        // Test: Paying a bill EARLY should not affect the next bill's due_date
+        // Bill 1: due_date=1000000, paid at time=500000 (paid 500000 seconds early)
+        // Bill 2: due_date should still be 1000000 + (30*86400)
+        let env = make_env();
+        env.ledger().set_timestamp(500_000); // Set time BEFORE due date
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due_date = 1_000_000u64;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Early Payment Test"),
+            &200,
+            &base_due_date,
+            &true, // recurring
+            &30,   // frequency_days = 30
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay the bill early (at time 500_000)
+        client.pay_bill(&owner, &bill_id);
+
+        // Verify original bill has paid_at set to early time
+        let paid_bill = client.get_bill(&bill_id).unwrap();
+        assert!(paid_bill.paid);
+        assert_eq!(paid_bill.paid_at, Some(500_000));
+
+        // Verify next bill's due_date is still based on original due_date
+        let next_bill = client.get_bill(&2).unwrap();
+        let expected_due_date = base_due_date + (30u64 * 86400);
+        assert_eq!(
+            next_bill.due_date, expected_due_date,
+            "Next due date should not be affected by early payment"
+        );
+    }
+
+    #[test]
+    fn test_recurring_date_math_preserves_frequency_across_cycles() {
+        // Test: frequency_days is preserved across all recurring cycles
+        // Verify that Bill 1, 2, 3 all have the same frequency_days value
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let frequency = 7u32; // Weekly
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Weekly Bill"),
+            &50,
+            &1_000_000,
+            &true,
+            &frequency,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay first bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Pay second bill
+        client.pay_bill(&owner, &2);
+
+        // Verify all bills have the same frequency_days
+        let bill1 = client.get_bill(&1).unwrap();
+        let bill2 = client.get_bill(&2).unwrap();
+        let bill3 = client.get_bill(&3).unwrap();
+
+        assert_eq!(bill1.frequency_days, frequency);
+        assert_eq!(bill2.frequency_days, frequency);
+        assert_eq!(bill3.frequency_days, frequency);
+    }
+
+    #[test]
+    fn test_recurring_date_math_amount_preserved_across_cycles() {
+        // Test: Bill amount is preserved across all recurring cycles
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let amount = 999i128;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Fixed Amount Bill"),
+            &amount,
+            &1_000_000,
+            &true,
+            &30,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay first bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Pay second bill
+        client.pay_bill(&owner, &2);
+
+        // Verify all bills have the same amount
+        let bill1 = client.get_bill(&1).unwrap();
+        let bill2 = client.get_bill(&2).unwrap();
+        let bill3 = client.get_bill(&3).unwrap();
+
+        assert_eq!(bill1.amount, amount);
+        assert_eq!(bill2.amount, amount);
+        assert_eq!(bill3.amount, amount);
+    }
+
+    #[test]
+    fn test_recurring_date_math_owner_preserved_across_cycles() {
+        // Test: Bill owner is preserved across all recurring cycles
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Owner Test"),
+            &100,
+            &1_000_000,
+            &true,
+            &30,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Pay first bill
+        client.pay_bill(&owner, &bill_id);
+
+        // Pay second bill
+        client.pay_bill(&owner, &2);
+
+        // Verify all bills have the same owner
+        let bill1 = client.get_bill(&1).unwrap();
+        let bill2 = client.get_bill(&2).unwrap();
+        let bill3 = client.get_bill(&3).unwrap();
+
+        assert_eq!(bill1.owner, owner);
+        assert_eq!(bill2.owner, owner);
+        assert_eq!(bill3.owner, owner);
+    }
+
+    #[test]
+    fn test_recurring_date_math_exact_calculation_verification() {
+        // Test: Verify exact date math calculation with known values
+        // due_date = 1_000_000
+        // frequency_days = 14
+        // Expected: 1_000_000 + (14 * 86400) = 1_000_000 + 1_209_600 = 2_209_600
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let base_due = 1_000_000u64;
+        let freq = 14u32;
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Math Verification"),
+            &100,
+            &base_due,
+            &true,
+            &freq,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        client.pay_bill(&owner, &bill_id);
+
+        let next_bill = client.get_bill(&2).unwrap();
+        let expected = 1_000_000u64 + (14u64 * 86400);
+        assert_eq!(next_bill.due_date, expected);
+        assert_eq!(next_bill.due_date, 2_209_600);
+    }
+
+    // -----------------------------------------------------------------------
+    // Property-based tests: time-dependent behavior
+    // -----------------------------------------------------------------------
+
+    proptest! {
+        /// All bills returned by get_overdue_bills must have due_date < now,
+        /// and every bill created with due_date < now must appear in the result.
+        #[test]
+        fn prop_overdue_bills_all_have_due_before_now(
+            now in 2_000_000u64..10_000_000u64,
+            n_overdue in 1usize..6usize,
+            n_future in 0usize..6usize,
+        ) {
+            let env = make_env();
+            let create_time = now.saturating_sub(10_000);
+            env.ledger().set_timestamp(create_time);
+            env.mock_all_auths();
+            let cid = env.register_contract(None, BillPayments);
+            let client = BillPaymentsClient::new(&env, &cid);
+            let owner = Address::generate(&env);
+
+            // Set initial ledger time to 0 to bypass "due_date >= now" check,
+            // then fast-forward to the target 'now' value.
+            env.ledger().set_timestamp(0);
+
+            // Create bills with due_date < now (overdue)
+            for i in 0..n_overdue {
+                client.create_bill(
+                    &owner,
+                    &String::from_str(&env, "Overdue"),
+                    &100,
+                    &(now - 1 - i as u64),
+                    &false,
+                    &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
+                );
+            }
+
+            // Create bills with due_date >= now (not overdue)
+            for i in 0..n_future {
+                client.create_bill(
+                    &owner,
+                    &String::from_str(&env, "Future"),
+                    &100,
+                    &(now + 1 + i as u64),
+                    &false,
+                    &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
+                );
+            }
+
+            // Fast-forward to 'now' so they become overdue
+            env.ledger().set_timestamp(now);
+
+            let page = client.get_overdue_bills(&0, &50);
+            for bill in page.items.iter() {
+                prop_assert!(bill.due_date < now, "returned bill must be past due");
+            }
+            prop_assert_eq!(page.count as usize, n_overdue);
+        }
+    }
+
+    proptest! {
+        /// Bills with due_date >= now must never appear in get_overdue_bills.
+        #[test]
+        fn prop_future_bills_not_in_overdue_set(
+            now in 1_000_000u64..5_000_000u64,
+            n in 1usize..6usize,
+        ) {
+            let env = make_env();
+            env.ledger().set_timestamp(now);
+            env.mock_all_auths();
+            let cid = env.register_contract(None, BillPayments);
+            let client = BillPaymentsClient::new(&env, &cid);
+            let owner = Address::generate(&env);
+
+            for i in 0..n {
+                client.create_bill(
+                    &owner,
+                    &String::from_str(&env, "NotOverdue"),
+                    &100,
+                    &(now + i as u64), // due_date >= now — strict less-than is required to be overdue
+                    &false,
+                    &0,
+                    &None,
+                    &String::from_str(&env, "XLM"),
+                );
+            }
+
+            let page = client.get_overdue_bills(&0, &50);
+            prop_assert_eq!(
+                page.count,
+                0u32,
+                "bills with due_date >= now must not appear as overdue"
+            );
+        }
+    }
+
+    proptest! {
+        /// After paying a recurring bill, the next bill's due_date equals
+        /// the original due_date + frequency_days * 86400, regardless of
+        /// when payment is made.
+        #[test]
+        fn prop_recurring_next_bill_due_date_follows_original(
+            base_due in 1_000_000u64..5_000_000u64,
+            base_due_offset in 1_000_000u64..5_000_000u64,
+            pay_offset in 1u64..100_000u64,
+            freq_days in 1u32..366u32,
+        ) {
+            let env = make_env();
+            env.mock_all_auths();
+            let cid = env.register_contract(None, BillPayments);
+            let client = BillPaymentsClient::new(&env, &cid);
+            let owner = Address::generate(&env);
+
+            // Set base due date far in the future relative to 0
+            let base_due = 1_000_000 + base_due_offset;
+            env.ledger().set_timestamp(0);
+
+            let bill_id = client.create_bill(
+                &owner,
+                &String::from_str(&env, "Recurring"),
+                &100,
+                &base_due,
+                &true,
+                &freq_days,
+                &None,
+                &String::from_str(&env, "XLM"),
+            );
+
+            // Fast-forward to the payment time
+            let now = base_due + pay_offset;
+            env.ledger().set_timestamp(now);
+
+            client.pay_bill(&owner, &bill_id);
+
+            let next_bill = client.get_bill(&2).unwrap();
+            let expected_due = base_due + (freq_days as u64 * 86400);
+            prop_assert_eq!(
+                next_bill.due_date,
+                expected_due,
+                "next recurring bill due_date must equal original due_date + freq_days * 86400"
+            );
+            prop_assert!(!next_bill.paid, "next recurring bill must be unpaid");
+        }
+    }
+
+    /// Issue #102 – When pay_bill is called on a recurring bill, the contract
+    /// creates the next occurrence.  This test asserts every cloned field
+    /// individually so that a regression in the clone logic (e.g. paid left
+    /// true, wrong due_date, wrong owner) is caught immediately.
+    #[test]
+    fn test_create_bill_invalid_due_date() {
+        // 1. Setup
+        let env = make_env();
+        env.mock_all_auths();
+
+        // Explicitly set the ledger time
+        let current_ledger_time = 1_700_000_000;
+        env.ledger().with_mut(|info| {
+            info.timestamp = current_ledger_time;
+        });
+
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 2. Scenario Data
+        let past_due_date = 946684800; // Year 1999
+        let zero_due_date = 0u64;
+        let name = String::from_str(&env, "Electricity");
+        let currency = String::from_str(&env, ""); // New required parameter
+
+        // 3. Execution: Attempt to create bills with invalid dates
+        // Added '&currency' as the final argument to both calls
+        let result_past =
+            client.try_create_bill(&owner, &name, &1000, &past_due_date, &false, &0, &None, &currency);
+
+        let result_zero =
+            client.try_create_bill(&owner, &name, &1000, &zero_due_date, &false, &0, &None, &currency);
+
+        // 4. Assertions
+        assert!(
+            result_past.is_err(),
+            "Creation should have failed for a past date"
+        );
+        assert!(
+            result_zero.is_err(),
+            "Creation should have failed for a zero date"
+        );
+
+        // Check that the error code matches InvalidDueDate
+        match result_past {
+            Err(Ok(err)) => assert_eq!(err, Error::InvalidDueDate),
+            _ => panic!("Expected contract error InvalidDueDate for past date"),
+        }
+
+        match result_zero {
+            Err(Ok(err)) => assert_eq!(err, Error::InvalidDueDate),
+            _ => panic!("Expected contract error InvalidDueDate for zero date"),
+        }
+    }
+
+    // ══════════════════════════════════════════════════════════════════════
+    // Time & Ledger Drift Resilience Tests (#158)
+    //
+    // Assumptions:
+    //  - A bill is overdue when due_date < current_time (strict less-than).
+    //  - At exactly due_date the bill is NOT yet overdue.
+    //  - Late-fee terms move that boundary to the end of the grace period.
+    //  - Stellar ledger timestamps are monotonically increasing in production.
+    // ══════════════════════════════════════════════════════════════════════
+
+    /// Bill is NOT overdue when ledger timestamp == due_date (inclusive boundary).
+    #[test]
+    fn test_time_drift_bill_not_overdue_at_exact_due_date() {
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Power"),
+            &200,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let page = client.get_overdue_bills(&0, &100);
+        assert_eq!(
+            page.count, 0,
+            "Bill must not appear overdue when current_time == due_date"
+        );
+    }
+
+    /// Bill becomes overdue exactly one second after due_date.
+    #[test]
+    fn test_time_drift_bill_overdue_one_second_after_due_date() {
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Internet"),
+            &150,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let page = client.get_overdue_bills(&0, &100);
+        assert_eq!(page.count, 0);
+
+        env.ledger().set_timestamp(due_date + 1);
+        let page = client.get_overdue_bills(&0, &100);
+        assert_eq!(
+            page.count, 1,
+            "Bill must appear overdue exactly one second past due_date"
+        );
+    }
+    #[test]
+    /// Mix of past-due, exactly-due, and future bills: only past-due one appears.
+    fn test_time_drift_overdue_boundary_mixed_bills() {
+        let env = Env::default();
+        // 1. Set time to long ago
+        env.ledger().set_timestamp(1_000_000);
+
+        let contract_id = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        env.mock_all_auths();
+
+        // 2. Create bills with due dates in the "future" (relative to 1,000_000)
+        // This one will be our "Overdue" bill later
+        let overdue_target = 1_500_000u64;
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Overdue"),
+            &100,
+            &overdue_target,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // This one will be "DueNow" later
+        let due_now_target = 2_000_000u64;
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "DueNow"),
+            &200,
+            &due_now_target,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // 3. WARP to the "Present" (2,000_000)
+        env.ledger().set_timestamp(2_000_000);
+
+        let page = client.get_overdue_bills(&0, &100);
+
+        // Now overdue_target (1.5M) is < current (2M) -> OVERDUE
+        // due_now_target (2M) is NOT < current (2M) -> NOT OVERDUE
+        assert_eq!(page.count, 1);
+        assert_eq!(page.items.get(0).unwrap().amount, 100);
+    }
+
+    /// Full-day boundary (86400 s): bill created at due_date, queried one day later, is overdue.
+    #[test]
+    fn test_time_drift_overdue_full_day_boundary() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Monthly Rent"),
+            &5000,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let page = client.get_overdue_bills(&0, &100);
+        assert_eq!(page.count, 0);
+
+        env.ledger().set_timestamp(due_date + day);
+        let page = client.get_overdue_bills(&0, &100);
+        assert_eq!(
+            page.count, 1,
+            "Bill must be overdue one full day past due_date"
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Late fee and grace period tests
+    // -----------------------------------------------------------------------
+
+    fn late_fee_rule(grace_days: u32, flat_fee: i128, rate_bps: u32, max_fee: i128) -> LateFeeRule {
+        LateFeeRule {
+            grace_days,
+            period_days: 7,
+            flat_fee,
+            rate_bps,
+            max_fee,
+        }
+    }
+
+    /// Create a 1000-unit bill due at `due_date` with the given late-fee terms.
+    fn create_late_fee_bill(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        due_date: u64,
+        rule: &LateFeeRule,
+    ) -> u32 {
+        let bill_id = client.create_bill(
+            owner,
+            &String::from_str(env, "Water"),
+            &1_000,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(env, "XLM"),
+        );
+        client.set_late_fee(owner, &bill_id, &LateFeePolicy::Charge(rule.clone()));
+        bill_id
+    }
+
+    /// Grace period boundary: not overdue and no fee at exactly the end of
+    /// the grace period; overdue with one period's fee a second later.
+    #[test]
+    fn test_time_drift_grace_period_boundary() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let rule = late_fee_rule(3, 25, 0, 100);
+        let bill_id = create_late_fee_bill(&env, &client, &owner, due_date, &rule);
+
+        env.ledger().set_timestamp(due_date + 3 * day);
+        assert_eq!(client.get_overdue_bills(&0, &100).count, 0);
+        assert_eq!(client.get_amount_due_now(&bill_id), 1_000);
+
+        env.ledger().set_timestamp(due_date + 3 * day + 1);
+        assert_eq!(client.get_overdue_bills(&0, &100).count, 1);
+        assert_eq!(client.get_amount_due_now(&bill_id), 1_025);
+    }
+
+    /// One fee per started period, flat plus percentage, up to the cap.
+    #[test]
+    fn test_late_fee_accrues_per_period_up_to_cap() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 10 flat + 2% of 1000 = 30 per week, capped at 75.
+        let rule = late_fee_rule(0, 10, 200, 75);
+        let bill_id = create_late_fee_bill(&env, &client, &owner, due_date, &rule);
+
+        env.ledger().set_timestamp(due_date + 7 * day);
+        assert_eq!(client.get_amount_due_now(&bill_id), 1_030);
+        env.ledger().set_timestamp(due_date + 7 * day + 1);
+        assert_eq!(client.get_amount_due_now(&bill_id), 1_060);
+        env.ledger().set_timestamp(due_date + 30 * day);
+        assert_eq!(client.get_amount_due_now(&bill_id), 1_075);
+        // Fees are not part of the owner's unpaid total.
+        assert_eq!(client.get_total_unpaid(&owner), 1_000);
+    }
+
+    #[test]
+    fn test_pay_bill_charges_accrued_late_fee() {
+        let day = 86400u64;
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 2_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        let due_date = env.ledger().timestamp() + day;
+        let rule = late_fee_rule(2, 50, 0, 500);
+        let bill_id = create_late_fee_bill(&env, &client, &owner, due_date, &rule);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+
+        env.ledger().set_timestamp(due_date + 10 * day);
+        client.pay_bill(&owner, &bill_id);
+
+        // Grace ends on day 2; days 2-9 and 9-10 are two started periods.
+        assert_eq!(token_client.balance(&payee), 1_100);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.amount_paid, 1_000);
+        assert_eq!(bill.late_fee_paid, 100);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(client.get_amount_due_now(&bill_id), 0);
+    }
+
+    #[test]
+    fn test_partial_payment_settles_late_fee_first() {
+        let day = 86400u64;
+        let due_date = 1_000_000u64;
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(due_date);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let rule = late_fee_rule(0, 40, 0, 40);
+        let bill_id = create_late_fee_bill(&env, &client, &owner, due_date, &rule);
+        env.ledger().set_timestamp(due_date + day);
+
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &100), 940);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.late_fee_paid, 40);
+        assert_eq!(bill.amount_paid, 60);
+        assert_eq!(client.get_total_unpaid(&owner), 940);
+
+        let res = client.try_pay_bill_partial(&owner, &bill_id, &941);
+        assert_eq!(res, Err(Ok(Error::Overpayment)));
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &940), 0);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+    }
+
+    #[test]
+    fn test_set_late_fee_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, true);
+        let invalid = [
+            LateFeeRule {
+                period_days: 0,
+                ..late_fee_rule(0, 10, 0, 50)
+            },
+            late_fee_rule(0, -1, 100, 50),
+            late_fee_rule(0, 10, 10_001, 50),
+            late_fee_rule(0, 10, 0, 0),
+            late_fee_rule(0, 0, 0, 50),
+        ];
+        for rule in invalid {
+            let res = client.try_set_late_fee(&owner, &bill_id, &LateFeePolicy::Charge(rule));
+            assert_eq!(res, Err(Ok(Error::InvalidLateFee)));
+        }
+
+        let policy = LateFeePolicy::Charge(late_fee_rule(5, 10, 0, 50));
+        let res = client.try_set_late_fee(&other, &bill_id, &policy);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.set_late_fee(&owner, &bill_id, &policy);
+
+        // Recurring bills carry the terms over; the fee paid starts at zero.
+        client.pay_bill(&owner, &bill_id);
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.late_fee, policy);
+        assert_eq!(next.late_fee_paid, 0);
+
+        client.set_late_fee(&owner, &next.id, &LateFeePolicy::None);
+        assert_eq!(
+            client.get_bill(&next.id).unwrap().late_fee,
+            LateFeePolicy::None
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Recurrence rule tests
+    // -----------------------------------------------------------------------
+
+    fn recurrence_rule(recurrence: Recurrence) -> RecurrenceRule {
+        RecurrenceRule {
+            recurrence,
+            ends_at: None,
+            max_occurrences: None,
+        }
+    }
+
+    /// Create a bill due at `due_date` following `rule`, with the ledger
+    /// clock set to `due_date`.
+    fn create_recurring_bill(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        due_date: u64,
+        rule: &RecurrenceRule,
+    ) -> u32 {
+        env.ledger().set_timestamp(due_date);
+        let bill_id = client.create_bill(
+            owner,
+            &String::from_str(env, "Rent"),
+            &500,
+            &due_date,
+            &false,
+            &0,
+            &None,
+            &String::from_str(env, "XLM"),
+        );
+        client.set_bill_recurrence(owner, &bill_id, rule);
+        bill_id
+    }
+
+    /// Pay bills starting at `bill_id` and return the due dates of the
+    /// occurrences created, in order.
+    fn pay_series(
+        client: &BillPaymentsClient,
+        owner: &Address,
+        bill_id: u32,
+        n: u32,
+    ) -> StdVec<u64> {
+        let mut due_dates = StdVec::new();
+        for id in bill_id..bill_id + n {
+            client.pay_bill(owner, &id);
+            match client.get_bill(&(id + 1)) {
+                Some(next) => due_dates.push(next.due_date),
+                None => break,
+            }
+        }
+        due_dates
+    }
+
+    #[test]
+    fn test_monthly_on_day_clamps_to_month_end() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 2025-01-31 09:00 UTC
+        let rule = recurrence_rule(Recurrence::MonthlyOnDay(31));
+        let bill_id = create_recurring_bill(&env, &client, &owner, 1_738_314_000, &rule);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.recurring);
+        assert_eq!(bill.frequency_days, 30);
+
+        // Feb 28, Mar 31, Apr 30: the anchor day survives the short month.
+        let due_dates = pay_series(&client, &owner, bill_id, 3);
+        assert_eq!(due_dates, [1_740_733_200, 1_743_411_600, 1_746_003_600]);
+        assert_eq!(client.get_bill(&(bill_id + 3)).unwrap().occurrence, 4);
+    }
+
+    #[test]
+    fn test_monthly_on_day_rolls_over_year_end() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 2025-12-15 -> 2026-01-15
+        let rule = recurrence_rule(Recurrence::MonthlyOnDay(15));
+        let bill_id = create_recurring_bill(&env, &client, &owner, 1_765_756_800, &rule);
+        assert_eq!(pay_series(&client, &owner, bill_id, 1), [1_768_435_200]);
+    }
+
+    #[test]
+    fn test_weekly_on_weekday() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Wednesday 2025-01-01 12:00 UTC -> Mondays 6 and 13 January.
+        let rule = recurrence_rule(Recurrence::Weekly(0));
+        let bill_id = create_recurring_bill(&env, &client, &owner, 1_735_732_800, &rule);
+        let due_dates = pay_series(&client, &owner, bill_id, 2);
+        assert_eq!(due_dates, [1_736_164_800, 1_736_769_600]);
+    }
+
+    #[test]
+    fn test_yearly_from_leap_day() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // 2024-02-29 -> 2025-02-28 -> 2026-02-28
+        let rule = recurrence_rule(Recurrence::Yearly);
+        let bill_id = create_recurring_bill(&env, &client, &owner, 1_709_164_800, &rule);
+        let due_dates = pay_series(&client, &owner, bill_id, 2);
+        assert_eq!(due_dates, [1_740_700_800, 1_772_236_800]);
+    }
+
+    #[test]
+    fn test_recurrence_stops_at_max_occurrences_and_end_date() {
+        let day = 86400u64;
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let rule = RecurrenceRule {
+            max_occurrences: Some(3),
+            ..recurrence_rule(Recurrence::EveryDays(10))
+        };
+        let bill_id = create_recurring_bill(&env, &client, &owner, 1_000_000, &rule);
+        assert_eq!(pay_series(&client, &owner, bill_id, 5).len(), 2);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+
+        let rule = RecurrenceRule {
+            ends_at: Some(2_000_000 + 20 * day),
+            ..recurrence_rule(Recurrence::EveryDays(10))
+        };
+        let bill_id = create_recurring_bill(&env, &client, &owner, 2_000_000, &rule);
+        let due_dates = pay_series(&client, &owner, bill_id, 5);
+        assert_eq!(due_dates, [2_000_000 + 10 * day, 2_000_000 + 20 * day]);
+    }
+
+    #[test]
+    fn test_set_bill_recurrence_validation() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, true);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.recurrence, recurrence_rule(Recurrence::EveryDays(30)));
+        assert_eq!(bill.occurrence, 1);
+
+        for recurrence in [
+            Recurrence::EveryDays(0),
+            Recurrence::Weekly(7),
+            Recurrence::MonthlyOnDay(0),
+            Recurrence::MonthlyOnDay(32),
+        ] {
+            let res =
+                client.try_set_bill_recurrence(&owner, &bill_id, &recurrence_rule(recurrence));
+            assert_eq!(res, Err(Ok(Error::InvalidFrequency)));
+        }
+        let rule = RecurrenceRule {
+            max_occurrences: Some(0),
+            ..recurrence_rule(Recurrence::Yearly)
+        };
+        let res = client.try_set_bill_recurrence(&owner, &bill_id, &rule);
+        assert_eq!(res, Err(Ok(Error::InvalidFrequency)));
+        let rule = RecurrenceRule {
+            ends_at: Some(bill.due_date - 1),
+            ..recurrence_rule(Recurrence::Yearly)
+        };
+        let res = client.try_set_bill_recurrence(&owner, &bill_id, &rule);
+        assert_eq!(res, Err(Ok(Error::InvalidDueDate)));
+        let rule = recurrence_rule(Recurrence::Yearly);
+        let res = client.try_set_bill_recurrence(&other, &bill_id, &rule);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        // Turning recurrence off makes the bill one-off.
+        client.set_bill_recurrence(&owner, &bill_id, &recurrence_rule(Recurrence::None));
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.recurring);
+        assert_eq!(bill.frequency_days, 0);
+        client.pay_bill(&owner, &bill_id);
+        assert!(client.get_bill(&(bill_id + 1)).is_none());
+    }
+
+    // -----------------------------------------------------------------------
+    // Strict Owner Authorization Lifecycle Tests
+    // -----------------------------------------------------------------------
+
+    /// ### Test: `test_create_bill_no_auth_fails`
+    /// **Objective**: Verify that `create_bill` reverts if the owner doesn't authorize the call.
+    /// **Expected**: Reverts with a Soroban AuthError.
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_create_bill_no_auth_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Attempting to create a bill without mocking auth should fail on owner.require_auth()
+        client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+    }
+
+    /// ### Test: `test_pay_bill_wrong_owner_fails`
+    /// **Objective**: Verify that `pay_bill` reverts if a caller attempts to pay a bill they don't own.
+    /// **Authorized Caller**: `bill.owner`
+    /// **Unauthorized Caller**: `other`
+    /// **Expected**: Returns `Error::Unauthorized`.
+    #[test]
+    fn test_pay_bill_wrong_owner_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // 'other' attempts to pay owner's bill
+        let result = client.try_pay_bill(&other, &bill_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    /// ### Test: `test_pay_bill_no_auth_fails`
+    /// **Objective**: Verify that `pay_bill` reverts if the caller is the owner but does not authorize the call.
+    /// **Expected**: Reverts with a Soroban AuthError.
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_pay_bill_no_auth_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Use mock_auths specifically for creation so it doesn't affect the pay_bill call
+        env.mock_all_auths();
+        let _bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // This will panic as expected because we are NOT mocking auths for this call
+        // and 'owner.require_auth()' will fail.
+        // We set mock_all_auths to false to disable the global mock.
+        env.set_auths(&[]); 
+        client.pay_bill(&owner, &_bill_id);
+    }
+
+    #[test]
+    fn test_cancel_bill_wrong_owner_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Cancel"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let result = client.try_cancel_bill(&other, &bill_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_set_external_ref_wrong_owner_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "ExtRef"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let result =
+            client.try_set_external_ref(&other, &bill_id, &Some(String::from_str(&env, "REF")));
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_restore_bill_wrong_owner_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        env.mock_all_auths();
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Restore"),
+            &500,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+        client.pay_bill(&owner, &bill_id);
+
+        // Archive it
+        client.archive_paid_bills(&owner, &2000000);
+
+        // Other tries to restore
+        let result = client.try_restore_bill(&other, &bill_id);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    fn test_batch_pay_bills_mixed_ownership_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        env.mock_all_auths();
+        let alice_bill = client.create_bill(
+            &alice,
+            &String::from_str(&env, "Alice"),
+            &100,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+        let bob_bill = client.create_bill(
+            &bob,
+            &String::from_str(&env, "Bob"),
+            &200,
+            &1000000,
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        let mut ids = Vec::new(&env);
+        ids.push_back(alice_bill);
+        ids.push_back(bob_bill);
+
+        // Alice tries to batch pay both, but one is Bob's
+        let result = client.try_batch_pay_bills(&alice, &ids);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_archive_paid_bills_no_auth_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let caller = Address::generate(&env);
+
+        // No sign, should fail on caller.require_auth()
+        client.archive_paid_bills(&caller, &1000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Auth, InvalidAction)")]
+    fn test_bulk_cleanup_bills_no_auth_fails() {
+        let env = make_env();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+
+        client.bulk_cleanup_bills(&admin, &1000000);
+    }
+
+    #[test]
+    fn test_create_bill_max_frequency_exceeded() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let result = client.try_create_bill(
+            &owner,
+            &String::from_str(&env, "Too Long"),
+            &100,
+            &1000000,
+            &true,
+            &(MAX_FREQUENCY_DAYS + 1), // Exceeds max frequency
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+    }
+
+    #[test]
+    fn test_pay_bill_date_overflow_protection() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        // Create a bill with a very large due_date
+        let bill_id = client.create_bill(
+            &owner,
+            &String::from_str(&env, "Overflow Potential"),
+            &100,
+            &(u64::MAX - 100), // Near u64::MAX
+            &true,
+            &30,
+            &None,
+            &String::from_str(&env, "XLM"),
+        );
+
+        // Paying this should fail because next_due_date would overflow
+        let result = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(result, Err(Ok(Error::InvalidDueDate)));
     }
-}
 
-// -----------------------------------------------------------------------
-// Tests
-// -----------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
-    use proptest::prelude::*;
-    use soroban_sdk::{
-        testutils::{Address as _, Ledger},
-        Env, String,
-    };
+    // -----------------------------------------------------------------------
+    // Payee settlement tests
+    // -----------------------------------------------------------------------
 
-    fn make_env() -> Env {
-        Env::default()
+    /// Register a token and mint `amount` to `owner`. Returns the token address.
+    fn setup_token(env: &Env, owner: &Address, amount: i128) -> Address {
+        let admin = Address::generate(env);
+        let token = env.register_stellar_asset_contract_v2(admin).address();
+        soroban_sdk::token::StellarAssetClient::new(env, &token).mint(owner, &amount);
+        token
     }
 
-    /// Create `count` bills with a static name. Returns their IDs.
-    /// Due dates are set in the future so they are NOT overdue.
-    fn setup_bills(
+    fn create_test_bill(
         env: &Env,
         client: &BillPaymentsClient,
         owner: &Address,
-        count: u32,
-    ) -> Vec<u32> {
-        let mut ids = Vec::new(env);
-        for i in 0..count {
-            let id = client.create_bill(
-                owner,
-                &String::from_str(env, "Test Bill"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-                &None,
-                &String::from_str(env, "XLM"),
-            );
-            ids.push_back(id);
-        }
-        ids
+        amount: i128,
+        recurring: bool,
+    ) -> u32 {
+        client.create_bill(
+            owner,
+            &String::from_str(env, "Electricity"),
+            &amount,
+            &(env.ledger().timestamp() + 86400),
+            &recurring,
+            &(if recurring { 30 } else { 0 }),
+            &None,
+            &String::from_str(env, "USDC"),
+        )
     }
 
-    // --- get_unpaid_bills ---
-
     #[test]
-    fn test_get_unpaid_bills_empty() {
+    fn test_pay_bill_transfers_to_payee() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
 
-        let page = client.get_unpaid_bills(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
-        assert_eq!(page.items.len(), 0);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.pay_bill(&owner, &bill_id);
+
+        assert_eq!(token_client.balance(&owner), 700);
+        assert_eq!(token_client.balance(&payee), 300);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.amount_paid, 300);
+        assert_eq!(bill.paid_token, Some(token));
     }
 
     #[test]
-    fn test_get_unpaid_bills_single_page() {
+    fn test_pay_bill_without_payee_records_payment_only() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        setup_bills(&env, &client, &owner, 5);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.pay_bill(&owner, &bill_id);
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 5);
-        assert_eq!(page.next_cursor, 0);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.amount_paid, 300);
+        assert_eq!(bill.paid_token, None);
     }
 
     #[test]
-    fn test_get_unpaid_bills_multiple_pages() {
+    fn test_pay_bill_insufficient_funds_leaves_bill_unpaid() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 100);
 
-        setup_bills(&env, &client, &owner, 7);
-
-        let page1 = client.get_unpaid_bills(&owner, &0, &3);
-        assert_eq!(page1.count, 3);
-        assert!(page1.next_cursor > 0, "expected a next cursor");
-
-        let page2 = client.get_unpaid_bills(&owner, &page1.next_cursor, &3);
-        assert_eq!(page2.count, 3);
-        assert!(page2.next_cursor > 0);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
 
-        let page3 = client.get_unpaid_bills(&owner, &page2.next_cursor, &3);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
+        assert_eq!(
+            client.try_pay_bill(&owner, &bill_id),
+            Err(Ok(Error::InsufficientFunds))
+        );
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.amount_paid, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 300);
     }
 
     #[test]
-    fn test_get_unpaid_bills_excludes_paid() {
+    fn test_batch_pay_bills_skips_unfunded_transfers() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 500);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
 
-        let ids = setup_bills(&env, &client, &owner, 4);
-        let second_id = ids.get(1).unwrap();
-        client.pay_bill(&owner, &second_id);
+        let first = create_test_bill(&env, &client, &owner, 400, false);
+        let second = create_test_bill(&env, &client, &owner, 400, false);
+        let offline = create_test_bill(&env, &client, &owner, 50, false);
+        client.set_bill_payee(&owner, &first, &payee, &token);
+        client.set_bill_payee(&owner, &second, &payee, &token);
 
-        let page = client.get_unpaid_bills(&owner, &0, &10);
-        assert_eq!(page.count, 3);
+        let mut ids = Vec::new(&env);
+        ids.push_back(first);
+        ids.push_back(second);
+        ids.push_back(offline);
+        assert_eq!(client.batch_pay_bills(&owner, &ids), 2);
+
+        assert_eq!(token_client.balance(&payee), 400);
+        assert!(client.get_bill(&first).unwrap().paid);
+        assert!(!client.get_bill(&second).unwrap().paid);
+        assert!(client.get_bill(&offline).unwrap().paid);
+        assert_eq!(client.get_total_unpaid(&owner), 400);
     }
 
     #[test]
-    fn test_get_unpaid_bills_excludes_other_owner() {
+    fn test_set_bill_payee_validation_and_recurrence() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
 
-        setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
+        let bill_id = create_test_bill(&env, &client, &owner, 200, true);
+        assert_eq!(
+            client.try_set_bill_payee(&owner, &bill_id, &owner, &token),
+            Err(Ok(Error::InvalidPayee))
+        );
+        assert_eq!(
+            client.try_set_bill_payee(&other, &bill_id, &payee, &token),
+            Err(Ok(Error::Unauthorized))
+        );
+        assert_eq!(
+            client.try_set_bill_payee(&owner, &99, &payee, &token),
+            Err(Ok(Error::BillNotFound))
+        );
 
-        let page = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page.count, 3);
-        for bill in page.items.iter() {
-            assert_eq!(bill.owner, owner_a);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.pay_bill(&owner, &bill_id);
+        assert_eq!(
+            client.try_clear_bill_payee(&owner, &bill_id),
+            Err(Ok(Error::BillAlreadyPaid))
+        );
+
+        // The next recurring bill keeps the payee but starts unsettled.
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.payee, Some(payee));
+        assert_eq!(next.token, Some(token));
+        assert_eq!(next.amount_paid, 0);
+        assert_eq!(next.paid_token, None);
+
+        client.clear_bill_payee(&owner, &next.id);
+        assert_eq!(client.get_bill(&next.id).unwrap().payee, None);
+    }
+
+    // -----------------------------------------------------------------------
+    // Biller registry tests
+    // -----------------------------------------------------------------------
+
+    fn meter_format(env: &Env) -> RefFormat {
+        RefFormat {
+            prefix: String::from_str(env, "MTR"),
+            min_len: 8,
+            max_len: 11,
+            charset: RefCharset::Numeric,
         }
     }
 
+    /// Register and verify a biller accepting USDC in `token`. Returns its ID.
+    fn setup_biller(
+        env: &Env,
+        client: &BillPaymentsClient,
+        admin: &Address,
+        name: &str,
+        payee: &Address,
+        token: &Address,
+    ) -> u32 {
+        let mut currencies = Map::new(env);
+        currencies.set(String::from_str(env, "usdc"), token.clone());
+        let id = client.register_biller(
+            admin,
+            &String::from_str(env, name),
+            payee,
+            &currencies,
+            &meter_format(env),
+        );
+        client.set_biller_status(admin, &id, &BillerStatus::Verified);
+        id
+    }
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_bidirectional() {
+    fn test_register_biller_requires_admin() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let other = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        let mut currencies = Map::new(&env);
+        currencies.set(String::from_str(&env, "USDC"), token);
+        let name = String::from_str(&env, "NEPA");
+
+        let format = meter_format(&env);
+        let res = client.try_register_biller(&admin, &name, &payee, &currencies, &format);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        client.set_upgrade_admin(&admin, &admin);
+        let res = client.try_register_biller(&other, &name, &payee, &currencies, &format);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        let id = client.register_biller(&admin, &name, &payee, &currencies, &format);
+        let biller = client.get_biller(&id).unwrap();
+        assert_eq!(biller.status, BillerStatus::Pending);
+        assert_eq!(biller.payee, payee);
+    }
 
-        setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 3);
+    #[test]
+    fn test_register_biller_rejects_bad_config() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let name = String::from_str(&env, "NEPA");
+        let mut currencies = Map::new(&env);
+        currencies.set(String::from_str(&env, "USDC"), token.clone());
+
+        let mut format = meter_format(&env);
+        format.max_len = MAX_REF_LEN + 1;
+        let res = client.try_register_biller(&admin, &name, &payee, &currencies, &format);
+        assert_eq!(res, Err(Ok(Error::InvalidBillerConfig)));
+
+        let mut format = meter_format(&env);
+        format.min_len = 2; // shorter than the prefix
+        let res = client.try_register_biller(&admin, &name, &payee, &currencies, &format);
+        assert_eq!(res, Err(Ok(Error::InvalidBillerConfig)));
+
+        let format = meter_format(&env);
+        let res = client.try_register_biller(&admin, &name, &payee, &Map::new(&env), &format);
+        assert_eq!(res, Err(Ok(Error::InvalidBillerConfig)));
+
+        let mut bad_currencies = Map::new(&env);
+        bad_currencies.set(String::from_str(&env, "US$"), token);
+        let res = client.try_register_biller(&admin, &name, &payee, &bad_currencies, &format);
+        assert_eq!(res, Err(Ok(Error::InvalidCurrency)));
+    }
 
-        // owner_a sees only their own bills
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 2);
-        for bill in page_a.items.iter() {
-            assert_eq!(
-                bill.owner, owner_a,
-                "owner_a page must not contain owner_b bills"
-            );
-        }
+    #[test]
+    fn test_create_bill_for_biller_uses_biller_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
 
-        // owner_b sees only their own bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3);
-        for bill in page_b.items.iter() {
-            assert_eq!(
-                bill.owner, owner_b,
-                "owner_b page must not contain owner_a bills"
+        let bill_id = client.create_bill_for_biller(
+            &owner,
+            &biller_id,
+            &400,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
+        );
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.biller_id, Some(biller_id));
+        assert_eq!(bill.name, String::from_str(&env, "NEPA"));
+        assert_eq!(bill.payee, Some(payee.clone()));
+        assert_eq!(bill.token, Some(token.clone()));
+
+        client.pay_bill(&owner, &bill_id);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&payee), 400);
+    }
+
+    #[test]
+    fn test_create_bill_for_biller_validates_reference_and_currency() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+        let due = env.ledger().timestamp() + 86400;
+        let usdc = String::from_str(&env, "USDC");
+
+        for bad_ref in ["MTR123", "MTR123456789", "ABC12345", "MTR12a45"] {
+            let res = client.try_create_bill_for_biller(
+                &owner,
+                &biller_id,
+                &100,
+                &due,
+                &false,
+                &0,
+                &String::from_str(&env, bad_ref),
+                &usdc,
             );
+            assert_eq!(res, Err(Ok(Error::InvalidExternalRef)));
         }
+
+        let res = client.try_create_bill_for_biller(
+            &owner,
+            &biller_id,
+            &100,
+            &due,
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "NGN"),
+        );
+        assert_eq!(res, Err(Ok(Error::CurrencyNotAccepted)));
+
+        let res = client.try_create_bill_for_biller(
+            &owner,
+            &99,
+            &100,
+            &due,
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &usdc,
+        );
+        assert_eq!(res, Err(Ok(Error::BillerNotFound)));
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_after_one_pays() {
-        // If owner_a pays their bill, owner_b's unpaid bills are unaffected
+    fn test_unverified_biller_cannot_be_billed() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+        client.set_biller_status(&admin, &biller_id, &BillerStatus::Suspended);
 
-        let ids_a = setup_bills(&env, &client, &owner_a, 2);
-        setup_bills(&env, &client, &owner_b, 2);
+        let res = client.try_create_bill_for_biller(
+            &owner,
+            &biller_id,
+            &100,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
+        );
+        assert_eq!(res, Err(Ok(Error::BillerNotVerified)));
+    }
 
-        // owner_a pays one of their bills
-        client.pay_bill(&owner_a, &ids_a.get(0).unwrap());
+    #[test]
+    fn test_update_biller_payee_requires_reverification() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+        let biller = client.get_biller(&biller_id).unwrap();
+
+        // Same payee: verification is kept.
+        client.update_biller(
+            &admin,
+            &biller_id,
+            &String::from_str(&env, "NEPA Ikeja"),
+            &payee,
+            &biller.currencies,
+            &biller.ref_format,
+        );
+        assert_eq!(
+            client.get_biller(&biller_id).unwrap().status,
+            BillerStatus::Verified
+        );
 
-        // owner_a now has 1 unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 1);
-        for bill in page_a.items.iter() {
-            assert_eq!(bill.owner, owner_a, "Should only see owner_a bills");
-            assert!(!bill.paid, "Should only see unpaid bills");
-        }
+        let new_payee = Address::generate(&env);
+        client.update_biller(
+            &admin,
+            &biller_id,
+            &biller.name,
+            &new_payee,
+            &biller.currencies,
+            &biller.ref_format,
+        );
+        let biller = client.get_biller(&biller_id).unwrap();
+        assert_eq!(biller.status, BillerStatus::Pending);
+        assert_eq!(biller.payee, new_payee);
+    }
 
-        // owner_b still has 2 unpaid — unaffected by owner_a's payment
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b, "Should only see owner_b bills");
-        }
+    #[test]
+    fn test_set_external_ref_checks_biller_format() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+        let bill_id = client.create_bill_for_biller(
+            &owner,
+            &biller_id,
+            &100,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
+        );
+
+        let short_ref = Some(String::from_str(&env, "12345"));
+        let res = client.try_set_external_ref(&owner, &bill_id, &short_ref);
+        assert_eq!(res, Err(Ok(Error::InvalidExternalRef)));
+        let res = client.try_set_external_ref(&owner, &bill_id, &None);
+        assert_eq!(res, Err(Ok(Error::InvalidExternalRef)));
+
+        client.set_external_ref(&owner, &bill_id, &Some(String::from_str(&env, "MTR99999")));
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().external_ref,
+            Some(String::from_str(&env, "MTR99999"))
+        );
+    }
+
+    #[test]
+    fn test_search_billers_matches_verified_names() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let lagos_water = setup_biller(&env, &client, &admin, "Lagos Water", &payee, &token);
+        setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+        let lagos_uni = setup_biller(&env, &client, &admin, "University of Lagos", &payee, &token);
+        let suspended = setup_biller(&env, &client, &admin, "Lagos Waste", &payee, &token);
+        client.set_biller_status(&admin, &suspended, &BillerStatus::Suspended);
+
+        let page = client.search_billers(&String::from_str(&env, "LAGOS"), &0, &10);
+        assert_eq!(page.count, 2);
+        assert_eq!(page.items.get(0).unwrap().id, lagos_water);
+        assert_eq!(page.items.get(1).unwrap().id, lagos_uni);
+        assert_eq!(page.next_cursor, 0);
+
+        let page = client.search_billers(&String::from_str(&env, "lagos"), &0, &1);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.next_cursor, lagos_water);
+        let page = client.search_billers(&String::from_str(&env, "lagos"), &page.next_cursor, &1);
+        assert_eq!(page.items.get(0).unwrap().id, lagos_uni);
+        assert_eq!(page.next_cursor, 0);
+
+        let all = client.search_billers(&String::from_str(&env, ""), &0, &10);
+        assert_eq!(all.count, 3);
     }
 
+    // -----------------------------------------------------------------------
+    // Bills pool tests
+    // -----------------------------------------------------------------------
+
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_one_owner_no_bills() {
-        // owner_b has bills but owner_a has none — owner_a gets empty page
+    fn test_pay_bill_draws_from_pool() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
 
-        // Only owner_b creates bills
-        setup_bills(&env, &client, &owner_b, 3);
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &500), 0);
+        assert_eq!(client.get_pool_balance(&owner, &token), 500);
+        assert_eq!(token_client.balance(&owner), 500);
 
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should see no bills");
-        assert_eq!(page_a.next_cursor, 0);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.pay_bill(&owner, &bill_id);
 
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 3, "owner_b should see all their bills");
+        assert_eq!(client.get_pool_balance(&owner, &token), 200);
+        assert_eq!(token_client.balance(&owner), 500);
+        assert_eq!(token_client.balance(&payee), 300);
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_all_paid_other_owner_unpaid() {
-        // owner_a pays all their bills — owner_b's unpaid still isolated correctly
+    fn test_pay_bill_falls_back_to_wallet_when_pool_short() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-
-        let ids_a = setup_bills(&env, &client, &owner_a, 3);
-        setup_bills(&env, &client, &owner_b, 2);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
 
-        // owner_a pays all their bills
-        for id in ids_a.iter() {
-            client.pay_bill(&owner_a, &id);
-        }
+        client.top_up_pool(&owner, &owner, &token, &100);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.pay_bill(&owner, &bill_id);
 
-        // owner_a has zero unpaid
-        let page_a = client.get_unpaid_bills(&owner_a, &0, &10);
-        assert_eq!(page_a.count, 0, "owner_a should have no unpaid bills left");
+        assert_eq!(client.get_pool_balance(&owner, &token), 100);
+        assert_eq!(token_client.balance(&owner), 600);
+        assert_eq!(token_client.balance(&payee), 300);
+    }
 
-        // owner_b still has 2 unpaid — not polluted by owner_a's paid bills
-        let page_b = client.get_unpaid_bills(&owner_b, &0, &10);
-        assert_eq!(page_b.count, 2);
-        for bill in page_b.items.iter() {
-            assert_eq!(bill.owner, owner_b);
-            assert!(!bill.paid);
-        }
+    #[test]
+    fn test_top_up_pool_by_third_party_and_withdraw() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let funder = Address::generate(&env);
+        let token = setup_token(&env, &funder, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        client.top_up_pool(&funder, &owner, &token, &400);
+        assert_eq!(client.get_pool_balance(&owner, &token), 400);
+        assert_eq!(client.get_pool_balance(&funder, &token), 0);
+
+        let res = client.try_withdraw_from_pool(&owner, &token, &500);
+        assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
+        assert_eq!(client.withdraw_from_pool(&owner, &token, &150), 250);
+        assert_eq!(token_client.balance(&owner), 150);
+
+        let res = client.try_top_up_pool(&funder, &owner, &token, &0);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        let res = client.try_top_up_pool(&funder, &owner, &token, &1_000);
+        assert_eq!(res, Err(Ok(Error::InsufficientFunds)));
     }
 
     #[test]
-    fn test_get_unpaid_bills_owner_isolation_pagination_does_not_leak() {
-        // With many owners, paginating through owner_a's results never leaks owner_b's bills
+    fn test_pool_and_auto_pay_live_in_per_owner_entries() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let pool_key = DataKey::Pool(owner.clone(), token.clone());
+        let auto_key = DataKey::AutoPay(owner.clone());
+        let has = |key: &DataKey| env.as_contract(&cid, || env.storage().persistent().has(key));
+
+        client.top_up_pool(&owner, &owner, &token, &400);
+        client.set_auto_pay(&owner, &true);
+        assert!(has(&pool_key));
+        assert!(has(&auto_key));
+
+        client.withdraw_from_pool(&owner, &token, &400);
+        client.set_auto_pay(&owner, &false);
+        assert!(!has(&pool_key));
+        assert!(!has(&auto_key));
+        assert!(!client.is_auto_pay_enabled(&owner));
+    }
 
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
+    #[test]
+    fn test_auto_pay_pays_due_bills_in_due_date_order() {
+        let env = make_env();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_000);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let usdc = String::from_str(&env, "USDC");
 
-        // Interleave bills: a, b, a, b, a, b ...
-        for i in 0..4u32 {
-            // Added the 'currency' argument at the end to match the new signature
-            client.create_bill(
-                &owner_a,
-                &String::from_str(&env, "Bill A"),
-                &(100i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
-                &false,
-                &0,
-                &None,
-                &String::from_str(&env, "XLM"),
-            );
-            client.create_bill(
-                &owner_b,
-                &String::from_str(&env, "Bill B"),
-                &(200i128 * (i as i128 + 1)),
-                &(env.ledger().timestamp() + 86400 * (i as u64 + 1)),
+        let create = |amount: i128, due: u64| {
+            let id = client.create_bill(
+                &owner,
+                &String::from_str(&env, "Bill"),
+                &amount,
+                &due,
                 &false,
                 &0,
                 &None,
-                &String::from_str(&env, "XLM"),
+                &usdc,
             );
-        }
-
-        // Paginate through owner_a with small page size
-        let mut all_a_bills: soroban_sdk::Vec<Bill> = soroban_sdk::Vec::new(&env);
-        let mut cursor = 0u32;
-
-        loop {
-            // Assuming your get_unpaid_bills function returns a struct with 'items' and 'next_cursor'
-            let page = client.get_unpaid_bills(&owner_a, &cursor, &2);
-
-            for bill in page.items.iter() {
-                assert_eq!(
-                    bill.owner, owner_a,
-                    "Paginated result must never contain owner_b's bill"
-                );
-                // Verification: ensure the default currency logic worked
-                assert_eq!(bill.currency, String::from_str(&env, "XLM"));
-
-                all_a_bills.push_back(bill);
-            }
-
-            if page.next_cursor == 0 {
-                break;
-            }
-            cursor = page.next_cursor;
-        }
-
-        assert_eq!(
-            all_a_bills.len(),
-            4,
-            "owner_a should have exactly 4 bills across all pages"
-        );
+            client.set_bill_payee(&owner, &id, &payee, &token);
+            id
+        };
+        let later = create(200, 3_000);
+        let earliest = create(100, 2_000);
+        let not_due = create(50, 10_000);
+        let last = create(400, 4_000);
+
+        client.set_auto_pay(&owner, &true);
+        env.ledger().set_timestamp(5_000);
+
+        // Covers the first two due bills but not the third.
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &350), 2);
+        assert!(client.get_bill(&earliest).unwrap().paid);
+        assert!(client.get_bill(&later).unwrap().paid);
+        assert!(!client.get_bill(&last).unwrap().paid);
+        assert!(!client.get_bill(&not_due).unwrap().paid);
+        assert_eq!(client.get_pool_balance(&owner, &token), 50);
+        assert_eq!(client.get_total_unpaid(&owner), 450);
+
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &350), 1);
+        assert!(client.get_bill(&last).unwrap().paid);
+        assert_eq!(client.get_pool_balance(&owner, &token), 0);
     }
 
-    // --- get_overdue_bills ---
-
     #[test]
-    fn test_get_overdue_bills_not_overdue() {
+    fn test_auto_pay_from_pool_pays_only_the_owners_bills() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let neighbour = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 10_000);
+
+        // The neighbour's bill is due first and shares the payee and token.
+        let neighbours = create_bill_due(&env, &client, &neighbour, 1_000);
+        client.set_bill_payee(&neighbour, &neighbours, &payee, &token);
+        let mut ids = StdVec::new();
+        for i in 0..MAX_BATCH_SIZE + 1 {
+            let id = create_bill_due(&env, &client, &owner, 2_000 + i as u64);
+            client.set_bill_payee(&owner, &id, &payee, &token);
+            ids.push(id);
+        }
+        client.set_auto_pay(&owner, &true);
+        env.ledger().set_timestamp(10_000);
+        env.budget().reset_unlimited();
 
-        setup_bills(&env, &client, &owner, 3);
-        let page = client.get_overdue_bills(&0, &10);
-        assert_eq!(page.count, 0);
+        // One batch at most; the next top-up pays the rest.
+        assert_eq!(
+            client.top_up_pool(&owner, &owner, &token, &9_000),
+            MAX_BATCH_SIZE
+        );
+        assert!(!client.get_bill(&neighbours).unwrap().paid);
+        assert!(!client.get_bill(ids.last().unwrap()).unwrap().paid);
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &1), 1);
+        assert!(ids.iter().all(|id| client.get_bill(id).unwrap().paid));
     }
 
     #[test]
-    fn test_get_overdue_bills_pagination() {
+    fn test_top_up_without_auto_pay_leaves_bills_unpaid() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
 
-        // 1. Set initial time so create_bill succeeds
-        // The contract requires: due_date >= current_time
-        env.ledger().set_timestamp(10000);
-
-        let due_date = 20000;
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2 * 86400);
 
-        for _ in 0..6u32 {
-            client.create_bill(
-                &owner,
-                &String::from_str(&env, "Overdue Bill"),
-                &100,
-                &due_date, // 20000
-                &false,
-                &0,
-                &None,
-                &String::from_str(&env, "XLM"),
-            );
-        }
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &500), 0);
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
 
-        // 2. Advance time PAST the due date to make them "Overdue"
-        // current_time (25000) > due_date (20000)
-        env.ledger().set_timestamp(25000);
+        client.set_auto_pay(&owner, &true);
+        assert!(client.is_auto_pay_enabled(&owner));
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &1), 1);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+    }
 
-        // Now get_overdue_bills will actually find the 6 bills
-        let page1 = client.get_overdue_bills(&0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
+    // -----------------------------------------------------------------------
+    // Scheduled auto-pay tests
+    // -----------------------------------------------------------------------
 
-        let page2 = client.get_overdue_bills(&page1.next_cursor, &4);
-        assert_eq!(page2.count, 2);
-        assert_eq!(page2.next_cursor, 0);
+    /// Create a bill due in one day with a payee and auto-pay enabled.
+    fn create_auto_pay_bill(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        payee: &Address,
+        token: &Address,
+        amount: i128,
+        recurring: bool,
+    ) -> u32 {
+        let id = create_test_bill(env, client, owner, amount, recurring);
+        client.set_bill_payee(owner, &id, payee, token);
+        client.set_bill_auto_pay(owner, &id, &true);
+        id
     }
 
-    // --- get_all_bills_for_owner ---
-
     #[test]
-    fn test_get_all_bills_for_owner_includes_paid() {
+    fn test_execute_due_bills_pays_from_pool() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-
-        let ids = setup_bills(&env, &client, &owner, 5);
-        let first_id = ids.get(0).unwrap();
-        client.pay_bill(&owner, &first_id);
-
-        let page = client.get_all_bills_for_owner(&owner, &0, &10);
-        assert_eq!(page.count, 5);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        let auto = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 200, true);
+        let manual = create_test_bill(&env, &client, &owner, 100, false);
+        client.set_bill_payee(&owner, &manual, &payee, &token);
+        client.top_up_pool(&owner, &owner, &token, &500);
+
+        // Nothing is due yet.
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid.len(), 0);
+
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid, Vec::from_array(&env, [auto]));
+        assert_eq!(result.failed.len(), 0);
+        assert_eq!(result.next_cursor, 0);
+
+        assert!(client.get_bill(&auto).unwrap().paid);
+        assert!(!client.get_bill(&manual).unwrap().paid);
+        assert_eq!(token_client.balance(&payee), 200);
+        assert_eq!(client.get_pool_balance(&owner, &token), 300);
+
+        // The next occurrence keeps auto-pay but is not due yet.
+        let next = client.get_bill(&(manual + 1)).unwrap();
+        assert!(next.auto_pay);
+        assert!(!next.paid);
+        assert_eq!(client.execute_due_bills(&0, &0).paid.len(), 0);
     }
 
-    // --- limit clamping ---
-
     #[test]
-    fn test_limit_zero_uses_default() {
+    fn test_execute_due_bills_uses_allowance() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-
-        setup_bills(&env, &client, &owner, 3);
-        let page = client.get_unpaid_bills(&owner, &0, &0);
-        assert_eq!(page.count, 3);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        let bill_id = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 300, false);
+        token_client.approve(&owner, &cid, &300, &(env.ledger().sequence() + 1_000));
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid, Vec::from_array(&env, [bill_id]));
+        assert_eq!(token_client.balance(&owner), 700);
+        assert_eq!(token_client.balance(&payee), 300);
+        assert_eq!(client.get_bill(&bill_id).unwrap().paid_token, Some(token));
     }
 
     #[test]
-    fn test_limit_clamped_to_max() {
+    fn test_execute_due_bills_records_failure_without_paying() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-
-        setup_bills(&env, &client, &owner, 55);
-        let page = client.get_unpaid_bills(&owner, &0, &9999);
-        assert_eq!(page.count, MAX_PAGE_LIMIT);
-        assert!(page.next_cursor > 0);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+
+        let bill_id = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 300, false);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+
+        // No pool balance and no allowance.
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid.len(), 0);
+        assert_eq!(result.failed, Vec::from_array(&env, [bill_id]));
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.auto_pay_failures, 1);
+        assert_eq!(client.get_total_unpaid(&owner), 300);
+
+        client.execute_due_bills(&0, &0);
+        assert_eq!(client.get_bill(&bill_id).unwrap().auto_pay_failures, 2);
+
+        client.top_up_pool(&owner, &owner, &token, &300);
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid, Vec::from_array(&env, [bill_id]));
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.auto_pay_failures, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
     }
 
-    // --- archived bill pagination ---
-
     #[test]
-    fn test_get_archived_bills_pagination() {
+    fn test_execute_due_bills_respects_limit_and_cursor() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+
+        let first = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        let second = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        let third = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        client.top_up_pool(&owner, &owner, &token, &300);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+
+        let result = client.execute_due_bills(&0, &2);
+        assert_eq!(result.paid, Vec::from_array(&env, [first, second]));
+        assert_eq!(result.next_cursor, second);
+
+        let result = client.execute_due_bills(&result.next_cursor, &2);
+        assert_eq!(result.paid, Vec::from_array(&env, [third]));
+        assert_eq!(result.next_cursor, 0);
+    }
 
-        client.set_pause_admin(&owner, &owner);
+    #[test]
+    fn test_execute_due_bills_skips_bills_without_auto_pay() {
+        let env = make_env();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
 
-        let ids = setup_bills(&env, &client, &owner, 6);
-        for bill_id in ids.iter() {
-            client.pay_bill(&owner, &bill_id);
+        // More due manual bills than a call may visit, due before the auto-pay one.
+        for _ in 0..MAX_SCAN_IDS {
+            create_test_bill(&env, &client, &owner, 10, false);
         }
-        client.archive_paid_bills(&owner, &u64::MAX);
-
-        let page1 = client.get_archived_bills(&owner, &0, &4);
-        assert_eq!(page1.count, 4);
-        assert!(page1.next_cursor > 0);
-
-        let page2 = client.get_archived_bills(&owner, &page1.next_cursor, &4);
-        assert_eq!(page2.count, 2);
-        assert_eq!(page2.next_cursor, 0);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+        let auto = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        client.top_up_pool(&owner, &owner, &token, &100);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid, Vec::from_array(&env, [auto]));
+        assert_eq!(result.next_cursor, 0);
     }
 
-    // -----------------------------------------------------------------------
-    // RECURRING BILLS DATE MATH TESTS
-    // -----------------------------------------------------------------------
-    // These tests verify the core date math for recurring bills:
-    // next_due_date = due_date + (frequency_days * 86400)
-    // Ensures paid_at does not affect next bill's due_date calculation.
-
     #[test]
-    fn test_recurring_date_math_frequency_1_day() {
-        // Test: frequency_days = 1 → next due date is +1 day (86400 seconds)
+    fn test_auto_pay_index_follows_bill_changes() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let indexed = || -> Vec<u64> {
+            env.as_contract(&cid, || {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::AutoPayBuckets)
+                    .unwrap_or_else(|| Vec::new(&env))
+            })
+        };
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Daily Bill"),
-            &100,
-            &base_due_date,
-            &true, // recurring
-            &1,    // frequency_days = 1
-            &None,
-            &String::from_str(&env, "XLM"),
+        let off = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        client.set_bill_auto_pay(&owner, &off, &false);
+        let cleared = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        client.clear_bill_payee(&owner, &cleared);
+        let cancelled = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, false);
+        client.cancel_bill(&owner, &cancelled);
+        assert_eq!(indexed().len(), 0);
+
+        // A paid recurring bill leaves the index; its next occurrence joins it.
+        let recurring = create_auto_pay_bill(&env, &client, &owner, &payee, &token, 100, true);
+        client.pay_bill(&owner, &recurring);
+        let next = client.get_bill(&(recurring + 1)).unwrap();
+        assert!(next.auto_pay);
+        assert_eq!(
+            indexed(),
+            Vec::from_array(&env, [next.due_date / INDEX_BUCKET_SECONDS])
         );
+    }
 
-        // Pay the bill
-        client.pay_bill(&owner, &bill_id);
+    #[test]
+    fn test_set_bill_auto_pay_requires_payee() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
+        let res = client.try_set_bill_auto_pay(&owner, &bill_id, &true);
+        assert_eq!(res, Err(Ok(Error::PayeeNotSet)));
+
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        let res = client.try_set_bill_auto_pay(&other, &bill_id, &true);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.set_bill_auto_pay(&owner, &bill_id, &true);
+        assert!(client.get_bill(&bill_id).unwrap().auto_pay);
+
+        // Clearing the payee turns auto-pay off.
+        client.clear_bill_payee(&owner, &bill_id);
+        assert!(!client.get_bill(&bill_id).unwrap().auto_pay);
+    }
 
-        // Verify next bill's due_date = base_due_date + (1 * 86400)
-        let next_bill = client.get_bill(&2).unwrap();
-        assert!(!next_bill.paid, "Next bill should be unpaid");
-        assert_eq!(
-            next_bill.due_date,
-            base_due_date + 86400,
-            "Next due date should be exactly 1 day later"
-        );
-        assert_eq!(next_bill.frequency_days, 1, "Frequency should be preserved");
+    // -----------------------------------------------------------------------
+    // Partial payment tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_pay_bill_partial_until_paid() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 1_000, false);
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &400), 600);
+        assert_eq!(client.get_total_unpaid(&owner), 600);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert_eq!(bill.amount_paid, 400);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
+
+        assert_eq!(client.pay_bill_partial(&owner, &bill_id, &600), 0);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.amount_paid, 1_000);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+
+        let payments = client.get_bill_payments(&bill_id);
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments.get(0).unwrap().amount, 400);
+        assert_eq!(payments.get(1).unwrap().amount, 600);
+        assert_eq!(payments.get(1).unwrap().payer, owner);
     }
 
     #[test]
-    fn test_recurring_date_math_frequency_30_days() {
-        // Test: frequency_days = 30 → next due date is +30 days (2,592,000 seconds)
+    fn test_pay_bill_partial_rejects_overpayment() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Monthly Bill"),
-            &500,
-            &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+        let bill_id = create_test_bill(&env, &client, &owner, 500, false);
+        client.pay_bill_partial(&owner, &bill_id, &300);
+        let res = client.try_pay_bill_partial(&owner, &bill_id, &201);
+        assert_eq!(res, Err(Ok(Error::Overpayment)));
+        let res = client.try_pay_bill_partial(&owner, &bill_id, &0);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        assert_eq!(client.get_bill(&bill_id).unwrap().amount_paid, 300);
 
-        // Pay the bill
         client.pay_bill(&owner, &bill_id);
-
-        // Verify next bill's due_date = base_due_date + (30 * 86400)
-        let next_bill = client.get_bill(&2).unwrap();
-        assert!(!next_bill.paid, "Next bill should be unpaid");
-        let expected_due_date = base_due_date + (30u64 * 86400);
-        assert_eq!(
-            next_bill.due_date, expected_due_date,
-            "Next due date should be exactly 30 days later"
-        );
-        assert_eq!(
-            next_bill.frequency_days, 30,
-            "Frequency should be preserved"
-        );
+        let res = client.try_pay_bill_partial(&owner, &bill_id, &1);
+        assert_eq!(res, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     #[test]
-    fn test_recurring_date_math_frequency_365_days() {
-        // Test: frequency_days = 365 → next due date is +365 days (31,536,000 seconds)
+    fn test_pay_bill_after_partial_transfers_remaining_balance() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Annual Bill"),
-            &1200,
-            &base_due_date,
-            &true, // recurring
-            &365,  // frequency_days = 365
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+        let bill_id = create_test_bill(&env, &client, &owner, 800, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.pay_bill_partial(&owner, &bill_id, &250);
+        assert_eq!(token_client.balance(&payee), 250);
 
-        // Pay the bill
         client.pay_bill(&owner, &bill_id);
-
-        // Verify next bill's due_date = base_due_date + (365 * 86400)
-        let next_bill = client.get_bill(&2).unwrap();
-        assert!(!next_bill.paid, "Next bill should be unpaid");
-        let expected_due_date = base_due_date + (365u64 * 86400);
-        assert_eq!(
-            next_bill.due_date, expected_due_date,
-            "Next due date should be exactly 365 days later"
-        );
-        assert_eq!(
-            next_bill.frequency_days, 365,
-            "Frequency should be preserved"
-        );
+        assert_eq!(token_client.balance(&payee), 800);
+        assert_eq!(token_client.balance(&owner), 200);
+        assert_eq!(client.get_bill_payments(&bill_id).len(), 2);
     }
 
     #[test]
-    fn test_recurring_date_math_paid_at_does_not_affect_next_due() {
-        let env = Env::default();
-
-        // FORCE reset to a very small number first
-        env.ledger().set_timestamp(100);
-
-        let contract_id = env.register_contract(None, BillPayments);
-        let client = BillPaymentsClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
+    fn test_pay_bill_partial_completes_recurring_bill() {
+        let env = make_env();
         env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
 
-        // Now current_time (100) is definitely < base_due_date (1,000,000)
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Late Payment Test"),
-            &300,
-            &base_due_date,
-            &true,
-            &30,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+        let bill_id = create_test_bill(&env, &client, &owner, 300, true);
+        client.pay_bill_partial(&owner, &bill_id, &100);
+        assert_eq!(client.get_total_unpaid(&owner), 200);
+        client.pay_bill_partial(&owner, &bill_id, &200);
+
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.amount_paid, 0);
+        assert!(!next.paid);
+        assert_eq!(client.get_total_unpaid(&owner), 300);
+        assert_eq!(client.get_bill_payments(&next.id).len(), 0);
+    }
 
-        // Warp to late payment time
-        env.ledger().set_timestamp(1_000_500);
-        client.pay_bill(&owner, &bill_id);
+    // -----------------------------------------------------------------------
+    // Pay on behalf tests
+    // -----------------------------------------------------------------------
 
-        let next_bill = client.get_bill(&2).unwrap();
-        let expected_due_date = base_due_date + (30u64 * 86400);
-        assert_eq!(next_bill.due_date, expected_due_date);
+    /// Give `bill_id` a new payee and a new token, minting 1_000 of it to
+    /// each of `payers`. Returns the payee and a client for the token.
+    fn fund_bill_payers<'a>(
+        env: &'a Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        bill_id: u32,
+        payers: &[&Address],
+    ) -> (Address, soroban_sdk::token::TokenClient<'a>) {
+        let payee = Address::generate(env);
+        let token = setup_token(env, payers[0], 1_000);
+        let minter = soroban_sdk::token::StellarAssetClient::new(env, &token);
+        for payer in &payers[1..] {
+            minter.mint(payer, &1_000);
+        }
+        client.set_bill_payee(owner, &bill_id, &payee, &token);
+        (payee, soroban_sdk::token::TokenClient::new(env, &token))
     }
 
     #[test]
-    fn test_recurring_date_math_multiple_pay_cycles_2nd_bill() {
-        // Test: Multiple pay cycles - verify 2nd bill's due date advances correctly
-        // Bill 1: due_date=1000000, frequency=30
-        // Bill 2: due_date=1000000 + (30*86400)
+    fn test_owner_only_bill_rejects_third_party() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Multi-Cycle Bill"),
-            &250,
-            &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        // Pay first bill
-        client.pay_bill(&owner, &bill_id);
-
-        // Verify second bill
-        let bill2 = client.get_bill(&2).unwrap();
-        let expected_bill2_due = base_due_date + (30u64 * 86400);
-        assert_eq!(bill2.due_date, expected_bill2_due);
-        assert!(!bill2.paid);
-
-        // Pay second bill
-        client.pay_bill(&owner, &2);
-
-        // Verify second bill is now paid
-        let bill2_paid = client.get_bill(&2).unwrap();
-        assert!(bill2_paid.paid);
-
-        // Verify third bill was created with correct due_date
-        let bill3 = client.get_bill(&3).unwrap();
-        let expected_bill3_due = expected_bill2_due + (30u64 * 86400);
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
         assert_eq!(
-            bill3.due_date, expected_bill3_due,
-            "Bill 3 due_date should be Bill 2 due_date + (30*86400)"
+            client.get_bill(&bill_id).unwrap().payable_by,
+            PayerPolicy::Owner
         );
-        assert!(!bill3.paid);
+        let res = client.try_pay_bill(&sibling, &bill_id);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        let res = client.try_pay_bill_partial(&sibling, &bill_id, &10);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        let res = client.try_set_bill_payers(&sibling, &bill_id, &PayerPolicy::Anyone);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
     }
 
     #[test]
-    fn test_recurring_date_math_multiple_pay_cycles_3rd_bill() {
-        // Test: Multiple pay cycles - verify 3rd bill's due date advances correctly
-        // Bill 1: due_date=1000000, frequency=30
-        // Bill 2: due_date=1000000 + (30*86400)
-        // Bill 3: due_date=1000000 + (60*86400)
+    fn test_only_owner_settles_bill_without_payee() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        let res = client.try_pay_bill(&stranger, &bill_id);
+        assert_eq!(res, Err(Ok(Error::PayeeNotSet)));
+        let res = client.try_pay_bill_partial(&stranger, &bill_id, &10);
+        assert_eq!(res, Err(Ok(Error::PayeeNotSet)));
+        let ids = Vec::from_array(&env, [bill_id]);
+        assert_eq!(client.batch_pay_bills(&stranger, &ids), 0);
+        assert!(!client.get_bill(&bill_id).unwrap().paid);
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Three-Cycle Bill"),
-            &150,
-            &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        // Pay first bill
         client.pay_bill(&owner, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+    }
 
-        // Pay second bill
-        client.pay_bill(&owner, &2);
-
-        // Pay third bill
-        client.pay_bill(&owner, &3);
-
-        // Verify third bill is now paid
-        let bill3_paid = client.get_bill(&3).unwrap();
-        assert!(bill3_paid.paid);
+    #[test]
+    fn test_anyone_can_pay_from_own_wallet() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &sibling, 1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 400, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        client.pay_bill(&sibling, &bill_id);
+
+        // The paid event names the payer as well as the owner.
+        let event = env.events().all().last().unwrap();
+        let action: Symbol = soroban_sdk::FromVal::from_val(&env, &event.1.get(3).unwrap());
+        assert_eq!(action, symbol_short!("paid"));
+        let data: (u32, Address, i128, Address) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data, (bill_id, owner.clone(), 400, sibling.clone()));
+
+        assert_eq!(token_client.balance(&sibling), 600);
+        assert_eq!(token_client.balance(&payee), 400);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.owner, owner);
+        assert_eq!(bill.paid_by, Some(sibling.clone()));
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(client.get_total_unpaid(&sibling), 0);
+    }
 
-        // Verify fourth bill was created with correct due_date
-        let bill4 = client.get_bill(&4).unwrap();
-        let expected_bill4_due = base_due_date + (90u64 * 86400); // 3 * 30 days
+    #[test]
+    fn test_allowlist_payers() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let parent = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
+        let res =
+            client.try_set_bill_payers(&owner, &bill_id, &PayerPolicy::Allowlist(Vec::new(&env)));
+        assert_eq!(res, Err(Ok(Error::InvalidPayerPolicy)));
+        let mut too_many = Vec::new(&env);
+        for _ in 0..=MAX_ALLOWED_PAYERS {
+            too_many.push_back(Address::generate(&env));
+        }
+        let res = client.try_set_bill_payers(&owner, &bill_id, &PayerPolicy::Allowlist(too_many));
+        assert_eq!(res, Err(Ok(Error::InvalidPayerPolicy)));
+
+        let allowlist = PayerPolicy::Allowlist(Vec::from_array(&env, [parent.clone()]));
+        client.set_bill_payers(&owner, &bill_id, &allowlist);
+        let (payee, token) =
+            fund_bill_payers(&env, &client, &owner, bill_id, &[&parent, &stranger]);
+        let res = client.try_pay_bill(&stranger, &bill_id);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.pay_bill(&parent, &bill_id);
         assert_eq!(
-            bill4.due_date, expected_bill4_due,
-            "Bill 4 due_date should be base + (90*86400)"
+            client.get_bill(&bill_id).unwrap().paid_by,
+            Some(parent.clone())
         );
-        assert!(!bill4.paid);
+        assert_eq!(token.balance(&parent), 900);
+        assert_eq!(token.balance(&payee), 100);
+
+        let res = client.try_set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        assert_eq!(res, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     #[test]
-    fn test_recurring_date_math_early_payment_does_not_affect_schedule() {
-        // Test: Paying a bill EARLY should not affect the next bill's due_date
-        // Bill 1: due_date=1000000, paid at time=500000 (paid 500000 seconds early)
-        // Bill 2: due_date should still be 1000000 + (30*86400)
+    fn test_relatives_split_bill_with_partial_payments() {
         let env = make_env();
-        env.ledger().set_timestamp(500_000); // Set time BEFORE due date
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let sister = Address::generate(&env);
+        let brother = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 900, true);
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        let (payee, token) = fund_bill_payers(&env, &client, &owner, bill_id, &[&sister, &brother]);
+        client.pay_bill_partial(&sister, &bill_id, &500);
+        assert_eq!(client.get_total_unpaid(&owner), 400);
+        client.pay_bill_partial(&brother, &bill_id, &400);
+        assert_eq!(token.balance(&sister), 500);
+        assert_eq!(token.balance(&brother), 600);
+        assert_eq!(token.balance(&payee), 900);
+
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.paid_by, Some(brother.clone()));
+        let payments = client.get_bill_payments(&bill_id);
+        assert_eq!(payments.get(0).unwrap().payer, sister);
+        assert_eq!(payments.get(1).unwrap().payer, brother);
+
+        // The policy carries over to the next occurrence.
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.payable_by, PayerPolicy::Anyone);
+        assert_eq!(next.paid_by, None);
+        assert_eq!(client.get_total_unpaid(&owner), 900);
+    }
 
-        let base_due_date = 1_000_000u64;
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Early Payment Test"),
-            &200,
-            &base_due_date,
-            &true, // recurring
-            &30,   // frequency_days = 30
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+    #[test]
+    fn test_get_bills_paid_by() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other_owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+
+        let first = create_test_bill(&env, &client, &owner, 100, false);
+        let second = create_test_bill(&env, &client, &other_owner, 200, false);
+        let untouched = create_test_bill(&env, &client, &owner, 300, false);
+        let payees = [first, second, untouched].map(|id| {
+            let bill_owner = client.get_bill(&id).unwrap().owner;
+            client.set_bill_payers(&bill_owner, &id, &PayerPolicy::Anyone);
+            fund_bill_payers(&env, &client, &bill_owner, id, &[&sibling, &owner])
+        });
+        client.pay_bill(&sibling, &first);
+        client.pay_bill_partial(&sibling, &second, &50);
+        client.pay_bill(&owner, &untouched);
+        let (payee, token) = &payees[1];
+        assert_eq!(token.balance(&sibling), 950);
+        assert_eq!(token.balance(payee), 50);
+
+        let page = client.get_bills_paid_by(&sibling, &0, &1);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.items.get(0).unwrap().id, first);
+        let page = client.get_bills_paid_by(&sibling, &page.next_cursor, &1);
+        assert_eq!(page.items.get(0).unwrap().id, second);
+        assert_eq!(page.next_cursor, 0);
 
-        // Pay the bill early (at time 500_000)
-        client.pay_bill(&owner, &bill_id);
+        let page = client.get_bills_paid_by(&owner, &0, &10);
+        assert_eq!(page.count, 1);
+        assert_eq!(page.items.get(0).unwrap().id, untouched);
+    }
 
-        // Verify original bill has paid_at set to early time
-        let paid_bill = client.get_bill(&bill_id).unwrap();
-        assert!(paid_bill.paid);
-        assert_eq!(paid_bill.paid_at, Some(500_000));
+    #[test]
+    fn test_batch_pay_bills_on_behalf() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let other_owner = Address::generate(&env);
+        let parent = Address::generate(&env);
+
+        let allowed = create_test_bill(&env, &client, &owner, 100, false);
+        let other = create_test_bill(&env, &client, &other_owner, 200, false);
+        let allowlist = PayerPolicy::Allowlist(Vec::from_array(&env, [parent.clone()]));
+        client.set_bill_payers(&owner, &allowed, &allowlist);
+        let (payee, token) = fund_bill_payers(&env, &client, &owner, allowed, &[&parent]);
+
+        let paid = client.batch_pay_bills(&parent, &Vec::from_array(&env, [allowed, other]));
+        assert_eq!(paid, 1);
+        assert!(client.get_bill(&allowed).unwrap().paid);
+        assert!(!client.get_bill(&other).unwrap().paid);
+        assert_eq!(token.balance(&parent), 900);
+        assert_eq!(token.balance(&payee), 100);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert_eq!(client.get_total_unpaid(&other_owner), 200);
+    }
 
-        // Verify next bill's due_date is still based on original due_date
-        let next_bill = client.get_bill(&2).unwrap();
-        let expected_due_date = base_due_date + (30u64 * 86400);
+    // -----------------------------------------------------------------------
+    // Variable amount tests
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_variable_bill_next_occurrence_is_estimated() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 100, true);
         assert_eq!(
-            next_bill.due_date, expected_due_date,
-            "Next due date should not be affected by early payment"
+            client.get_bill(&bill_id).unwrap().amount_kind,
+            AmountKind::Fixed
+        );
+        client.set_variable_amount(&owner, &bill_id, &true);
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().amount_kind,
+            AmountKind::Actual
         );
+        client.pay_bill(&owner, &bill_id);
+
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.amount, 100);
+        assert_eq!(next.amount_kind, AmountKind::Estimated);
+        let breakdown = client.get_unpaid_breakdown(&owner);
+        assert_eq!(breakdown.actual, 0);
+        assert_eq!(breakdown.estimated, 100);
+
+        client.update_bill_amount(&owner, &next.id, &140);
+        let next = client.get_bill(&next.id).unwrap();
+        assert_eq!(next.amount, 140);
+        assert_eq!(next.amount_kind, AmountKind::Actual);
+        assert_eq!(client.get_total_unpaid(&owner), 140);
+        let breakdown = client.get_unpaid_breakdown(&owner);
+        assert_eq!(breakdown.actual, 140);
+        assert_eq!(breakdown.estimated, 0);
     }
 
     #[test]
-    fn test_recurring_date_math_preserves_frequency_across_cycles() {
-        // Test: frequency_days is preserved across all recurring cycles
-        // Verify that Bill 1, 2, 3 all have the same frequency_days value
+    fn test_fixed_bill_stays_fixed() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        let frequency = 7u32; // Weekly
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Weekly Bill"),
-            &50,
-            &1_000_000,
-            &true,
-            &frequency,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        // Pay first bill
+        let bill_id = create_test_bill(&env, &client, &owner, 100, true);
+        client.set_variable_amount(&owner, &bill_id, &true);
+        client.set_variable_amount(&owner, &bill_id, &false);
+        client.update_bill_amount(&owner, &bill_id, &120);
         client.pay_bill(&owner, &bill_id);
 
-        // Pay second bill
-        client.pay_bill(&owner, &2);
-
-        // Verify all bills have the same frequency_days
-        let bill1 = client.get_bill(&1).unwrap();
-        let bill2 = client.get_bill(&2).unwrap();
-        let bill3 = client.get_bill(&3).unwrap();
-
-        assert_eq!(bill1.frequency_days, frequency);
-        assert_eq!(bill2.frequency_days, frequency);
-        assert_eq!(bill3.frequency_days, frequency);
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert_eq!(next.amount, 120);
+        assert_eq!(next.amount_kind, AmountKind::Fixed);
+        assert_eq!(client.get_unpaid_breakdown(&owner).actual, 120);
     }
 
     #[test]
-    fn test_recurring_date_math_amount_preserved_across_cycles() {
-        // Test: Bill amount is preserved across all recurring cycles
+    fn test_biller_can_update_bill_amount() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
 
-        let amount = 999i128;
-        let bill_id = client.create_bill(
+        let bill_id = client.create_bill_for_biller(
             &owner,
-            &String::from_str(&env, "Fixed Amount Bill"),
-            &amount,
-            &1_000_000,
+            &biller_id,
+            &400,
+            &(env.ledger().timestamp() + 86400),
             &true,
             &30,
-            &None,
-            &String::from_str(&env, "XLM"),
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
         );
+        let res = client.try_update_bill_amount(&stranger, &bill_id, &450);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        // The biller may only set the amount of a variable bill.
+        let res = client.try_update_bill_amount(&payee, &bill_id, &450);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.get_bill(&bill_id).unwrap().amount, 400);
+        client.set_variable_amount(&owner, &bill_id, &true);
+        client.update_bill_amount(&payee, &bill_id, &450);
+        assert_eq!(client.get_bill(&bill_id).unwrap().amount, 450);
+        assert_eq!(client.get_total_unpaid(&owner), 450);
+
+        client.set_biller_status(&admin, &biller_id, &BillerStatus::Suspended);
+        let res = client.try_update_bill_amount(&payee, &bill_id, &500);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+    }
 
-        // Pay first bill
-        client.pay_bill(&owner, &bill_id);
+    #[test]
+    fn test_update_bill_amount_limits() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
 
-        // Pay second bill
-        client.pay_bill(&owner, &2);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        client.pay_bill_partial(&owner, &bill_id, &100);
+        let res = client.try_update_bill_amount(&owner, &bill_id, &100);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        client.update_bill_amount(&owner, &bill_id, &250);
+        assert_eq!(client.get_total_unpaid(&owner), 150);
 
-        // Verify all bills have the same amount
-        let bill1 = client.get_bill(&1).unwrap();
-        let bill2 = client.get_bill(&2).unwrap();
-        let bill3 = client.get_bill(&3).unwrap();
+        let due_date = client.get_bill(&bill_id).unwrap().due_date;
+        env.ledger().set_timestamp(due_date + 1);
+        let res = client.try_update_bill_amount(&owner, &bill_id, &260);
+        assert_eq!(res, Err(Ok(Error::DueDatePassed)));
 
-        assert_eq!(bill1.amount, amount);
-        assert_eq!(bill2.amount, amount);
-        assert_eq!(bill3.amount, amount);
+        client.pay_bill(&owner, &bill_id);
+        let res = client.try_update_bill_amount(&owner, &bill_id, &260);
+        assert_eq!(res, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     #[test]
-    fn test_recurring_date_math_owner_preserved_across_cycles() {
-        // Test: Bill owner is preserved across all recurring cycles
+    fn test_auto_pay_waits_for_approval_of_biller_increase() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 10_000);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
 
-        let bill_id = client.create_bill(
+        let bill_id = client.create_bill_for_biller(
             &owner,
-            &String::from_str(&env, "Owner Test"),
-            &100,
-            &1_000_000,
-            &true,
-            &30,
-            &None,
-            &String::from_str(&env, "XLM"),
+            &biller_id,
+            &400,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
         );
-
-        // Pay first bill
-        client.pay_bill(&owner, &bill_id);
-
-        // Pay second bill
-        client.pay_bill(&owner, &2);
-
-        // Verify all bills have the same owner
-        let bill1 = client.get_bill(&1).unwrap();
-        let bill2 = client.get_bill(&2).unwrap();
-        let bill3 = client.get_bill(&3).unwrap();
-
-        assert_eq!(bill1.owner, owner);
-        assert_eq!(bill2.owner, owner);
-        assert_eq!(bill3.owner, owner);
+        client.set_bill_auto_pay(&owner, &bill_id, &true);
+        client.set_variable_amount(&owner, &bill_id, &true);
+        client.update_bill_amount(&payee, &bill_id, &9_000);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.amount, 9_000);
+        assert_eq!(bill.approved_amount, 400);
+
+        // Neither a pool top-up nor the keeper pays the raised amount.
+        client.set_auto_pay(&owner, &true);
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &9_000), 0);
+        env.ledger().set_timestamp(env.ledger().timestamp() + 86400);
+        assert_eq!(client.top_up_pool(&owner, &owner, &token, &1), 0);
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.failed, Vec::from_array(&env, [bill_id]));
+        let event = env.events().all().last().unwrap();
+        let data: (u32, Address, u32, u32) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data.2, Error::AmountNotApproved as u32);
+
+        let res = client.try_approve_bill_amount(&payee, &bill_id);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.approve_bill_amount(&owner, &bill_id);
+        let result = client.execute_due_bills(&0, &0);
+        assert_eq!(result.paid, Vec::from_array(&env, [bill_id]));
     }
 
     #[test]
-    fn test_recurring_date_math_exact_calculation_verification() {
-        // Test: Verify exact date math calculation with known values
-        // due_date = 1_000_000
-        // frequency_days = 14
-        // Expected: 1_000_000 + (14 * 86400) = 1_000_000 + 1_209_600 = 2_209_600
+    fn test_third_party_cannot_pay_unapproved_amount() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
-
-        let base_due = 1_000_000u64;
-        let freq = 14u32;
-        let bill_id = client.create_bill(
+        let sister = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &sister, 10_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        client.set_upgrade_admin(&admin, &admin);
+        let biller_id = setup_biller(&env, &client, &admin, "NEPA", &payee, &token);
+
+        let bill_id = client.create_bill_for_biller(
             &owner,
-            &String::from_str(&env, "Math Verification"),
-            &100,
-            &base_due,
-            &true,
-            &freq,
-            &None,
-            &String::from_str(&env, "XLM"),
+            &biller_id,
+            &400,
+            &(env.ledger().timestamp() + 86400),
+            &false,
+            &0,
+            &String::from_str(&env, "MTR12345"),
+            &String::from_str(&env, "USDC"),
         );
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        client.set_variable_amount(&owner, &bill_id, &true);
+        client.update_bill_amount(&payee, &bill_id, &9_000);
+
+        let res = client.try_pay_bill(&sister, &bill_id);
+        assert_eq!(res, Err(Ok(Error::AmountNotApproved)));
+        let res = client.try_pay_bill_partial(&sister, &bill_id, &400);
+        assert_eq!(res, Err(Ok(Error::AmountNotApproved)));
+        let ids = Vec::from_array(&env, [bill_id]);
+        assert_eq!(client.batch_pay_bills(&sister, &ids), 0);
+        assert_eq!(token_client.balance(&sister), 10_000);
+
+        client.approve_bill_amount(&owner, &bill_id);
+        client.pay_bill(&sister, &bill_id);
+        assert_eq!(token_client.balance(&payee), 9_000);
+    }
 
-        client.pay_bill(&owner, &bill_id);
+    #[test]
+    fn test_owner_amount_update_is_approved() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
 
-        let next_bill = client.get_bill(&2).unwrap();
-        let expected = 1_000_000u64 + (14u64 * 86400);
-        assert_eq!(next_bill.due_date, expected);
-        assert_eq!(next_bill.due_date, 2_209_600);
+        let bill_id = create_test_bill(&env, &client, &owner, 300, false);
+        assert_eq!(client.get_bill(&bill_id).unwrap().approved_amount, 300);
+        client.update_bill_amount(&owner, &bill_id, &350);
+        assert_eq!(client.get_bill(&bill_id).unwrap().approved_amount, 350);
+
+        client.pay_bill(&owner, &bill_id);
+        let res = client.try_approve_bill_amount(&owner, &bill_id);
+        assert_eq!(res, Err(Ok(Error::BillAlreadyPaid)));
     }
 
     // -----------------------------------------------------------------------
-    // Property-based tests: time-dependent behavior
+    // Archive retention tests
     // -----------------------------------------------------------------------
 
-    proptest! {
-        /// All bills returned by get_overdue_bills must have due_date < now,
-        /// and every bill created with due_date < now must appear in the result.
-        #[test]
-        fn prop_overdue_bills_all_have_due_before_now(
-            now in 2_000_000u64..10_000_000u64,
-            n_overdue in 1usize..6usize,
-            n_future in 0usize..6usize,
-        ) {
-            let env = make_env();
-            let create_time = now.saturating_sub(10_000);
-            env.ledger().set_timestamp(create_time);
-            env.mock_all_auths();
-            let cid = env.register_contract(None, BillPayments);
-            let client = BillPaymentsClient::new(&env, &cid);
-            let owner = Address::generate(&env);
+    /// Create and pay a bill for `owner`. Returns its ID.
+    fn create_paid_bill(env: &Env, client: &BillPaymentsClient, owner: &Address) -> u32 {
+        let bill_id = create_test_bill(env, client, owner, 100, false);
+        client.pay_bill(owner, &bill_id);
+        bill_id
+    }
 
-            // Set initial ledger time to 0 to bypass "due_date >= now" check,
-            // then fast-forward to the target 'now' value.
-            env.ledger().set_timestamp(0);
+    #[test]
+    fn test_archive_and_cleanup_are_owner_scoped() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
 
-            // Create bills with due_date < now (overdue)
-            for i in 0..n_overdue {
-                client.create_bill(
-                    &owner,
-                    &String::from_str(&env, "Overdue"),
-                    &100,
-                    &(now - 1 - i as u64),
-                    &false,
-                    &0,
-                    &None,
-                    &String::from_str(&env, "XLM"),
-                );
-            }
+        let alice_bill = create_paid_bill(&env, &client, &alice);
+        let bob_bill = create_paid_bill(&env, &client, &bob);
+        assert_eq!(client.archive_paid_bills(&alice, &u64::MAX), 1);
+        assert!(client.get_archived_bill(&alice_bill).is_some());
+        assert!(client.get_bill(&bob_bill).is_some());
 
-            // Create bills with due_date >= now (not overdue)
-            for i in 0..n_future {
-                client.create_bill(
-                    &owner,
-                    &String::from_str(&env, "Future"),
-                    &100,
-                    &(now + 1 + i as u64),
-                    &false,
-                    &0,
-                    &None,
-                    &String::from_str(&env, "XLM"),
-                );
-            }
+        client.archive_paid_bills(&bob, &u64::MAX);
+        assert_eq!(client.bulk_cleanup_bills(&alice, &u64::MAX), 1);
+        assert!(client.get_archived_bill(&alice_bill).is_none());
+        assert!(client.get_archived_bill(&bob_bill).is_some());
+    }
 
-            // Fast-forward to 'now' so they become overdue
-            env.ledger().set_timestamp(now);
+    #[test]
+    fn test_cleanup_respects_retention_policy() {
+        let day = 86400u64;
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
 
-            let page = client.get_overdue_bills(&0, &50);
-            for bill in page.items.iter() {
-                prop_assert!(bill.due_date < now, "returned bill must be past due");
-            }
-            prop_assert_eq!(page.count as usize, n_overdue);
-        }
+        let policy = RetentionPolicy {
+            min_retention_days: 30,
+        };
+        let res = client.try_set_retention_policy(&owner, &policy);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.set_retention_policy(&admin, &policy);
+        assert_eq!(client.get_retention_policy(), policy);
+
+        let bill_id = create_paid_bill(&env, &client, &owner);
+        client.archive_paid_bills(&owner, &u64::MAX);
+        let archived_at = env.ledger().timestamp();
+
+        env.ledger().set_timestamp(archived_at + 30 * day - 1);
+        assert_eq!(client.bulk_cleanup_bills(&owner, &u64::MAX), 0);
+        assert_eq!(client.admin_cleanup_bills(&admin, &u64::MAX), 0);
+        assert!(client.get_archived_bill(&bill_id).is_some());
+
+        env.ledger().set_timestamp(archived_at + 30 * day);
+        assert_eq!(client.bulk_cleanup_bills(&owner, &u64::MAX), 1);
     }
 
-    proptest! {
-        /// Bills with due_date >= now must never appear in get_overdue_bills.
-        #[test]
-        fn prop_future_bills_not_in_overdue_set(
-            now in 1_000_000u64..5_000_000u64,
-            n in 1usize..6usize,
-        ) {
-            let env = make_env();
-            env.ledger().set_timestamp(now);
-            env.mock_all_auths();
-            let cid = env.register_contract(None, BillPayments);
-            let client = BillPaymentsClient::new(&env, &cid);
-            let owner = Address::generate(&env);
+    #[test]
+    fn test_admin_cleanup_lists_affected_owners() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+
+        create_paid_bill(&env, &client, &alice);
+        create_paid_bill(&env, &client, &alice);
+        create_paid_bill(&env, &client, &bob);
+        client.archive_paid_bills(&alice, &u64::MAX);
+        client.archive_paid_bills(&bob, &u64::MAX);
+
+        let res = client.try_admin_cleanup_bills(&alice, &u64::MAX);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.admin_cleanup_bills(&admin, &u64::MAX), 3);
+        assert_eq!(client.get_storage_stats().archived_bills, 0);
+
+        let event = env.events().all().last().unwrap();
+        let data: (u32, Vec<Address>) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data, (3, Vec::from_array(&env, [alice, bob])));
+    }
 
-            for i in 0..n {
-                client.create_bill(
-                    &owner,
-                    &String::from_str(&env, "NotOverdue"),
-                    &100,
-                    &(now + i as u64), // due_date >= now — strict less-than is required to be overdue
-                    &false,
-                    &0,
-                    &None,
-                    &String::from_str(&env, "XLM"),
-                );
-            }
+    // -----------------------------------------------------------------------
+    // Storage layout and migration tests
+    // -----------------------------------------------------------------------
 
-            let page = client.get_overdue_bills(&0, &50);
-            prop_assert_eq!(
-                page.count,
-                0u32,
-                "bills with due_date >= now must not appear as overdue"
-            );
+    /// The baseline-shaped copy of `bill`.
+    fn to_legacy_bill(bill: &Bill) -> LegacyBill {
+        LegacyBill {
+            id: bill.id,
+            owner: bill.owner.clone(),
+            name: bill.name.clone(),
+            external_ref: bill.external_ref.clone(),
+            amount: bill.amount,
+            due_date: bill.due_date,
+            recurring: bill.recurring,
+            frequency_days: bill.frequency_days,
+            paid: bill.paid,
+            created_at: bill.created_at,
+            paid_at: bill.paid_at,
+            schedule_id: bill.schedule_id,
+            tags: bill.tags.clone(),
+            currency: bill.currency.clone(),
         }
     }
 
-    proptest! {
-        /// After paying a recurring bill, the next bill's due_date equals
-        /// the original due_date + frequency_days * 86400, regardless of
-        /// when payment is made.
-        #[test]
-        fn prop_recurring_next_bill_due_date_follows_original(
-            base_due in 1_000_000u64..5_000_000u64,
-            base_due_offset in 1_000_000u64..5_000_000u64,
-            pay_offset in 1u64..100_000u64,
-            freq_days in 1u32..366u32,
-        ) {
-            let env = make_env();
-            env.mock_all_auths();
-            let cid = env.register_contract(None, BillPayments);
-            let client = BillPaymentsClient::new(&env, &cid);
-            let owner = Address::generate(&env);
+    /// Move every bill of `owner`, with its payments and unpaid total, back
+    /// into the pre-upgrade instance maps.
+    fn move_to_legacy_layout(env: &Env, cid: &Address, owner: &Address) {
+        env.as_contract(cid, || {
+            let instance = env.storage().instance();
+            let persistent = env.storage().persistent();
+            let mut bills: Map<u32, LegacyBill> = instance
+                .get(&STORAGE_BILLS)
+                .unwrap_or_else(|| Map::new(env));
+            let mut payments: Map<u32, Vec<BillPayment>> = instance
+                .get(&STORAGE_PAYMENTS)
+                .unwrap_or_else(|| Map::new(env));
+            let ids: Vec<u32> = persistent
+                .get(&DataKey::OwnerBills(owner.clone()))
+                .unwrap_or_else(|| Vec::new(env));
+            for id in ids.iter() {
+                let bill: Bill = persistent.get(&DataKey::Bill(id)).unwrap();
+                BillPayments::remove_bill(env, &bill);
+                bills.set(id, to_legacy_bill(&bill));
+                if let Some(list) = persistent.get::<_, Vec<BillPayment>>(&DataKey::Payments(id)) {
+                    persistent.remove(&DataKey::Payments(id));
+                    payments.set(id, list);
+                }
+            }
+            let mut totals: Map<Address, i128> = instance
+                .get(&STORAGE_UNPAID_TOTALS)
+                .unwrap_or_else(|| Map::new(env));
+            let total_key = DataKey::UnpaidTotal(owner.clone());
+            totals.set(owner.clone(), persistent.get(&total_key).unwrap_or(0));
+            persistent.remove(&total_key);
+
+            instance.set(&STORAGE_BILLS, &bills);
+            instance.set(&STORAGE_PAYMENTS, &payments);
+            instance.set(&STORAGE_UNPAID_TOTALS, &totals);
+        });
+    }
 
-            // Set base due date far in the future relative to 0
-            let base_due = 1_000_000 + base_due_offset;
-            env.ledger().set_timestamp(0);
+    #[test]
+    fn test_legacy_layout_readable_before_migration() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+
+        let paid = create_test_bill(&env, &client, &owner, 100, false);
+        let unpaid = create_test_bill(&env, &client, &owner, 200, false);
+        let cancelled = create_test_bill(&env, &client, &owner, 300, false);
+        client.pay_bill(&owner, &paid);
+        move_to_legacy_layout(&env, &cid, &owner);
+
+        assert_eq!(client.get_bill(&unpaid).unwrap().amount, 200);
+        assert_eq!(client.get_bill_payments(&paid).len(), 1);
+        assert_eq!(client.get_total_unpaid(&owner), 500);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 2);
+        assert_eq!(client.get_all_bills_for_owner(&owner, &0, &10).count, 3);
+        assert_eq!(client.get_bills_paid_by(&owner, &0, &10).count, 1);
+
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2 * 86400);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 2);
+
+        client.pay_bill(&owner, &unpaid);
+        client.cancel_bill(&owner, &cancelled);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 0);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
+        assert!(client.get_bill(&cancelled).is_none());
+    }
+
+    #[test]
+    fn test_migrate_storage_moves_legacy_entries_in_batches() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+
+        let paid = create_test_bill(&env, &client, &owner, 100, false);
+        create_test_bill(&env, &client, &owner, 200, false);
+        create_test_bill(&env, &client, &owner, 300, false);
+        client.pay_bill(&owner, &paid);
+        move_to_legacy_layout(&env, &cid, &owner);
+
+        // 3 bills, 1 payment history and 1 unpaid total.
+        assert_eq!(client.migrate_storage(&admin, &2), 3);
+        let event = env.events().all().last().unwrap();
+        let data: (u32, u32) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data, (2, 3));
+        assert_eq!(client.migrate_storage(&admin, &2), 1);
+        assert_eq!(client.migrate_storage(&admin, &2), 0);
+
+        env.as_contract(&cid, || {
+            let instance = env.storage().instance();
+            assert!(!instance.has(&STORAGE_BILLS));
+            assert!(!instance.has(&STORAGE_PAYMENTS));
+            assert!(!instance.has(&STORAGE_UNPAID_TOTALS));
+            assert!(env.storage().persistent().has(&DataKey::Bill(paid)));
+        });
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 2);
+        assert_eq!(client.get_all_bills_for_owner(&owner, &0, &10).count, 3);
+        assert_eq!(client.get_bills_paid_by(&owner, &0, &10).count, 1);
+        assert_eq!(client.get_total_unpaid(&owner), 500);
+        assert_eq!(client.migrate_storage(&admin, &2), 0);
+    }
 
-            let bill_id = client.create_bill(
-                &owner,
-                &String::from_str(&env, "Recurring"),
-                &100,
-                &base_due,
-                &true,
-                &freq_days,
-                &None,
-                &String::from_str(&env, "XLM"),
-            );
+    #[test]
+    fn test_migrate_storage_keeps_entries_written_since_upgrade() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
 
-            // Fast-forward to the payment time
-            let now = base_due + pay_offset;
-            env.ledger().set_timestamp(now);
+        let bill_id = create_test_bill(&env, &client, &owner, 100, false);
+        move_to_legacy_layout(&env, &cid, &owner);
 
-            client.pay_bill(&owner, &bill_id);
+        client.pay_bill_partial(&owner, &bill_id, &50);
+        assert_eq!(client.migrate_storage(&admin, &0), 0);
 
-            let next_bill = client.get_bill(&2).unwrap();
-            let expected_due = base_due + (freq_days as u64 * 86400);
-            prop_assert_eq!(
-                next_bill.due_date,
-                expected_due,
-                "next recurring bill due_date must equal original due_date + freq_days * 86400"
-            );
-            prop_assert!(!next_bill.paid, "next recurring bill must be unpaid");
-        }
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert_eq!(bill.amount_paid, 50);
+        assert_eq!(client.get_bill_payments(&bill_id).len(), 1);
+        assert_eq!(client.get_total_unpaid(&owner), 50);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
     }
 
-    /// Issue #102 – When pay_bill is called on a recurring bill, the contract
-    /// creates the next occurrence.  This test asserts every cloned field
-    /// individually so that a regression in the clone logic (e.g. paid left
-    /// true, wrong due_date, wrong owner) is caught immediately.
     #[test]
-    fn test_create_bill_invalid_due_date() {
-        // 1. Setup
+    fn test_migrate_storage_upgrades_baseline_bills() {
         let env = make_env();
         env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let now = env.ledger().timestamp();
 
-        // Explicitly set the ledger time
-        let current_ledger_time = 1_700_000_000;
-        env.ledger().with_mut(|info| {
-            info.timestamp = current_ledger_time;
+        // Maps as a deployment before per-bill storage left them.
+        let legacy = |id: u32, amount: i128, recurring: bool, paid: bool| LegacyBill {
+            id,
+            owner: owner.clone(),
+            name: String::from_str(&env, "Legacy"),
+            external_ref: None,
+            amount,
+            due_date: now + 86400,
+            recurring,
+            frequency_days: if recurring { 30 } else { 0 },
+            paid,
+            created_at: now,
+            paid_at: if paid { Some(now) } else { None },
+            schedule_id: None,
+            tags: Vec::new(&env),
+            currency: String::from_str(&env, "XLM"),
+        };
+        env.as_contract(&cid, || {
+            let instance = env.storage().instance();
+            let mut bills: Map<u32, LegacyBill> = Map::new(&env);
+            bills.set(1, legacy(1, 100, true, false));
+            bills.set(2, legacy(2, 200, false, true));
+            let mut archived: Map<u32, LegacyArchivedBill> = Map::new(&env);
+            archived.set(
+                3,
+                LegacyArchivedBill {
+                    id: 3,
+                    owner: owner.clone(),
+                    name: String::from_str(&env, "Old"),
+                    external_ref: None,
+                    amount: 300,
+                    paid_at: now,
+                    archived_at: now,
+                    tags: Vec::new(&env),
+                    currency: String::from_str(&env, "XLM"),
+                },
+            );
+            let mut totals: Map<Address, i128> = Map::new(&env);
+            totals.set(owner.clone(), 100);
+            instance.set(&STORAGE_BILLS, &bills);
+            instance.set(&STORAGE_ARCHIVED, &archived);
+            instance.set(&STORAGE_UNPAID_TOTALS, &totals);
+            instance.set(&symbol_short!("NEXT_ID"), &3u32);
+        });
+
+        // Readable before migration.
+        let unpaid = client.get_bill(&1).unwrap();
+        assert_eq!(unpaid.recurrence, RecurrenceRule::from_frequency(true, 30));
+        assert_eq!(unpaid.amount_paid, 0);
+        assert_eq!(client.get_archived_bill(&3).unwrap().amount, 300);
+
+        assert_eq!(client.migrate_storage(&admin, &0), 0);
+        env.as_contract(&cid, || {
+            let instance = env.storage().instance();
+            assert!(!instance.has(&STORAGE_BILLS));
+            assert!(!instance.has(&STORAGE_ARCHIVED));
         });
 
+        let paid = client.get_bill(&2).unwrap();
+        assert!(paid.paid);
+        assert_eq!(paid.amount_paid, 200);
+        assert_eq!(paid.paid_by, Some(owner.clone()));
+        assert_eq!(paid.payable_by, PayerPolicy::Owner);
+        let archived = client.get_archived_bill(&3).unwrap();
+        assert_eq!(archived.paid_by, Some(owner.clone()));
+        assert_eq!(client.get_archived_bills(&owner, &0, &10).count, 1);
+        assert_eq!(client.get_unpaid_bills(&owner, &0, &10).count, 1);
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+
+        // Migrated bills keep working and new IDs follow the legacy ones.
+        client.pay_bill(&owner, &1);
+        assert_eq!(client.get_bill(&4).unwrap().occurrence, 2);
+        assert_eq!(client.get_total_unpaid(&owner), 100);
+    }
+
+    #[test]
+    fn test_migrate_storage_requires_admin() {
+        let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
         let owner = Address::generate(&env);
 
-        // 2. Scenario Data
-        let past_due_date = 946684800; // Year 1999
-        let zero_due_date = 0u64;
-        let name = String::from_str(&env, "Electricity");
-        let currency = String::from_str(&env, ""); // New required parameter
+        let res = client.try_migrate_storage(&owner, &10);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.set_upgrade_admin(&admin, &admin);
+        let res = client.try_migrate_storage(&owner, &10);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        assert_eq!(client.migrate_storage(&admin, &10), 0);
+    }
 
-        // 3. Execution: Attempt to create bills with invalid dates
-        // Added '&currency' as the final argument to both calls
-        let result_past =
-            client.try_create_bill(&owner, &name, &1000, &past_due_date, &false, &0, &None, &currency);
+    #[test]
+    fn test_indexes_follow_bill_lifecycle() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
 
-        let result_zero =
-            client.try_create_bill(&owner, &name, &1000, &zero_due_date, &false, &0, &None, &currency);
+        let recurring = create_test_bill(&env, &client, &owner, 100, true);
+        let one_off = create_test_bill(&env, &client, &owner, 200, false);
+        let cancelled = create_test_bill(&env, &client, &owner, 300, false);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + 2 * 86400);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 3);
+
+        client.pay_bill(&owner, &recurring);
+        client.pay_bill(&owner, &one_off);
+        client.cancel_bill(&owner, &cancelled);
+        // Only the next occurrence of the recurring bill is left unpaid.
+        let unpaid = client.get_unpaid_bills(&owner, &0, &10);
+        assert_eq!(unpaid.count, 1);
+        assert_eq!(unpaid.items.get(0).unwrap().occurrence, 2);
+        assert_eq!(client.get_overdue_bills(&0, &10).count, 0);
+        assert_eq!(client.get_storage_stats().active_bills, 3);
 
-        // 4. Assertions
-        assert!(
-            result_past.is_err(),
-            "Creation should have failed for a past date"
-        );
-        assert!(
-            result_zero.is_err(),
-            "Creation should have failed for a zero date"
-        );
+        client.archive_paid_bills(&owner, &u64::MAX);
+        assert_eq!(client.get_all_bills_for_owner(&owner, &0, &10).count, 1);
+        assert_eq!(client.get_archived_bills(&owner, &0, &10).count, 2);
+        client.restore_bill(&owner, &one_off);
+        assert_eq!(client.get_all_bills_for_owner(&owner, &0, &10).count, 2);
+        assert_eq!(client.get_bills_paid_by(&owner, &0, &10).count, 1);
+
+        env.as_contract(&cid, || {
+            let persistent = env.storage().persistent();
+            assert!(!persistent.has(&DataKey::Bill(cancelled)));
+            // Only the next occurrence's bucket remains.
+            let buckets: Vec<u64> = persistent.get(&DataKey::DueBuckets).unwrap();
+            assert_eq!(buckets.len(), 1);
+        });
+    }
 
-        // Check that the error code matches InvalidDueDate
-        match result_past {
-            Err(Ok(err)) => assert_eq!(err, Error::InvalidDueDate),
-            _ => panic!("Expected contract error InvalidDueDate for past date"),
-        }
+    // -----------------------------------------------------------------------
+    // Dispute tests
+    // -----------------------------------------------------------------------
 
-        match result_zero {
-            Err(Ok(err)) => assert_eq!(err, Error::InvalidDueDate),
-            _ => panic!("Expected contract error InvalidDueDate for zero date"),
-        }
+    /// Create a bill for `owner` with a payee and pay it from `owner`'s
+    /// wallet. Returns the bill ID, payee and token.
+    fn setup_disputed_payment(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+    ) -> (u32, Address, Address) {
+        let payee = Address::generate(env);
+        let token = setup_token(env, owner, 1_000);
+        let bill_id = create_test_bill(env, client, owner, 300, false);
+        client.set_bill_payee(owner, &bill_id, &payee, &token);
+        client.pay_bill(owner, &bill_id);
+        (bill_id, payee, token)
     }
 
-    // ══════════════════════════════════════════════════════════════════════
-    // Time & Ledger Drift Resilience Tests (#158)
-    //
-    // Assumptions:
-    //  - A bill is overdue when due_date < current_time (strict less-than).
-    //  - At exactly due_date the bill is NOT yet overdue.
-    //  - Stellar ledger timestamps are monotonically increasing in production.
-    // ══════════════════════════════════════════════════════════════════════
-
-    /// Bill is NOT overdue when ledger timestamp == due_date (inclusive boundary).
     #[test]
-    fn test_time_drift_bill_not_overdue_at_exact_due_date() {
-        let due_date = 1_000_000u64;
+    fn test_accepted_dispute_refunds_owner() {
         let env = make_env();
         env.mock_all_auths();
-        env.ledger().set_timestamp(due_date);
-
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let (bill_id, payee, token) = setup_disputed_payment(&env, &client, &owner);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        let reason = String::from_str(&env, "Charged twice");
 
-        client.create_bill(
-            &owner,
-            &String::from_str(&env, "Power"),
-            &200,
-            &due_date,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().dispute,
+            DisputeStatus::None
         );
-
-        let page = client.get_overdue_bills(&0, &100);
+        client.open_dispute(&owner, &bill_id, &120, &reason);
         assert_eq!(
-            page.count, 0,
-            "Bill must not appear overdue when current_time == due_date"
+            client.get_bill(&bill_id).unwrap().dispute,
+            DisputeStatus::Open
+        );
+        let res = client.try_open_dispute(&owner, &bill_id, &120, &reason);
+        assert_eq!(res, Err(Ok(Error::DisputeExists)));
+        let res = client.try_accept_dispute(&owner, &bill_id);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        client.accept_dispute(&payee, &bill_id);
+        assert_eq!(token_client.balance(&owner), 820);
+        assert_eq!(token_client.balance(&payee), 180);
+        let dispute = client.get_dispute(&bill_id).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Refunded);
+        assert_eq!(dispute.opened_by, owner);
+        assert_eq!(dispute.reason, reason);
+        assert_eq!(dispute.resolved_at, Some(env.ledger().timestamp()));
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().dispute,
+            DisputeStatus::Refunded
         );
+        let event = env.events().all().last().unwrap();
+        let data: (u32, Address, Address, i128) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data, (bill_id, payee.clone(), owner, 120));
+
+        let res = client.try_accept_dispute(&payee, &bill_id);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
     }
 
-    /// Bill becomes overdue exactly one second after due_date.
     #[test]
-    fn test_time_drift_bill_overdue_one_second_after_due_date() {
-        let due_date = 1_000_000u64;
+    fn test_open_dispute_checks_bill_and_window() {
+        let day = 86400u64;
         let env = make_env();
         env.mock_all_auths();
-        env.ledger().set_timestamp(due_date);
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let reason = String::from_str(&env, "Wrong biller");
+
+        let unpaid = create_test_bill(&env, &client, &owner, 100, false);
+        let res = client.try_open_dispute(&owner, &unpaid, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::NotDisputable)));
+        let off_chain = create_paid_bill(&env, &client, &owner);
+        let res = client.try_open_dispute(&owner, &off_chain, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::NotDisputable)));
+        let res = client.try_open_dispute(&owner, &999, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::BillNotFound)));
+
+        let (bill_id, _, _) = setup_disputed_payment(&env, &client, &owner);
+        let res = client.try_open_dispute(&stranger, &bill_id, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        let res = client.try_open_dispute(&owner, &bill_id, &0, &reason);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        let res = client.try_open_dispute(&owner, &bill_id, &301, &reason);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+        let paid_at = env.ledger().timestamp();
+        env.ledger()
+            .set_timestamp(paid_at + DISPUTE_WINDOW_DAYS * day + 1);
+        let res = client.try_open_dispute(&owner, &bill_id, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::DisputeWindowClosed)));
+        env.ledger()
+            .set_timestamp(paid_at + DISPUTE_WINDOW_DAYS * day);
+        client.open_dispute(&owner, &bill_id, &300, &reason);
+    }
 
+    #[test]
+    fn test_payer_disputes_own_payment() {
+        let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sibling, &1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        let reason = String::from_str(&env, "Paid the wrong bill");
+
+        let bill_id = create_test_bill(&env, &client, &owner, 400, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        client.pay_bill_partial(&sibling, &bill_id, &100);
+        client.pay_bill(&owner, &bill_id);
 
-        client.create_bill(
-            &owner,
-            &String::from_str(&env, "Internet"),
-            &150,
-            &due_date,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+        let res = client.try_open_dispute(&sibling, &bill_id, &101, &reason);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        client.open_dispute(&sibling, &bill_id, &100, &reason);
+        client.accept_dispute(&payee, &bill_id);
+        assert_eq!(token_client.balance(&sibling), 1_000);
+        assert_eq!(token_client.balance(&owner), 700);
+    }
 
-        let page = client.get_overdue_bills(&0, &100);
-        assert_eq!(page.count, 0);
+    #[test]
+    fn test_escalated_dispute_resolved_by_admin() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let (upheld, payee, token) = setup_disputed_payment(&env, &client, &owner);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        let reason = String::from_str(&env, "Service not delivered");
+
+        client.open_dispute(&owner, &upheld, &300, &reason);
+        let res = client.try_escalate_dispute(&owner, &upheld);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
+        let res = client.try_reject_dispute(&owner, &upheld);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.reject_dispute(&payee, &upheld);
+        assert_eq!(token_client.balance(&payee), 0);
+        assert_eq!(token_client.balance(&cid), 300);
+        assert!(client.get_dispute(&upheld).unwrap().escrowed);
+        let res = client.try_resolve_dispute(&admin, &upheld, &true);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
+        client.escalate_dispute(&owner, &upheld);
+        assert_eq!(
+            client.get_bill(&upheld).unwrap().dispute,
+            DisputeStatus::Escalated
+        );
 
-        env.ledger().set_timestamp(due_date + 1);
-        let page = client.get_overdue_bills(&0, &100);
+        // Ruling for the disputant pays the refund out of escrow.
+        let res = client.try_resolve_dispute(&owner, &upheld, &true);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+        client.resolve_dispute(&admin, &upheld, &true);
+        let dispute = client.get_dispute(&upheld).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Refunded);
+        assert!(!dispute.escrowed);
+        assert_eq!(dispute.resolved_at, Some(env.ledger().timestamp()));
+        assert_eq!(token_client.balance(&owner), 1_000);
+        assert_eq!(token_client.balance(&cid), 0);
+        let res = client.try_accept_dispute(&payee, &upheld);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
+
+        // Ruling for the payee returns the escrow and closes the dispute.
+        let dismissed = create_test_bill(&env, &client, &owner, 200, false);
+        client.set_bill_payee(&owner, &dismissed, &payee, &token);
+        client.pay_bill(&owner, &dismissed);
+        client.open_dispute(&owner, &dismissed, &200, &reason);
+        client.reject_dispute(&payee, &dismissed);
+        client.escalate_dispute(&owner, &dismissed);
+        client.resolve_dispute(&admin, &dismissed, &false);
         assert_eq!(
-            page.count, 1,
-            "Bill must appear overdue exactly one second past due_date"
+            client.get_bill(&dismissed).unwrap().dispute,
+            DisputeStatus::Dismissed
         );
+        let res = client.try_accept_dispute(&payee, &dismissed);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
+        assert_eq!(token_client.balance(&owner), 800);
+        assert_eq!(token_client.balance(&payee), 200);
+        assert_eq!(token_client.balance(&cid), 0);
     }
-    #[test]
-    /// Mix of past-due, exactly-due, and future bills: only past-due one appears.
-    fn test_time_drift_overdue_boundary_mixed_bills() {
-        let env = Env::default();
-        // 1. Set time to long ago
-        env.ledger().set_timestamp(1_000_000);
 
-        let contract_id = env.register_contract(None, BillPayments);
-        let client = BillPaymentsClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
+    #[test]
+    fn test_unanswered_dispute_can_be_escalated() {
+        let env = make_env();
         env.mock_all_auths();
-
-        // 2. Create bills with due dates in the "future" (relative to 1,000_000)
-        // This one will be our "Overdue" bill later
-        let overdue_target = 1_500_000u64;
-        client.create_bill(
-            &owner,
-            &String::from_str(&env, "Overdue"),
-            &100,
-            &overdue_target,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let (bill_id, payee, token) = setup_disputed_payment(&env, &client, &owner);
+
+        let reason = String::from_str(&env, "Overcharged");
+        client.open_dispute(&owner, &bill_id, &50, &reason);
+        let opened_at = env.ledger().timestamp();
+        env.ledger()
+            .set_timestamp(opened_at + DISPUTE_RESPONSE_DAYS * 86400 - 1);
+        let res = client.try_escalate_dispute(&owner, &bill_id);
+        assert_eq!(res, Err(Ok(Error::InvalidDisputeState)));
+        env.ledger()
+            .set_timestamp(opened_at + DISPUTE_RESPONSE_DAYS * 86400);
+        client.escalate_dispute(&owner, &bill_id);
+        assert_eq!(
+            client.get_dispute(&bill_id).unwrap().status,
+            DisputeStatus::Escalated
         );
 
-        // This one will be "DueNow" later
-        let due_now_target = 2_000_000u64;
-        client.create_bill(
-            &owner,
-            &String::from_str(&env, "DueNow"),
-            &200,
-            &due_now_target,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        // Nothing is in escrow, so an upheld ruling leaves the refund to the
+        // payee but closes the dispute.
+        client.resolve_dispute(&admin, &bill_id, &true);
+        let dispute = client.get_dispute(&bill_id).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Upheld);
+        assert_eq!(dispute.resolved_at, Some(env.ledger().timestamp()));
+        client.accept_dispute(&payee, &bill_id);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 750);
+    }
+
+    #[test]
+    fn test_upheld_dispute_does_not_block_new_dispute() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        client.set_upgrade_admin(&admin, &admin);
+        let (bill_id, payee, token) = setup_disputed_payment(&env, &client, &owner);
+        let reason = String::from_str(&env, "Overcharged");
+
+        client.open_dispute(&owner, &bill_id, &50, &reason);
+        env.ledger()
+            .set_timestamp(env.ledger().timestamp() + DISPUTE_RESPONSE_DAYS * 86400);
+        client.escalate_dispute(&owner, &bill_id);
+        client.resolve_dispute(&admin, &bill_id, &true);
+
+        // The payee ignores the ruling; the owner disputes again and the new
+        // dispute can be escalated and escrowed like any other.
+        client.open_dispute(&owner, &bill_id, &50, &reason);
+        assert_eq!(
+            client.get_dispute(&bill_id).unwrap().status,
+            DisputeStatus::Open
+        );
+        let past = client.get_past_disputes(&bill_id, &0, &10);
+        assert_eq!(past.get(0).unwrap().status, DisputeStatus::Upheld);
+        client.reject_dispute(&payee, &bill_id);
+        client.escalate_dispute(&owner, &bill_id);
+        client.resolve_dispute(&admin, &bill_id, &true);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&owner), 750);
+        assert_eq!(
+            client.get_dispute(&bill_id).unwrap().status,
+            DisputeStatus::Refunded
         );
+    }
 
-        // 3. WARP to the "Present" (2,000_000)
-        env.ledger().set_timestamp(2_000_000);
+    #[test]
+    fn test_owner_dispute_refunds_payers_pro_rata() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sibling, &1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        let reason = String::from_str(&env, "Charged twice");
+
+        let bill_id = create_test_bill(&env, &client, &owner, 400, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.set_bill_payers(&owner, &bill_id, &PayerPolicy::Anyone);
+        client.pay_bill_partial(&sibling, &bill_id, &100);
+        client.pay_bill(&owner, &bill_id);
 
-        let page = client.get_overdue_bills(&0, &100);
+        // The owner cannot take the sibling's contribution for themselves.
+        client.open_dispute(&owner, &bill_id, &200, &reason);
+        let refunds = client.get_dispute(&bill_id).unwrap().refunds;
+        assert_eq!(refunds.get(owner.clone()), Some(150));
+        assert_eq!(refunds.get(sibling.clone()), Some(50));
+        client.accept_dispute(&payee, &bill_id);
+        assert_eq!(token_client.balance(&owner), 850);
+        assert_eq!(token_client.balance(&sibling), 950);
+    }
 
-        // Now overdue_target (1.5M) is < current (2M) -> OVERDUE
-        // due_now_target (2M) is NOT < current (2M) -> NOT OVERDUE
-        assert_eq!(page.count, 1);
-        assert_eq!(page.items.get(0).unwrap().amount, 100);
+    #[test]
+    fn test_bill_can_be_disputed_again_once_closed() {
+        let env = make_env();
+        env.mock_all_auths();
+        let cid = env.register_contract(None, BillPayments);
+        let client = BillPaymentsClient::new(&env, &cid);
+        let owner = Address::generate(&env);
+        let (bill_id, payee, _) = setup_disputed_payment(&env, &client, &owner);
+        let reason = String::from_str(&env, "Overcharged");
+
+        client.open_dispute(&owner, &bill_id, &100, &reason);
+        let res = client.try_open_dispute(&owner, &bill_id, &100, &reason);
+        assert_eq!(res, Err(Ok(Error::DisputeExists)));
+        client.accept_dispute(&payee, &bill_id);
+
+        // Only what has not been refunded yet can be reclaimed.
+        let res = client.try_open_dispute(&owner, &bill_id, &201, &reason);
+        assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+        client.open_dispute(&owner, &bill_id, &200, &reason);
+        let dispute = client.get_dispute(&bill_id).unwrap();
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.refunded.get(owner.clone()), Some(100));
+        assert_eq!(
+            client.get_bill(&bill_id).unwrap().dispute,
+            DisputeStatus::Open
+        );
+
+        // The first dispute and its outcome are kept.
+        let past = client.get_past_disputes(&bill_id, &0, &10);
+        assert_eq!(past.len(), 1);
+        let first = past.get(0).unwrap();
+        assert_eq!(first.status, DisputeStatus::Refunded);
+        assert_eq!(first.amount, 100);
+        client.accept_dispute(&payee, &bill_id);
+        assert_eq!(client.get_past_disputes(&bill_id, &1, &10).len(), 0);
     }
 
-    /// Full-day boundary (86400 s): bill created at due_date, queried one day later, is overdue.
     #[test]
-    fn test_time_drift_overdue_full_day_boundary() {
-        let day = 86400u64;
-        let due_date = 1_000_000u64;
+    fn test_dispute_survives_archive_and_restore() {
         let env = make_env();
         env.mock_all_auths();
-        env.ledger().set_timestamp(due_date);
-
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
+        let (bill_id, payee, _) = setup_disputed_payment(&env, &client, &owner);
 
-        client.create_bill(
-            &owner,
-            &String::from_str(&env, "Monthly Rent"),
-            &5000,
-            &due_date,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        let page = client.get_overdue_bills(&0, &100);
-        assert_eq!(page.count, 0);
-
-        env.ledger().set_timestamp(due_date + day);
-        let page = client.get_overdue_bills(&0, &100);
+        client.open_dispute(&owner, &bill_id, &300, &String::from_str(&env, "Duplicate"));
+        client.archive_paid_bills(&owner, &u64::MAX);
+        client.reject_dispute(&payee, &bill_id);
+        client.restore_bill(&owner, &bill_id);
         assert_eq!(
-            page.count, 1,
-            "Bill must be overdue one full day past due_date"
+            client.get_bill(&bill_id).unwrap().dispute,
+            DisputeStatus::Rejected
         );
     }
 
     // -----------------------------------------------------------------------
-    // Strict Owner Authorization Lifecycle Tests
+    // Due reminder tests
     // -----------------------------------------------------------------------
 
-    /// ### Test: `test_create_bill_no_auth_fails`
-    /// **Objective**: Verify that `create_bill` reverts if the owner doesn't authorize the call.
-    /// **Expected**: Reverts with a Soroban AuthError.
-    #[test]
-    #[should_panic(expected = "Error(Auth, InvalidAction)")]
-    fn test_create_bill_no_auth_fails() {
-        let env = make_env();
-        let cid = env.register_contract(None, BillPayments);
-        let client = BillPaymentsClient::new(&env, &cid);
-        let owner = Address::generate(&env);
-
-        // Attempting to create a bill without mocking auth should fail on owner.require_auth()
+    fn create_bill_due(
+        env: &Env,
+        client: &BillPaymentsClient,
+        owner: &Address,
+        due_date: u64,
+    ) -> u32 {
         client.create_bill(
-            &owner,
-            &String::from_str(&env, "Water"),
-            &500,
-            &1000000,
+            owner,
+            &String::from_str(env, "Water"),
+            &100,
+            &due_date,
             &false,
             &0,
             &None,
-            &String::from_str(&env, "XLM"),
-        );
+            &String::from_str(env, "XLM"),
+        )
     }
 
-    /// ### Test: `test_pay_bill_wrong_owner_fails`
-    /// **Objective**: Verify that `pay_bill` reverts if a caller attempts to pay a bill they don't own.
-    /// **Authorized Caller**: `bill.owner`
-    /// **Unauthorized Caller**: `other`
-    /// **Expected**: Returns `Error::Unauthorized`.
     #[test]
-    fn test_pay_bill_wrong_owner_fails() {
+    fn test_get_bills_due_between() {
+        let day = 86400u64;
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
         let other = Address::generate(&env);
-
-        env.mock_all_auths();
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Water"),
-            &500,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        // 'other' attempts to pay owner's bill
-        let result = client.try_pay_bill(&other, &bill_id);
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let now = env.ledger().timestamp();
+
+        let tomorrow = create_bill_due(&env, &client, &owner, now + day);
+        let paid = create_bill_due(&env, &client, &owner, now + 2 * day);
+        let in_five = create_bill_due(&env, &client, &owner, now + 5 * day);
+        create_bill_due(&env, &client, &owner, now + 10 * day);
+        create_bill_due(&env, &client, &other, now + 2 * day);
+        client.pay_bill(&owner, &paid);
+
+        let page = client.get_bills_due_between(&owner, &(now + day), &(now + 5 * day), &0, &10);
+        assert_eq!(page.count, 2);
+        assert_eq!(page.items.get(0).unwrap().id, tomorrow);
+        assert_eq!(page.items.get(1).unwrap().id, in_five);
+
+        let first = client.get_bills_due_between(&owner, &now, &(now + 5 * day), &0, &1);
+        assert_eq!(first.count, 1);
+        assert_eq!(first.next_cursor, tomorrow);
+        let second =
+            client.get_bills_due_between(&owner, &now, &(now + 5 * day), &first.next_cursor, &1);
+        assert_eq!(second.items.get(0).unwrap().id, in_five);
+        assert_eq!(second.next_cursor, 0);
+
+        let empty = client.get_bills_due_between(&owner, &(now + 5 * day), &now, &0, &10);
+        assert_eq!(empty.count, 0);
     }
 
-    /// ### Test: `test_pay_bill_no_auth_fails`
-    /// **Objective**: Verify that `pay_bill` reverts if the caller is the owner but does not authorize the call.
-    /// **Expected**: Reverts with a Soroban AuthError.
     #[test]
-    #[should_panic(expected = "Error(Auth, InvalidAction)")]
-    fn test_pay_bill_no_auth_fails() {
+    fn test_set_reminder_days() {
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
 
-        // Use mock_auths specifically for creation so it doesn't affect the pay_bill call
-        env.mock_all_auths();
-        let _bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Water"),
-            &500,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        assert!(client.get_reminder_days(&owner).is_empty());
+        client.set_reminder_days(&owner, &Vec::from_array(&env, [1, 3, 3]));
+        assert_eq!(
+            client.get_reminder_days(&owner),
+            Vec::from_array(&env, [3, 1])
         );
 
-        // This will panic as expected because we are NOT mocking auths for this call
-        // and 'owner.require_auth()' will fail.
-        // We set mock_all_auths to false to disable the global mock.
-        env.set_auths(&[]); 
-        client.pay_bill(&owner, &_bill_id);
+        for days in [
+            Vec::from_array(&env, [0]),
+            Vec::from_array(&env, [MAX_REMINDER_DAYS + 1]),
+            Vec::from_array(&env, [1, 2, 3, 4, 5, 6]),
+        ] {
+            let res = client.try_set_reminder_days(&owner, &days);
+            assert_eq!(res, Err(Ok(Error::InvalidReminderConfig)));
+        }
+
+        client.set_reminder_days(&owner, &Vec::new(&env));
+        assert!(client.get_reminder_days(&owner).is_empty());
     }
 
     #[test]
-    fn test_cancel_bill_wrong_owner_fails() {
+    fn test_due_reminders_emitted_once() {
+        let day = 86400u64;
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-        let other = Address::generate(&env);
-
-        env.mock_all_auths();
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Cancel"),
-            &500,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
+        let quiet = Address::generate(&env);
+        let now = env.ledger().timestamp();
+        let due_date = now + 10 * day;
+
+        client.set_reminder_days(&owner, &Vec::from_array(&env, [3, 1]));
+        let bill_id = create_bill_due(&env, &client, &owner, due_date);
+        let paid = create_bill_due(&env, &client, &owner, due_date);
+        client.pay_bill(&owner, &paid);
+        create_bill_due(&env, &client, &quiet, due_date);
+
+        assert!(client.emit_due_reminders(&0, &0).reminded.is_empty());
+
+        env.ledger().set_timestamp(due_date - 3 * day);
+        let result = client.emit_due_reminders(&0, &0);
+        assert_eq!(result.reminded, Vec::from_array(&env, [bill_id]));
+        let event = env.events().all().last().unwrap();
+        let data: (u32, Address, u64, u32) = soroban_sdk::FromVal::from_val(&env, &event.2);
+        assert_eq!(data, (bill_id, owner.clone(), due_date, 3));
+        assert_eq!(client.get_bill(&bill_id).unwrap().reminded_days, Some(3));
+        assert!(client.emit_due_reminders(&0, &0).reminded.is_empty());
+
+        env.ledger().set_timestamp(due_date - day);
+        let result = client.emit_due_reminders(&0, &0);
+        assert_eq!(result.reminded, Vec::from_array(&env, [bill_id]));
+        assert!(client.emit_due_reminders(&0, &0).reminded.is_empty());
 
-        let result = client.try_cancel_bill(&other, &bill_id);
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        env.ledger().set_timestamp(due_date);
+        assert!(client.emit_due_reminders(&0, &0).reminded.is_empty());
     }
 
     #[test]
-    fn test_set_external_ref_wrong_owner_fails() {
+    fn test_late_reminder_run_sends_nearest_in_batches() {
+        let day = 86400u64;
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-        let other = Address::generate(&env);
-
-        env.mock_all_auths();
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "ExtRef"),
-            &500,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-
-        let result =
-            client.try_set_external_ref(&other, &bill_id, &Some(String::from_str(&env, "REF")));
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        let due_date = env.ledger().timestamp() + 10 * day;
+
+        client.set_reminder_days(&owner, &Vec::from_array(&env, [7, 3, 1]));
+        let ids = [
+            create_bill_due(&env, &client, &owner, due_date),
+            create_bill_due(&env, &client, &owner, due_date),
+            create_bill_due(&env, &client, &owner, due_date),
+        ];
+
+        // The keeper missed the 7- and 3-day reminders.
+        env.ledger().set_timestamp(due_date - day / 2);
+        let first = client.emit_due_reminders(&0, &2);
+        assert_eq!(first.reminded, Vec::from_array(&env, [ids[0], ids[1]]));
+        assert_eq!(first.next_cursor, ids[1]);
+        let second = client.emit_due_reminders(&first.next_cursor, &2);
+        assert_eq!(second.reminded, Vec::from_array(&env, [ids[2]]));
+        assert_eq!(second.next_cursor, 0);
+
+        for id in ids {
+            assert_eq!(client.get_bill(&id).unwrap().reminded_days, Some(1));
+        }
+        assert!(client.emit_due_reminders(&0, &0).reminded.is_empty());
     }
 
     #[test]
-    fn test_restore_bill_wrong_owner_fails() {
+    fn test_due_reminders_skip_overdue_bills() {
+        let day = 86400u64;
         let env = make_env();
+        env.mock_all_auths();
+        env.budget().reset_unlimited();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-        let other = Address::generate(&env);
+        let start = env.ledger().timestamp();
 
-        env.mock_all_auths();
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Restore"),
-            &500,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
-        );
-        client.pay_bill(&owner, &bill_id);
+        client.set_reminder_days(&owner, &Vec::from_array(&env, [3]));
+        for _ in 0..MAX_SCAN_IDS {
+            create_bill_due(&env, &client, &owner, start + day);
+        }
+        let due_date = start + 10 * day;
+        let bill_id = create_bill_due(&env, &client, &owner, due_date);
+
+        // More overdue bills than a run may visit do not hold up the reminder.
+        env.ledger().set_timestamp(due_date - 3 * day);
+        let result = client.emit_due_reminders(&0, &0);
+        assert_eq!(result.reminded, Vec::from_array(&env, [bill_id]));
+        assert_eq!(result.next_cursor, 0);
+    }
 
-        // Archive it
-        client.archive_paid_bills(&owner, &2000000);
+    // -----------------------------------------------------------------------
+    // Bill splitting tests
+    // -----------------------------------------------------------------------
 
-        // Other tries to restore
-        let result = client.try_restore_bill(&other, &bill_id);
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    fn share(participant: &Address, portion: SharePortion) -> ShareSpec {
+        ShareSpec {
+            participant: participant.clone(),
+            portion,
+        }
     }
 
     #[test]
-    fn test_batch_pay_bills_mixed_ownership_fails() {
+    fn test_split_bill_resolves_amounts_and_percentages() {
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let alice = Address::generate(&env);
-        let bob = Address::generate(&env);
-
-        env.mock_all_auths();
-        let alice_bill = client.create_bill(
-            &alice,
-            &String::from_str(&env, "Alice"),
-            &100,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        let owner = Address::generate(&env);
+        let sister = Address::generate(&env);
+        let uncle = Address::generate(&env);
+        let landlord = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+
+        let rent = create_test_bill(&env, &client, &owner, 1_000, false);
+        client.set_bill_payee(&owner, &rent, &landlord, &token);
+        let shares = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Bps(5_000)),
+                share(&sister, SharePortion::Amount(300)),
+                share(&uncle, SharePortion::Bps(2_000)),
+            ],
         );
-        let bob_bill = client.create_bill(
-            &bob,
-            &String::from_str(&env, "Bob"),
-            &200,
-            &1000000,
-            &false,
-            &0,
-            &None,
-            &String::from_str(&env, "XLM"),
+        client.split_bill(&owner, &rent, &shares);
+        let amounts: StdVec<i128> = client
+            .get_bill(&rent)
+            .unwrap()
+            .shares
+            .iter()
+            .map(|share| share.amount)
+            .collect();
+        assert_eq!(amounts, [500, 300, 200]);
+
+        // A third each of 100: the unit lost to rounding goes to the first.
+        let internet = create_test_bill(&env, &client, &owner, 100, false);
+        client.set_bill_payee(&owner, &internet, &landlord, &token);
+        let thirds = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Bps(3_333)),
+                share(&sister, SharePortion::Bps(3_333)),
+                share(&uncle, SharePortion::Bps(3_333)),
+            ],
         );
-
-        let mut ids = Vec::new(&env);
-        ids.push_back(alice_bill);
-        ids.push_back(bob_bill);
-
-        // Alice tries to batch pay both, but one is Bob's
-        let result = client.try_batch_pay_bills(&alice, &ids);
-        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        client.split_bill(&owner, &internet, &thirds);
+        let amounts: StdVec<i128> = client
+            .get_bill(&internet)
+            .unwrap()
+            .shares
+            .iter()
+            .map(|share| share.amount)
+            .collect();
+        assert_eq!(amounts, [34, 33, 33]);
+
+        for shares in [
+            Vec::from_array(&env, [share(&owner, SharePortion::Amount(999))]),
+            Vec::from_array(&env, [share(&owner, SharePortion::Bps(10_001))]),
+            Vec::from_array(
+                &env,
+                [
+                    share(&sister, SharePortion::Amount(500)),
+                    share(&sister, SharePortion::Amount(500)),
+                ],
+            ),
+            Vec::from_array(
+                &env,
+                [
+                    share(&sister, SharePortion::Amount(1_000)),
+                    share(&uncle, SharePortion::Amount(0)),
+                ],
+            ),
+        ] {
+            let res = client.try_split_bill(&owner, &rent, &shares);
+            assert_eq!(res, Err(Ok(Error::InvalidShares)));
+        }
+        let res = client.try_split_bill(&sister, &rent, &shares);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        client.split_bill(&owner, &rent, &Vec::new(&env));
+        assert!(client.get_bill(&rent).unwrap().shares.is_empty());
+        let owed = client.get_shares_owed(&sister, &0, &10);
+        assert_eq!(owed.count, 1);
+        assert_eq!(owed.items.get(0).unwrap().id, internet);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Auth, InvalidAction)")]
-    fn test_archive_paid_bills_no_auth_fails() {
+    fn test_split_bill_paid_when_all_shares_settled() {
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let caller = Address::generate(&env);
-
-        // No sign, should fail on caller.require_auth()
-        client.archive_paid_bills(&caller, &1000000);
+        let owner = Address::generate(&env);
+        let sister = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        let landlord = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&sister, &1_000);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+        let bill_id = create_test_bill(&env, &client, &owner, 1_000, false);
+        client.set_bill_payee(&owner, &bill_id, &landlord, &token);
+        let shares = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Bps(6_000)),
+                share(&sister, SharePortion::Bps(4_000)),
+            ],
+        );
+        client.split_bill(&owner, &bill_id, &shares);
+        assert_eq!(client.get_total_share_owed(&sister), 400);
+        assert_eq!(client.get_shares_owed(&sister, &0, &10).count, 1);
+        assert_eq!(client.get_total_share_owed(&owner), 0);
+
+        let res = client.try_pay_bill(&owner, &bill_id);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+        let res = client.try_pay_bill_partial(&owner, &bill_id, &100);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+        let res = client.try_pay_share(&stranger, &bill_id);
+        assert_eq!(res, Err(Ok(Error::Unauthorized)));
+
+        client.pay_share(&sister, &bill_id);
+        assert_eq!(token_client.balance(&sister), 600);
+        assert_eq!(token_client.balance(&landlord), 400);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(!bill.paid);
+        assert!(bill.shares.get(1).unwrap().paid);
+        assert!(!bill.shares.get(0).unwrap().paid);
+        assert_eq!(client.get_total_unpaid(&owner), 600);
+        assert_eq!(client.get_total_share_owed(&sister), 0);
+        assert!(client.get_shares_owed(&sister, &0, &10).items.is_empty());
+        let res = client.try_pay_share(&sister, &bill_id);
+        assert_eq!(res, Err(Ok(Error::BillAlreadyPaid)));
+
+        client.pay_share(&owner, &bill_id);
+        let bill = client.get_bill(&bill_id).unwrap();
+        assert!(bill.paid);
+        assert_eq!(bill.paid_by, Some(owner.clone()));
+        assert_eq!(token_client.balance(&landlord), 1_000);
+        assert_eq!(client.get_total_unpaid(&owner), 0);
     }
 
     #[test]
-    #[should_panic(expected = "Error(Auth, InvalidAction)")]
-    fn test_bulk_cleanup_bills_no_auth_fails() {
+    fn test_recurring_split_bill_carries_shares() {
         let env = make_env();
+        env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
-        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let sister = Address::generate(&env);
 
-        client.bulk_cleanup_bills(&admin, &1000000);
+        let bill_id = create_test_bill(&env, &client, &owner, 500, true);
+        let (payee, token) = fund_bill_payers(&env, &client, &owner, bill_id, &[&owner, &sister]);
+        let shares = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Amount(200)),
+                share(&sister, SharePortion::Amount(300)),
+            ],
+        );
+        client.split_bill(&owner, &bill_id, &shares);
+        client.pay_share(&owner, &bill_id);
+        client.pay_share(&sister, &bill_id);
+        assert!(client.get_bill(&bill_id).unwrap().paid);
+        assert_eq!(token.balance(&payee), 500);
+
+        let next = client.get_bill(&(bill_id + 1)).unwrap();
+        assert!(!next.paid);
+        assert_eq!(next.shares.len(), 2);
+        assert!(next.shares.iter().all(|share| !share.paid));
+        let owed = client.get_shares_owed(&sister, &0, &10);
+        assert_eq!(owed.items.get(0).unwrap().id, next.id);
+        assert_eq!(client.get_total_share_owed(&sister), 300);
+
+        client.cancel_bill(&owner, &next.id);
+        assert_eq!(client.get_total_share_owed(&sister), 0);
     }
 
     #[test]
-    fn test_create_bill_max_frequency_exceeded() {
+    fn test_split_bill_restrictions() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-
-        let result = client.try_create_bill(
-            &owner,
-            &String::from_str(&env, "Too Long"),
-            &100,
-            &1000000,
-            &true,
-            &(MAX_FREQUENCY_DAYS + 1), // Exceeds max frequency
-            &None,
-            &String::from_str(&env, "XLM"),
+        let sister = Address::generate(&env);
+        let payee = Address::generate(&env);
+        let token = setup_token(&env, &owner, 1_000);
+        let shares = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Bps(5_000)),
+                share(&sister, SharePortion::Bps(5_000)),
+            ],
         );
 
-        assert_eq!(result, Err(Ok(Error::InvalidFrequency)));
+        let partly_paid = create_test_bill(&env, &client, &owner, 200, false);
+        client.set_bill_payee(&owner, &partly_paid, &payee, &token);
+        client.pay_bill_partial(&owner, &partly_paid, &50);
+        let res = client.try_split_bill(&owner, &partly_paid, &shares);
+        assert_eq!(res, Err(Ok(Error::InvalidShares)));
+
+        let bill_id = create_test_bill(&env, &client, &owner, 200, false);
+        client.set_bill_payee(&owner, &bill_id, &payee, &token);
+        client.split_bill(&owner, &bill_id, &shares);
+        let res = client.try_set_bill_auto_pay(&owner, &bill_id, &true);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+        let res = client.try_update_bill_amount(&owner, &bill_id, &300);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+        let policy = LateFeePolicy::Charge(LateFeeRule {
+            grace_days: 3,
+            period_days: 7,
+            flat_fee: 10,
+            rate_bps: 0,
+            max_fee: 50,
+        });
+        let res = client.try_set_late_fee(&owner, &bill_id, &policy);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+
+        let res = client.try_clear_bill_payee(&owner, &bill_id);
+        assert_eq!(res, Err(Ok(Error::BillIsSplit)));
+
+        client.pay_share(&owner, &bill_id);
+        let res = client.try_split_bill(&owner, &bill_id, &Vec::new(&env));
+        assert_eq!(res, Err(Ok(Error::InvalidShares)));
     }
 
     #[test]
-    fn test_pay_bill_date_overflow_protection() {
+    fn test_split_bill_requires_payee() {
         let env = make_env();
         env.mock_all_auths();
         let cid = env.register_contract(None, BillPayments);
         let client = BillPaymentsClient::new(&env, &cid);
         let owner = Address::generate(&env);
-
-        // Create a bill with a very large due_date
-        let bill_id = client.create_bill(
-            &owner,
-            &String::from_str(&env, "Overflow Potential"),
-            &100,
-            &(u64::MAX - 100), // Near u64::MAX
-            &true,
-            &30,
-            &None,
-            &String::from_str(&env, "XLM"),
+        let sister = Address::generate(&env);
+        let shares = Vec::from_array(
+            &env,
+            [
+                share(&owner, SharePortion::Bps(5_000)),
+                share(&sister, SharePortion::Bps(5_000)),
+            ],
         );
 
-        // Paying this should fail because next_due_date would overflow
-        let result = client.try_pay_bill(&owner, &bill_id);
-        assert_eq!(result, Err(Ok(Error::InvalidDueDate)));
+        // Without a payee the sister's share could be marked paid with no
+        // funds moving.
+        let bill_id = create_test_bill(&env, &client, &owner, 200, false);
+        let res = client.try_split_bill(&owner, &bill_id, &shares);
+        assert_eq!(res, Err(Ok(Error::PayeeNotSet)));
+        assert_eq!(client.get_total_share_owed(&sister), 0);
+
+        let (payee, token) = fund_bill_payers(&env, &client, &owner, bill_id, &[&sister]);
+        client.split_bill(&owner, &bill_id, &shares);
+        client.pay_share(&sister, &bill_id);
+        assert_eq!(token.balance(&sister), 900);
+        assert_eq!(token.balance(&payee), 100);
     }
 }
@@ -1,6 +1,6 @@
 use bill_payments::{BillPayments, BillPaymentsClient};
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
-use soroban_sdk::{Address, Env, String};
+use soroban_sdk::{Address, Env, String, Vec};
 
 fn bench_env() -> Env {
     let env = Env::new_with_config(EnvTestConfig {
@@ -75,3 +75,162 @@ fn bench_get_total_unpaid_worst_case() {
         cpu, mem
     );
 }
+
+/// Register a contract with `other_bills` unpaid bills spread over 10 other
+/// owners and due dates, plus one bill due tomorrow for the returned owner.
+fn populated_contract(env: &Env, other_bills: u32) -> (BillPaymentsClient<'_>, Address, u32) {
+    let contract_id = env.register_contract(None, BillPayments);
+    let client = BillPaymentsClient::new(env, &contract_id);
+
+    let name = String::from_str(env, "Filler");
+    let currency = String::from_str(env, "XLM");
+    let mut others = Vec::new(env);
+    for _ in 0..10 {
+        others.push_back(<Address as AddressTrait>::generate(env));
+    }
+    for i in 0..other_bills {
+        let other = others.get(i % 10).unwrap();
+        let due_date = 1_800_000_000u64 + i as u64 * 86_400;
+        client.create_bill(
+            &other, &name, &100i128, &due_date, &false, &0u32, &None, &currency,
+        );
+    }
+
+    let owner = <Address as AddressTrait>::generate(env);
+    let bill_id = client.create_bill(
+        &owner,
+        &String::from_str(env, "Measured"),
+        &100i128,
+        &1_700_086_400u64,
+        &false,
+        &0u32,
+        &None,
+        &currency,
+    );
+    (client, owner, bill_id)
+}
+
+/// Per-call cost must not grow with the number of bills other users hold.
+///
+/// Every storage access in the test host gets slightly dearer as the total
+/// entry count grows, so each sample is paired with a control call making one
+/// fixed-size storage access; the measured call may grow by at most
+/// `accesses` times the control's growth, where `accesses` is the number of
+/// storage entries it touches.
+fn assert_flat(method: &str, accesses: u64, small: [(u64, u64); 2], large: [(u64, u64); 2]) {
+    let [op_small, ctrl_small] = small;
+    let [op_large, ctrl_large] = large;
+    println!(
+        r#"{{"contract":"bill_payments","method":"{}","scenario":"150_vs_300_bills","cpu_150":{},"cpu_300":{},"mem_150":{},"mem_300":{},"control_cpu_150":{},"control_cpu_300":{}}}"#,
+        method, op_small.0, op_large.0, op_small.1, op_large.1, ctrl_small.0, ctrl_large.0
+    );
+    let cpu_growth = op_large.0.saturating_sub(op_small.0);
+    let ctrl_cpu_growth = ctrl_large.0.saturating_sub(ctrl_small.0);
+    assert!(
+        cpu_growth <= accesses * ctrl_cpu_growth + op_small.0 / 20,
+        "{} cpu grew with total bill count: {} -> {} (control {} -> {})",
+        method,
+        op_small.0,
+        op_large.0,
+        ctrl_small.0,
+        ctrl_large.0
+    );
+    let mem_growth = op_large.1.saturating_sub(op_small.1);
+    let ctrl_mem_growth = ctrl_large.1.saturating_sub(ctrl_small.1);
+    assert!(
+        mem_growth <= accesses * ctrl_mem_growth + op_small.1 / 20,
+        "{} mem grew with total bill count: {} -> {} (control {} -> {})",
+        method,
+        op_small.1,
+        op_large.1,
+        ctrl_small.1,
+        ctrl_large.1
+    );
+}
+
+#[test]
+fn bench_get_unpaid_bills_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, _bill_id) = populated_contract(&env, *n);
+        // Control: a single instance read.
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, page) = measure(&env, || client.get_unpaid_bills(&owner, &0u32, &20u32));
+        assert_eq!(page.count, 1);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Unpaid index + one bill entry + instance.
+    assert_flat("get_unpaid_bills", 3, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_get_total_unpaid_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, _bill_id) = populated_contract(&env, *n);
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, total) = measure(&env, || client.get_total_unpaid(&owner));
+        assert_eq!(total, 100);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Unpaid total + instance.
+    assert_flat("get_total_unpaid", 2, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_pay_bill_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, bill_id) = populated_contract(&env, *n);
+        // Control: a single fixed-size instance write.
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.set_pause_admin(&owner, &owner));
+        let (cpu, mem, _) = measure(&env, || client.pay_bill(&owner, &bill_id));
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Bill, payments, payer index, unpaid index, due bucket, bucket list,
+    // unpaid total, instance.
+    assert_flat("pay_bill", 8, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_get_overdue_bills_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, _owner, _bill_id) = populated_contract(&env, *n);
+        env.ledger().set_timestamp(1_700_200_000);
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, page) = measure(&env, || client.get_overdue_bills(&0u32, &20u32));
+        assert_eq!(page.count, 1);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Bucket list, one due bucket, one bill entry, instance.
+    assert_flat("get_overdue_bills", 4, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_get_overdue_bills_with_others_overdue() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, _owner, _bill_id) = populated_contract(&env, *n);
+        // Every other owner's bill is overdue as well.
+        env.ledger()
+            .set_timestamp(1_800_000_000 + (*n as u64 + 1) * 86_400);
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, page) = measure(&env, || client.get_overdue_bills(&0u32, &20u32));
+        assert_eq!(page.count, 20);
+        assert!(page.next_cursor > 0);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Bucket list, 21 due buckets and 21 bill entries, instance.
+    assert_flat(
+        "get_overdue_bills_others_overdue",
+        44,
+        samples[0],
+        samples[1],
+    );
+}
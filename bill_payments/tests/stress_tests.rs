@@ -3,14 +3,18 @@
 //! Issue #178: Stress Test Storage Limits and TTL
 //!
 //! Coverage:
-//!   - Many bills per user (200+) exercising the instance-storage Map
+//!   - Many bills per user (200+) exercising the per-owner ID indexes
 //!   - Many bills across multiple users, verifying per-owner isolation
 //!   - Instance TTL re-bump after a ledger advancement that crosses the threshold
 //!   - Archive + cleanup behavior at scale (100 paid bills)
-//!   - Performance benchmarks (CPU instructions + memory bytes) for key reads
+//!   - Performance benchmarks (CPU instructions + memory bytes) for key reads,
+//!     including reads among 1,000 other users' bills
 //!
 //! Storage layout (bill_payments):
-//!   All bills live in one Map<u32, Bill> inside instance() storage.
+//!   Each bill lives in its own persistent() entry (`DataKey::Bill(id)`),
+//!   found through per-owner and due-date ID indexes. instance() storage
+//!   only holds counters and configuration.
+//!   PERSISTENT_BUMP_AMOUNT = 1,036,800 ledgers (~60 days)
 //!   INSTANCE_BUMP_AMOUNT   = 518,400 ledgers (~30 days)
 //!   INSTANCE_LIFETIME_THRESHOLD = 17,280 ledgers (~1 day)
 //!   ARCHIVE_BUMP_AMOUNT    = 2,592,000 ledgers (~180 days)
@@ -18,8 +22,8 @@
 //!   DEFAULT_PAGE_LIMIT     = 20
 //!   MAX_BATCH_SIZE         = 50
 
-use bill_payments::{BillPayments, BillPaymentsClient};
-use soroban_sdk::testutils::storage::Instance as _;
+use bill_payments::{BillPayments, BillPaymentsClient, DataKey};
+use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
 use soroban_sdk::{Address, Env, String};
 
@@ -114,7 +118,7 @@ fn stress_200_bills_single_user() {
 }
 
 /// Create 200 bills for a single user and verify the instance TTL stays valid
-/// after the storage Map grows to 200 entries.
+/// and every bill entry got its own persistent TTL.
 #[test]
 fn stress_instance_ttl_valid_after_200_bills() {
     let env = stress_env();
@@ -135,6 +139,19 @@ fn stress_instance_ttl_valid_after_200_bills() {
         "Instance TTL ({}) must remain >= INSTANCE_BUMP_AMOUNT (518,400) after 200 creates",
         ttl
     );
+
+    // PERSISTENT_BUMP_AMOUNT is capped by max_entry_ttl (700,000) here.
+    for id in [1u32, 100, 200] {
+        let ttl = env.as_contract(&contract_id, || {
+            env.storage().persistent().get_ttl(&DataKey::Bill(id))
+        });
+        assert!(
+            ttl >= 259_200,
+            "Bill {} TTL ({}) must be >= PERSISTENT_LIFETIME_THRESHOLD (259,200)",
+            id,
+            ttl
+        );
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -403,15 +420,18 @@ fn stress_archive_across_5_users() {
         client.pay_bill(&users[((id - 1) / BILLS_PER_USER) as usize], &id);
     }
 
-    // Archive using first user as caller (any authenticated address may archive)
+    // Each user archives only their own bills
     let archived = client.archive_paid_bills(&users[0], &2_000_000_000u64);
     assert_eq!(
-        archived,
-        N_USERS as u32 * BILLS_PER_USER,
-        "All {} bills across {} users must be archived",
-        N_USERS * BILLS_PER_USER as usize,
-        N_USERS
+        archived, BILLS_PER_USER,
+        "Archiving must not touch other users' bills"
     );
+    for user in users.iter().skip(1) {
+        assert_eq!(
+            client.archive_paid_bills(user, &2_000_000_000u64),
+            BILLS_PER_USER
+        );
+    }
 
     let stats = client.get_storage_stats();
     assert_eq!(stats.active_bills, 0);
@@ -423,7 +443,7 @@ fn stress_archive_across_5_users() {
 // ---------------------------------------------------------------------------
 
 /// Measure CPU and memory cost for fetching the first page (50 items) of
-/// unpaid bills when the instance Map holds 200 entries.
+/// unpaid bills when the owner has 200 bills.
 #[test]
 fn bench_get_unpaid_bills_first_page_of_200() {
     let env = stress_env();
@@ -533,6 +553,65 @@ fn bench_get_total_unpaid_200_bills() {
     );
 }
 
+/// Measure reads for one owner with 10 overdue bills while 20 other users hold
+/// 1,000 bills due later. Only the owner's index entries and bills are loaded.
+#[test]
+fn bench_reads_among_1000_other_bills() {
+    let env = stress_env();
+    let contract_id = env.register_contract(None, BillPayments);
+    let client = BillPaymentsClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let currency = String::from_str(&env, "XLM");
+
+    let name = String::from_str(&env, "OtherBill");
+    let mut others = soroban_sdk::Vec::new(&env);
+    for _ in 0..20 {
+        others.push_back(Address::generate(&env));
+    }
+    for i in 0..1000u32 {
+        let other = others.get(i % 20).unwrap();
+        let due_date = 2_000_000_000u64 + (i % 30) as u64 * 86_400;
+        client.create_bill(
+            &other, &name, &100i128, &due_date, &false, &0u32, &None, &currency,
+        );
+    }
+    let name = String::from_str(&env, "OwnBill");
+    for _ in 0..10 {
+        client.create_bill(
+            &owner,
+            &name,
+            &100i128,
+            &1_700_086_400u64,
+            &false,
+            &0u32,
+            &None,
+            &currency,
+        );
+    }
+    env.ledger().set_timestamp(1_700_200_000);
+
+    let (cpu, mem, page) = measure(&env, || client.get_unpaid_bills(&owner, &0u32, &50u32));
+    assert_eq!(page.count, 10);
+    println!(
+        r#"{{"contract":"bill_payments","method":"get_unpaid_bills","scenario":"10_of_1010_bills","cpu":{},"mem":{}}}"#,
+        cpu, mem
+    );
+
+    let (cpu, mem, page) = measure(&env, || client.get_overdue_bills(&0u32, &50u32));
+    assert_eq!(page.count, 10);
+    println!(
+        r#"{{"contract":"bill_payments","method":"get_overdue_bills","scenario":"10_of_1010_bills","cpu":{},"mem":{}}}"#,
+        cpu, mem
+    );
+
+    let (cpu, mem, total) = measure(&env, || client.get_total_unpaid(&owner));
+    assert_eq!(total, 1_000);
+    println!(
+        r#"{{"contract":"bill_payments","method":"get_total_unpaid","scenario":"10_of_1010_bills","cpu":{},"mem":{}}}"#,
+        cpu, mem
+    );
+}
+
 /// Stress test for `batch_pay_bills` with a large mixed batch (valid + invalid).
 #[test]
 fn stress_batch_pay_mixed_50() {
@@ -548,13 +627,13 @@ fn stress_batch_pay_mixed_50() {
     // Create 30 valid bills for owner
     let mut valid_ids = soroban_sdk::Vec::new(&env);
     for _ in 0..30 {
-        valid_ids.push_back(client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32));
+        valid_ids.push_back(client.create_bill(&owner, &name, &100i128, &due_date, &false, &0u32, &None, &String::from_str(&env, "XLM")));
     }
 
     // Create 10 bills for 'other' (invalid for 'owner' to pay in batch)
     let mut other_ids = soroban_sdk::Vec::new(&env);
     for _ in 0..10 {
-        other_ids.push_back(client.create_bill(&other, &name, &100i128, &due_date, &false, &0u32));
+        other_ids.push_back(client.create_bill(&other, &name, &100i128, &due_date, &false, &0u32, &None, &String::from_str(&env, "XLM")));
     }
 
     // Mix them up with some non-existent IDs (total 50)
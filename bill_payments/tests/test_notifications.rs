@@ -1,8 +1,8 @@
 #![cfg(test)]
 
-use bill_payments::{BillPayments, BillPaymentsClient};
-use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, testutils::Events, Address, Env, Symbol, TryFromVal};
+use bill_payments::{BillEvent, BillPayments, BillPaymentsClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, testutils::Events, vec, Address, Env, Symbol, TryFromVal};
 
 #[test]
 fn test_notification_flow() {
@@ -66,3 +66,49 @@ fn test_notification_flow() {
 
     std::println!("✅ Payment Event Verified");
 }
+
+#[test]
+fn test_due_soon_notification() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, BillPayments);
+    let client = BillPaymentsClient::new(&e, &contract_id);
+    let user = Address::generate(&e);
+    e.mock_all_auths();
+
+    // Remind the user 3 days before each bill is due
+    client.set_reminder_days(&user, &vec![&e, 3]);
+    let due_date = e.ledger().timestamp() + 10 * 86400;
+    let bill_id = client.create_bill(
+        &user,
+        &soroban_sdk::String::from_str(&e, "Electricity"),
+        &1000,
+        &due_date,
+        &false,
+        &0,
+        &None,
+        &soroban_sdk::String::from_str(&e, "XLM"),
+    );
+
+    // CALL: Keeper run once the reminder is due
+    e.ledger().set_timestamp(due_date - 3 * 86400);
+    client.emit_due_reminders(&0, &0);
+
+    // VERIFY: Check for DueSoon Event
+    let due_event = e.events().all().last().unwrap();
+    let due_topics = &due_event.1;
+
+    let namespace: Symbol = Symbol::try_from_val(&e, &due_topics.get(0).unwrap()).unwrap();
+    let kind = BillEvent::try_from_val(&e, &due_topics.get(1).unwrap()).unwrap();
+    let data: (u32, Address, u64, u32) = <_>::try_from_val(&e, &due_event.2).unwrap();
+
+    assert_eq!(namespace, symbol_short!("bill"));
+    assert_eq!(kind, BillEvent::DueSoon);
+    assert_eq!(data, (bill_id, user, due_date, 3));
+
+    // A second run does not repeat the reminder
+    let before = e.events().all().len();
+    client.emit_due_reminders(&0, &0);
+    assert_eq!(e.events().all().len(), before);
+
+    std::println!("✅ Due Soon Event Verified");
+}
@@ -41,6 +41,7 @@ pub trait SavingsGoalsTrait {
 #[contractclient(name = "BillPaymentsClient")]
 pub trait BillPaymentsTrait {
     fn pay_bill(env: Env, caller: Address, bill_id: u32);
+    fn top_up_pool(env: Env, from: Address, owner: Address, token: Address, amount: i128) -> u32;
 }
 
 #[contractclient(name = "InsuranceClient")]
@@ -305,6 +306,31 @@ impl Orchestrator {
         result
     }
 
+    /// Move `amount` of `token` from the caller into their prepaid bills pool.
+    ///
+    /// Returns the number of due bills the bills contract auto-paid from the
+    /// pool as a result.
+    pub fn execute_bills_pool_top_up(
+        env: Env,
+        caller: Address,
+        amount: i128,
+        family_wallet_addr: Address,
+        bills_addr: Address,
+        token: Address,
+    ) -> Result<u32, OrchestratorError> {
+        Self::acquire_execution_lock(&env)?;
+        caller.require_auth();
+        let result = (|| {
+            if amount <= 0 {
+                return Err(OrchestratorError::InvalidAmount);
+            }
+            Self::check_spending_limit(&env, &family_wallet_addr, &caller, amount)?;
+            Self::top_up_bills_pool(&env, &bills_addr, &caller, &token, amount)
+        })();
+        Self::release_execution_lock(&env);
+        result
+    }
+
     pub fn execute_insurance_payment(
         env: Env,
         caller: Address,
@@ -355,6 +381,11 @@ impl Orchestrator {
         Ok(())
     }
 
+    fn top_up_bills_pool(env: &Env, addr: &Address, caller: &Address, token: &Address, amount: i128) -> Result<u32, OrchestratorError> {
+        let client = BillPaymentsClient::new(env, addr);
+        Ok(client.top_up_pool(caller, caller, token, &amount))
+    }
+
     fn pay_insurance_premium(env: &Env, addr: &Address, caller: &Address, policy_id: u32) -> Result<(), OrchestratorError> {
         let client = InsuranceClient::new(env, addr);
         client.pay_premium(caller, &policy_id);
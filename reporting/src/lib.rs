@@ -91,6 +91,8 @@ pub struct BillComplianceReport {
     pub total_amount: i128,
     pub paid_amount: i128,
     pub unpaid_amount: i128,
+    /// Part of `unpaid_amount` that is still an estimate
+    pub estimated_unpaid_amount: i128,
     pub compliance_percentage: u32,
     pub period_start: u64,
     pub period_end: u64,
@@ -280,6 +282,15 @@ pub struct Bill {
     pub paid_at: Option<u64>,
     pub schedule_id: Option<u32>,
     pub currency: soroban_sdk::String,
+    pub amount_kind: AmountKind,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AmountKind {
+    Fixed,
+    Estimated,
+    Actual,
 }
 
 #[contracttype]
@@ -641,6 +652,7 @@ impl ReportingContract {
         let mut total_amount = 0i128;
         let mut paid_amount = 0i128;
         let mut unpaid_amount = 0i128;
+        let mut estimated_unpaid_amount = 0i128;
 
         let current_time = env.ledger().timestamp();
 
@@ -659,6 +671,9 @@ impl ReportingContract {
             } else {
                 unpaid_bills += 1;
                 unpaid_amount += bill.amount;
+                if bill.amount_kind == AmountKind::Estimated {
+                    estimated_unpaid_amount += bill.amount;
+                }
                 if bill.due_date < current_time {
                     overdue_bills += 1;
                 }
@@ -679,6 +694,7 @@ impl ReportingContract {
             total_amount,
             paid_amount,
             unpaid_amount,
+            estimated_unpaid_amount,
             compliance_percentage,
             period_start,
             period_end,
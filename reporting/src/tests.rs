@@ -82,7 +82,7 @@ mod savings_goals {
 }
 
 mod bill_payments {
-    use crate::{Bill, BillPage, BillPaymentsTrait};
+    use crate::{AmountKind, Bill, BillPage, BillPaymentsTrait};
     use soroban_sdk::{contract, contractimpl, Address, Env, String as SorobanString, Vec};
 
     #[contract]
@@ -106,6 +106,7 @@ mod bill_payments {
                 paid_at: None,
                 schedule_id: None,
                 currency: SorobanString::from_str(&env, "XLM"),
+                amount_kind: AmountKind::Fixed,
             });
             BillPage {
                 count: bills.len(),
@@ -139,6 +140,7 @@ mod bill_payments {
                 paid_at: None,
                 schedule_id: None,
                 currency: SorobanString::from_str(&env, "XLM"),
+                amount_kind: AmountKind::Fixed,
             });
             bills.push_back(Bill {
                 id: 2,
@@ -153,6 +155,7 @@ mod bill_payments {
                 paid_at: Some(1704153600),
                 schedule_id: None,
                 currency: SorobanString::from_str(&env, "XLM"),
+                amount_kind: AmountKind::Fixed,
             });
             BillPage {
                 count: bills.len(),
@@ -1,10 +1,13 @@
 #![no_std]
 #![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, Address,
+    Env, Map, String, Symbol, Vec,
+};
+use remitwise_common::{
+    EventCategory, EventPriority, RemitwiseEvents, ARCHIVE_BUMP_AMOUNT, ARCHIVE_LIFETIME_THRESHOLD,
+    PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD,
 };
-use remitwise_common::{EventCategory, EventPriority, RemitwiseEvents};
 
 // Event topics
 const GOAL_CREATED: Symbol = symbol_short!("created");
@@ -46,6 +49,61 @@ const INSTANCE_BUMP_AMOUNT: u32 = 518400;
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
 pub const MAX_PAGE_LIMIT: u32 = 50;
 
+/// Width of one due-time index bucket (one day).
+pub const SCHEDULE_BUCKET_SECONDS: u64 = 86_400;
+/// Most due-index buckets (about a month) one `execute_due_savings_schedules`
+/// call walks past; a keeper returning after a long gap catches up over
+/// several calls.
+pub const MAX_DUE_BUCKET_SCAN: u32 = 30;
+
+/// Consecutive failed pulls after which a schedule is paused.
+pub const MAX_SCHEDULE_FAILURES: u32 = 3;
+/// Delay before the first retry of a failed pull; doubles on each further
+/// consecutive failure.
+pub const SCHEDULE_RETRY_BASE_SECONDS: u64 = 3_600;
+/// Shortest inactivity period a beneficiary can be configured with (30 days).
+pub const MIN_INACTIVITY_PERIOD: u64 = 30 * 86_400;
+/// Maximum number of goal templates per owner.
+pub const MAX_TEMPLATES_PER_OWNER: u32 = 20;
+/// Maximum length of a template name in bytes, leaving room for the
+/// ` #<n>` suffix added to each goal created from it.
+pub const MAX_TEMPLATE_NAME_LEN: u32 = 64;
+/// Maximum number of goals per owner with an inflow rule.
+pub const MAX_INFLOW_RULES_PER_OWNER: u32 = 10;
+const BASIS_POINTS: u32 = 10_000;
+
+/// How much of each inflow reported through `record_inflow` goes to a goal.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InflowRule {
+    /// Share of the inflow in basis points (1..=10_000).
+    Percentage(u32),
+    /// Round the inflow up to the next multiple of this unit and save the
+    /// difference.
+    RoundUp(i128),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoalInflowRule {
+    pub goal_id: u32,
+    pub rule: InflowRule,
+}
+
+/// What happens to a goal once `current_amount` first reaches `target_amount`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompletionPolicy {
+    /// Keep accepting deposits and schedule executions (legacy behavior).
+    KeepOpen,
+    /// Deactivate the goal's schedules and reject further deposits.
+    StopSchedules,
+    /// Forward any amount above the target to another goal of the same owner.
+    RedirectOverflow(u32),
+    /// Release the whole balance to the payout address and reject further deposits.
+    AutoRelease(Address),
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct SavingsGoal {
@@ -58,6 +116,14 @@ pub struct SavingsGoal {
     pub locked: bool,
     pub unlock_date: Option<u64>,
     pub tags: Vec<String>,
+    /// Ledger timestamp at which the goal first reached its target.
+    pub completed_at: Option<u64>,
+    pub completion_policy: CompletionPolicy,
+    /// Address that may claim the goal once the owner has made no authorized
+    /// call for `inactivity_period` seconds.
+    pub beneficiary: Option<Address>,
+    /// Zero when no beneficiary is set.
+    pub inactivity_period: u64,
 }
 
 /// Paginated result for savings goal queries
@@ -72,9 +138,122 @@ pub struct GoalPage {
     pub count: u32,
 }
 
+/// A completed or emptied goal moved out of the active set.
 #[contracttype]
 #[derive(Clone)]
-pub struct SavingsSchedule {
+pub struct ArchivedGoal {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub target_date: u64,
+    pub completed_at: Option<u64>,
+    pub archived_at: u64,
+    pub tags: Vec<String>,
+    pub completion_policy: CompletionPolicy,
+    pub beneficiary: Option<Address>,
+    pub inactivity_period: u64,
+}
+
+/// Owner-defined blueprint for goals created with `create_goal_from_template`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoalTemplate {
+    pub id: u32,
+    pub owner: Address,
+    /// Name given to goals created from the template.
+    pub name: String,
+    /// Recommended target; can be overridden per goal.
+    pub target_amount: i128,
+    /// Seconds from goal creation to its target date.
+    pub duration: u64,
+    pub tags: Vec<String>,
+    /// Amount pulled per interval by the auto-schedule; zero for none.
+    pub schedule_amount: i128,
+    /// Seconds between auto-schedule executions. The first runs one
+    /// interval after the goal is created.
+    pub schedule_interval: u64,
+    /// Number of goals created from the template so far.
+    pub uses: u32,
+}
+
+/// Paginated result for archived goal queries
+#[contracttype]
+#[derive(Clone)]
+pub struct ArchivedGoalPage {
+    pub items: Vec<ArchivedGoal>,
+    /// 0 means no more pages
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
+/// Saved vs target totals across an owner's active goals carrying one tag.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagTotals {
+    pub tag: String,
+    pub goal_count: u32,
+    pub total_saved: i128,
+    pub total_target: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StorageStats {
+    pub active_goals: u32,
+    pub archived_goals: u32,
+    pub total_archived_amount: i128,
+    pub last_updated: u64,
+}
+
+/// Persistent storage keys. Goals and schedules each live in their own
+/// entry so a call only pays for the entries it touches.
+#[contracttype]
+pub enum DataKey {
+    Goal(u32),
+    OwnerGoals(Address),
+    Schedule(u32),
+    OwnerSchedules(Address),
+    ArchivedGoal(u32),
+    OwnerArchivedGoals(Address),
+    /// IDs of active schedules whose `next_due` falls in this
+    /// `SCHEDULE_BUCKET_SECONDS`-wide bucket, ascending.
+    DueSchedules(u64),
+    /// Distinct tags used on an owner's active goals, in first-use order.
+    OwnerTags(Address),
+    /// IDs of an owner's active goals carrying a tag, ascending.
+    TagGoals(Address, String),
+    /// Ledger timestamp of the owner's last authorized call.
+    LastActivity(Address),
+    Template(u32),
+    /// IDs of an owner's goal templates, ascending.
+    OwnerTemplates(Address),
+    InflowRule(u32),
+    /// IDs of an owner's goals with an inflow rule, ascending.
+    OwnerInflowGoals(Address),
+}
+
+/// `SavingsGoal` as stored in the legacy instance `GOALS` map, read only by
+/// `migrate_storage` and the fallback lookups that run until it completes.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacySavingsGoal {
+    pub id: u32,
+    pub owner: Address,
+    pub name: String,
+    pub target_amount: i128,
+    pub current_amount: i128,
+    pub target_date: u64,
+    pub locked: bool,
+    pub unlock_date: Option<u64>,
+    pub tags: Vec<String>,
+}
+
+/// `SavingsSchedule` as stored in the legacy instance `SAV_SCH` map.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacySavingsSchedule {
     pub id: u32,
     pub owner: Address,
     pub goal_id: u32,
@@ -89,58 +268,66 @@ pub struct SavingsSchedule {
 }
 
 #[contracttype]
-#[derive(Clone, Copy, Debug)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum SavingsGoalsError {
-    InvalidAmount = 1,
-    GoalNotFound = 2,
-    Unauthorized = 3,
-    GoalLocked = 4,
-    InsufficientBalance = 5,
-    Overflow = 6,
+#[derive(Clone)]
+pub struct SavingsSchedule {
+    pub id: u32,
+    pub owner: Address,
+    pub goal_id: u32,
+    pub amount: i128,
+    pub next_due: u64,
+    pub interval: u64,
+    pub recurring: bool,
+    pub active: bool,
+    pub created_at: u64,
+    pub last_executed: Option<u64>,
+    pub missed_count: u32,
+    /// When set, the pending retry of a failed pull; `next_due` stays the
+    /// cadence anchor.
+    pub retry_at: Option<u64>,
+    /// Failed pulls since the last successful execution.
+    pub consecutive_failures: u32,
+    /// Set when the schedule was paused after `MAX_SCHEDULE_FAILURES`
+    /// consecutive failures; cleared by `resume_savings_schedule`.
+    pub paused: bool,
 }
 
-impl From<SavingsGoalsError> for soroban_sdk::Error {
-    fn from(err: SavingsGoalsError) -> Self {
-        match err {
-            SavingsGoalsError::InvalidAmount => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-            SavingsGoalsError::GoalNotFound => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::MissingValue,
-            )),
-            SavingsGoalsError::Unauthorized => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidAction,
-            )),
-            SavingsGoalsError::GoalLocked => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidAction,
-            )),
-            SavingsGoalsError::InsufficientBalance => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-            SavingsGoalsError::Overflow => soroban_sdk::Error::from((
-                soroban_sdk::xdr::ScErrorType::Contract,
-                soroban_sdk::xdr::ScErrorCode::InvalidInput,
-            )),
-        }
-    }
+/// Why a due schedule could not be executed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleFailureReason {
+    /// The funded goal no longer exists (archived or replaced by an import).
+    GoalNotFound,
+    /// Adding the amount would overflow the goal balance.
+    Overflow,
+    /// The owner's token balance is below the schedule amount.
+    InsufficientBalance,
+    /// The owner has not approved this contract for the schedule amount.
+    InsufficientAllowance,
+    /// The token contract rejected the transfer.
+    TransferFailed,
 }
 
-impl From<&SavingsGoalsError> for soroban_sdk::Error {
-    fn from(err: &SavingsGoalsError) -> Self {
-        (*err).into()
-    }
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduleFailure {
+    pub schedule_id: u32,
+    pub reason: ScheduleFailureReason,
 }
 
-impl From<soroban_sdk::Error> for SavingsGoalsError {
-    fn from(_err: soroban_sdk::Error) -> Self {
-        SavingsGoalsError::Unauthorized
-    }
+/// Outcome of one `execute_due_savings_schedules` call.
+#[contracttype]
+#[derive(Clone)]
+pub struct ExecutionReport {
+    /// Schedules that funded their goal.
+    pub executed: Vec<u32>,
+    /// Schedules deactivated without funding because their goal's completion
+    /// policy closed it.
+    pub skipped: Vec<u32>,
+    /// Schedules that could not be executed. Failed token pulls are retried
+    /// with backoff (or paused); other failures deactivate the schedule.
+    pub failed: Vec<ScheduleFailure>,
+    /// True when due schedules may remain; call again to continue.
+    pub has_more: bool,
 }
 
 #[contracttype]
@@ -157,6 +344,29 @@ pub enum SavingsEvent {
     ScheduleMissed,
     ScheduleModified,
     ScheduleCancelled,
+    CompletionPolicySet,
+    OverflowRedirected,
+    FundsReleased,
+    GoalArchived,
+    GoalRestored,
+    ScheduleFailed,
+    ScheduleRetry,
+    SchedulePaused,
+    ScheduleResumed,
+    FundsTransferred,
+    GoalsRebalanced,
+    BeneficiarySet,
+    BeneficiaryCleared,
+    Heartbeat,
+    GoalClaimed,
+    GoalFundsClaimed,
+    TemplateCreated,
+    TemplateDeleted,
+    TemplateApplied,
+    InflowRuleSet,
+    InflowRuleCleared,
+    InflowApplied,
+    InflowFailed,
 }
 
 /// Snapshot for savings goals export/import (migration).
@@ -201,6 +411,9 @@ pub mod pause_functions {
     pub const WITHDRAW: Symbol = symbol_short!("withdraw");
     pub const LOCK: Symbol = symbol_short!("lock");
     pub const UNLOCK: Symbol = symbol_short!("unlock");
+    pub const ARCHIVE: Symbol = symbol_short!("archive");
+    pub const RESTORE: Symbol = symbol_short!("restore");
+    pub const CLAIM: Symbol = symbol_short!("claim");
 }
 
 #[contracttype]
@@ -210,12 +423,24 @@ pub struct ContributionItem {
     pub amount: i128,
 }
 
+/// Relative share of a goal in `rebalance_goals`.
+#[contracttype]
+#[derive(Clone)]
+pub struct GoalWeight {
+    pub goal_id: u32,
+    pub weight: u32,
+}
+
+/// Errors returned by every fallible savings goals entry point.
+///
+/// Codes are stable: new variants are only ever appended.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum SavingsGoalError {
     GoalNotFound = 1,
     InsufficientBalance = 2,
+    /// The goal is locked or its time-lock has not expired.
     GoalLocked = 3,
     Unauthorized = 4,
     TargetAmountMustBePositive = 5,
@@ -224,6 +449,39 @@ pub enum SavingsGoalError {
     UnsupportedVersion = 6,
     /// Snapshot checksum does not match the recomputed digest.
     ChecksumMismatch = 7,
+    InvalidAmount = 8,
+    Overflow = 9,
+    /// The goal's completion policy no longer accepts deposits.
+    GoalCompleted = 10,
+    /// Only completed or empty goals can be archived.
+    GoalNotArchivable = 11,
+    /// The same goal was given twice.
+    DuplicateGoal = 12,
+    InvalidBeneficiary = 13,
+    /// No beneficiary is set or the owner's inactivity period has not elapsed.
+    NotClaimable = 14,
+    TemplateNotFound = 15,
+    TemplateLimitReached = 16,
+    InflowRuleLimitReached = 17,
+    ContractPaused = 18,
+    FunctionPaused = 19,
+    /// The scheduled unpause time has not been reached.
+    UnpauseTimeLocked = 20,
+    /// The pause or upgrade admin has not been set.
+    AdminNotSet = 21,
+    TokenAlreadySet = 22,
+    /// The savings token must be set before the first goal is created.
+    GoalsAlreadyExist = 23,
+    /// Tag list is empty or a tag is not 1..=32 characters.
+    InvalidTags = 24,
+    InvalidCursor = 25,
+    InvalidNonce = 26,
+    ScheduleNotFound = 27,
+    /// A due date or unlock date is not in the future.
+    InvalidDate = 28,
+    ScheduleNotPaused = 29,
+    /// Template name is empty or longer than `MAX_TEMPLATE_NAME_LEN` bytes.
+    InvalidName = 30,
 }
 #[contract]
 pub struct SavingsGoalContract;
@@ -231,8 +489,10 @@ pub struct SavingsGoalContract;
 #[contractimpl]
 impl SavingsGoalContract {
     const STORAGE_NEXT_ID: Symbol = symbol_short!("NEXT_ID");
+    /// Legacy instance keys, emptied by `migrate_storage`.
     const STORAGE_GOALS: Symbol = symbol_short!("GOALS");
     const STORAGE_OWNER_GOAL_IDS: Symbol = symbol_short!("OWN_GOAL");
+    const STORAGE_SCHEDULES: Symbol = symbol_short!("SAV_SCH");
 
     // -----------------------------------------------------------------------
     // Internal helpers
@@ -265,12 +525,223 @@ impl SavingsGoalContract {
             .get(func)
             .unwrap_or(false)
     }
-    fn require_not_paused(env: &Env, func: Symbol) {
+    fn require_not_paused(env: &Env, func: Symbol) -> Result<(), SavingsGoalError> {
         if Self::get_global_paused(env) {
-            panic!("Contract is paused");
+            return Err(SavingsGoalError::ContractPaused);
         }
         if Self::is_function_paused(env, func) {
-            panic!("Function is paused");
+            return Err(SavingsGoalError::FunctionPaused);
+        }
+        Ok(())
+    }
+
+    fn require_pause_admin(env: &Env, caller: &Address) -> Result<(), SavingsGoalError> {
+        match Self::get_pause_admin(env) {
+            Some(admin) if admin == *caller => Ok(()),
+            Some(_) => Err(SavingsGoalError::Unauthorized),
+            None => Err(SavingsGoalError::AdminNotSet),
+        }
+    }
+
+    fn require_upgrade_admin(env: &Env, caller: &Address) -> Result<(), SavingsGoalError> {
+        match Self::get_upgrade_admin(env) {
+            Some(admin) if admin == *caller => Ok(()),
+            Some(_) => Err(SavingsGoalError::Unauthorized),
+            None => Err(SavingsGoalError::AdminNotSet),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Per-entry storage
+    // -----------------------------------------------------------------------
+
+    /// Loads a goal from its persistent entry, falling back to the legacy
+    /// instance map for goals `migrate_storage` has not moved yet.
+    fn load_goal(env: &Env, goal_id: u32) -> Option<SavingsGoal> {
+        let goal: Option<SavingsGoal> = env.storage().persistent().get(&DataKey::Goal(goal_id));
+        if goal.is_some() {
+            return goal;
+        }
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, LegacySavingsGoal>>(&Self::STORAGE_GOALS)
+            .and_then(|legacy| legacy.get(goal_id))
+            .map(|legacy| Self::upgrade_legacy_goal(env, legacy))
+    }
+
+    fn save_goal(env: &Env, goal: &SavingsGoal) {
+        let key = DataKey::Goal(goal.id);
+        env.storage().persistent().set(&key, goal);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    fn load_schedule(env: &Env, schedule_id: u32) -> Option<SavingsSchedule> {
+        let schedule: Option<SavingsSchedule> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id));
+        if schedule.is_some() {
+            return schedule;
+        }
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, LegacySavingsSchedule>>(&Self::STORAGE_SCHEDULES)
+            .and_then(|legacy| legacy.get(schedule_id))
+            .map(Self::upgrade_legacy_schedule)
+    }
+
+    fn save_schedule(env: &Env, schedule: &SavingsSchedule) {
+        let key = DataKey::Schedule(schedule.id);
+        env.storage().persistent().set(&key, schedule);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    /// Adds an active schedule to the due-time index.
+    fn index_due_schedule(env: &Env, schedule_id: u32, next_due: u64) {
+        let bucket = next_due / SCHEDULE_BUCKET_SECONDS;
+        let key = DataKey::DueSchedules(bucket);
+        let mut ids = Self::load_id_index(env, &key, None);
+        Self::insert_sorted(&mut ids, schedule_id);
+        Self::save_id_index(env, &key, &ids);
+
+        let head: Option<u64> = env.storage().instance().get(&symbol_short!("DUE_HEAD"));
+        if head.is_none_or(|h| bucket < h) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("DUE_HEAD"), &bucket);
+        }
+    }
+
+    fn unindex_due_schedule(env: &Env, schedule_id: u32, next_due: u64) {
+        Self::remove_from_due_bucket(env, next_due / SCHEDULE_BUCKET_SECONDS, schedule_id);
+    }
+
+    fn remove_from_due_bucket(env: &Env, bucket: u64, schedule_id: u32) {
+        let key = DataKey::DueSchedules(bucket);
+        let mut ids = Self::load_id_index(env, &key, None);
+        if let Some(i) = ids.first_index_of(schedule_id) {
+            ids.remove(i);
+            Self::save_id_index(env, &key, &ids);
+        }
+    }
+
+    /// Reads a per-owner ID index, merged with the legacy instance index
+    /// while a migration is in progress. IDs are in ascending order.
+    fn load_id_index(env: &Env, key: &DataKey, legacy_ids: Option<Vec<u32>>) -> Vec<u32> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(key)
+            .unwrap_or_else(|| Vec::new(env));
+        match legacy_ids {
+            Some(legacy) => {
+                let mut merged = ids;
+                for id in legacy.iter() {
+                    Self::insert_sorted(&mut merged, id);
+                }
+                merged
+            }
+            None => ids,
+        }
+    }
+
+    fn save_id_index(env: &Env, key: &DataKey, ids: &Vec<u32>) {
+        if ids.is_empty() {
+            env.storage().persistent().remove(key);
+            return;
+        }
+        env.storage().persistent().set(key, ids);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+
+    /// Inserts `id` keeping `ids` ascending; no-op if already present.
+    fn insert_sorted(ids: &mut Vec<u32>, id: u32) {
+        let mut position = ids.len();
+        for (i, existing) in ids.iter().enumerate() {
+            if existing == id {
+                return;
+            }
+            if existing > id {
+                position = i as u32;
+                break;
+            }
+        }
+        ids.insert(position, id);
+    }
+
+    fn owner_goal_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        let legacy = env
+            .storage()
+            .instance()
+            .get::<_, Map<Address, Vec<u32>>>(&Self::STORAGE_OWNER_GOAL_IDS)
+            .and_then(|m| m.get(owner.clone()));
+        Self::load_id_index(env, &DataKey::OwnerGoals(owner.clone()), legacy)
+    }
+
+    fn owner_schedule_ids(env: &Env, owner: &Address) -> Vec<u32> {
+        // The legacy layout had no schedule index; unmigrated schedules are
+        // found by scanning the legacy map.
+        let legacy = env
+            .storage()
+            .instance()
+            .get::<_, Map<u32, LegacySavingsSchedule>>(&Self::STORAGE_SCHEDULES)
+            .map(|m| {
+                let mut ids = Vec::new(env);
+                for (id, schedule) in m.iter() {
+                    if schedule.owner == *owner {
+                        ids.push_back(id);
+                    }
+                }
+                ids
+            });
+        Self::load_id_index(env, &DataKey::OwnerSchedules(owner.clone()), legacy)
+    }
+
+    fn upgrade_legacy_goal(env: &Env, legacy: LegacySavingsGoal) -> SavingsGoal {
+        let completed_at = if legacy.current_amount >= legacy.target_amount {
+            Some(env.ledger().timestamp())
+        } else {
+            None
+        };
+        SavingsGoal {
+            id: legacy.id,
+            owner: legacy.owner,
+            name: legacy.name,
+            target_amount: legacy.target_amount,
+            current_amount: legacy.current_amount,
+            target_date: legacy.target_date,
+            locked: legacy.locked,
+            unlock_date: legacy.unlock_date,
+            tags: legacy.tags,
+            completed_at,
+            completion_policy: CompletionPolicy::KeepOpen,
+            beneficiary: None,
+            inactivity_period: 0,
+        }
+    }
+
+    fn upgrade_legacy_schedule(legacy: LegacySavingsSchedule) -> SavingsSchedule {
+        SavingsSchedule {
+            id: legacy.id,
+            owner: legacy.owner,
+            goal_id: legacy.goal_id,
+            amount: legacy.amount,
+            next_due: legacy.next_due,
+            interval: legacy.interval,
+            recurring: legacy.recurring,
+            active: legacy.active,
+            created_at: legacy.created_at,
+            last_executed: legacy.last_executed,
+            missed_count: legacy.missed_count,
+            retry_at: None,
+            consecutive_failures: 0,
+            paused: false,
         }
     }
 
@@ -278,64 +749,58 @@ impl SavingsGoalContract {
     // Pause / upgrade
     // -----------------------------------------------------------------------
 
-    /// Bootstrap storage: set NEXT_ID to 1 and GOALS to an empty map only when
-    /// those keys are missing. Intended to be idempotent: calling init() more
-    /// than once (e.g. from different entrypoints or upgrade paths) must not
-    /// overwrite existing goals or reset NEXT_ID, to avoid ID collisions and
-    /// data loss.
+    /// Bootstrap storage: set NEXT_ID to 1 only when the key is missing.
+    /// Goals live in per-goal persistent entries and need no bootstrap.
+    /// Intended to be idempotent: calling init() more than once (e.g. from
+    /// different entrypoints or upgrade paths) must not overwrite existing
+    /// goals or reset NEXT_ID, to avoid ID collisions and data loss.
     pub fn init(env: Env) {
         let storage = env.storage().persistent();
         if storage.get::<_, u32>(&Self::STORAGE_NEXT_ID).is_none() {
             storage.set(&Self::STORAGE_NEXT_ID, &1u32);
         }
-        if storage
-            .get::<_, Map<u32, SavingsGoal>>(&Self::STORAGE_GOALS)
-            .is_none()
-        {
-            storage.set(&Self::STORAGE_GOALS, &Map::<u32, SavingsGoal>::new(&env));
-        }
     }
 
-    pub fn set_pause_admin(env: Env, caller: Address, new_admin: Address) {
+    pub fn set_pause_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), SavingsGoalError> {
         caller.require_auth();
         let current = Self::get_pause_admin(&env);
         match current {
             None => {
                 if caller != new_admin {
-                    panic!("Unauthorized");
+                    return Err(SavingsGoalError::Unauthorized);
                 }
             }
-            Some(admin) if admin != caller => panic!("Unauthorized"),
+            Some(admin) if admin != caller => return Err(SavingsGoalError::Unauthorized),
             _ => {}
         }
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSE_ADM"), &new_admin);
+        Ok(())
     }
 
-    pub fn pause(env: Env, caller: Address) {
+    pub fn pause(env: Env, caller: Address) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).unwrap();
-        if admin != caller {
-            panic!("Unauthorized");
-        }
+        Self::require_pause_admin(&env, &caller)?;
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED"), &true);
         env.events()
             .publish((symbol_short!("savings"), symbol_short!("paused")), ());
+        Ok(())
     }
 
-    pub fn unpause(env: Env, caller: Address) {
+    pub fn unpause(env: Env, caller: Address) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).unwrap();
-        if admin != caller {
-            panic!("Unauthorized");
-        }
+        Self::require_pause_admin(&env, &caller)?;
         let unpause_at: Option<u64> = env.storage().instance().get(&symbol_short!("UNP_AT"));
         if let Some(at) = unpause_at {
             if env.ledger().timestamp() < at {
-                panic!("Time-locked unpause not yet reached");
+                return Err(SavingsGoalError::UnpauseTimeLocked);
             }
             env.storage().instance().remove(&symbol_short!("UNP_AT"));
         }
@@ -344,14 +809,12 @@ impl SavingsGoalContract {
             .set(&symbol_short!("PAUSED"), &false);
         env.events()
             .publish((symbol_short!("savings"), symbol_short!("unpaused")), ());
+        Ok(())
     }
 
-    pub fn pause_function(env: Env, caller: Address, func: Symbol) {
+    pub fn pause_function(env: Env, caller: Address, func: Symbol) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).unwrap();
-        if admin != caller {
-            panic!("Unauthorized");
-        }
+        Self::require_pause_admin(&env, &caller)?;
         let mut m: Map<Symbol, bool> = env
             .storage()
             .instance()
@@ -361,14 +824,16 @@ impl SavingsGoalContract {
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED_FN"), &m);
+        Ok(())
     }
 
-    pub fn unpause_function(env: Env, caller: Address, func: Symbol) {
+    pub fn unpause_function(
+        env: Env,
+        caller: Address,
+        func: Symbol,
+    ) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        let admin = Self::get_pause_admin(&env).unwrap();
-        if admin != caller {
-            panic!("Unauthorized");
-        }
+        Self::require_pause_admin(&env, &caller)?;
         let mut m: Map<Symbol, bool> = env
             .storage()
             .instance()
@@ -378,6 +843,7 @@ impl SavingsGoalContract {
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED_FN"), &m);
+        Ok(())
     }
 
     pub fn is_paused(env: Env) -> bool {
@@ -395,6 +861,10 @@ impl SavingsGoalContract {
         env.storage().instance().get(&symbol_short!("UPG_ADM"))
     }
 
+    fn savings_token(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("SAV_TOKEN"))
+    }
+
     /// Set or transfer the upgrade admin role.
     ///
     /// # Security Requirements
@@ -406,9 +876,13 @@ impl SavingsGoalContract {
     /// - `caller`: The address attempting to set the upgrade admin
     /// - `new_admin`: The address to become the new upgrade admin
     ///
-    /// # Panics
-    /// - If caller is unauthorized for the operation
-    pub fn set_upgrade_admin(env: Env, caller: Address, new_admin: Address) {
+    /// # Errors
+    /// - `Unauthorized` if caller is unauthorized for the operation
+    pub fn set_upgrade_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), SavingsGoalError> {
         caller.require_auth();
 
         let current_upgrade_admin = Self::get_upgrade_admin(&env);
@@ -420,17 +894,15 @@ impl SavingsGoalContract {
             None => {
                 // Bootstrap pattern - caller must be setting themselves as admin
                 if caller != new_admin {
-                    panic!("Unauthorized: bootstrap requires caller == new_admin");
+                    return Err(SavingsGoalError::Unauthorized);
                 }
             }
             Some(ref current_admin) => {
                 // Admin transfer - only current admin can transfer
                 if *current_admin != caller {
-                    panic!("Unauthorized: only current upgrade admin can transfer");
+                    return Err(SavingsGoalError::Unauthorized);
                 }
             }
-        } else if caller != new_admin {
-            panic!("Unauthorized: bootstrap requires caller == new_admin");
         }
 
         env.storage()
@@ -442,6 +914,7 @@ impl SavingsGoalContract {
             (symbol_short!("savings"), symbol_short!("adm_xfr")),
             (current_upgrade_admin.clone(), new_admin.clone()),
         );
+        Ok(())
     }
 
     /// Get the current upgrade admin address.
@@ -453,15 +926,9 @@ impl SavingsGoalContract {
         Self::get_upgrade_admin(&env)
     }
 
-    pub fn set_version(env: Env, caller: Address, new_version: u32) {
+    pub fn set_version(env: Env, caller: Address, new_version: u32) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        let admin = match Self::get_upgrade_admin(&env) {
-            Some(a) => a,
-            None => panic!("No upgrade admin set"),
-        };
-        if admin != caller {
-            panic!("Unauthorized");
-        }
+        Self::require_upgrade_admin(&env, &caller)?;
         let prev = Self::get_version(env.clone());
         env.storage()
             .instance()
@@ -473,6 +940,194 @@ impl SavingsGoalContract {
             symbol_short!("upgraded"),
             (prev, new_version),
         );
+        Ok(())
+    }
+
+    /// Sets the token that backs goal balances.
+    ///
+    /// Once set, deposits and schedule executions move real tokens into the
+    /// contract and withdrawals and auto-releases pay them out. Balances
+    /// credited without a token would not be backed, so the token can only be
+    /// set once, before the first goal is created.
+    ///
+    /// # Errors
+    /// - `AdminNotSet` / `Unauthorized` if caller is not the upgrade admin
+    /// - `TokenAlreadySet` if a token is already set
+    /// - `GoalsAlreadyExist` if a goal has already been created
+    pub fn set_savings_token(env: Env, caller: Address, token: Address) -> Result<(), SavingsGoalError> {
+        caller.require_auth();
+        Self::require_upgrade_admin(&env, &caller)?;
+        if Self::savings_token(&env).is_some() {
+            return Err(SavingsGoalError::TokenAlreadySet);
+        }
+        let next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_NEXT_ID)
+            .unwrap_or(0);
+        if next_id > 0 {
+            return Err(SavingsGoalError::GoalsAlreadyExist);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("SAV_TOKEN"), &token);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::High,
+            symbol_short!("token_set"),
+            token,
+        );
+        Ok(())
+    }
+
+    pub fn get_savings_token(env: Env) -> Option<Address> {
+        Self::savings_token(&env)
+    }
+
+    /// Sets the contract (e.g. remittance split or orchestrator) allowed to
+    /// report owner inflows through `record_inflow`. Replaces any previous
+    /// reporter.
+    ///
+    /// # Errors
+    /// * `AdminNotSet` / `Unauthorized` - If caller is not the upgrade admin
+    pub fn set_inflow_reporter(
+        env: Env,
+        caller: Address,
+        reporter: Address,
+    ) -> Result<(), SavingsGoalError> {
+        caller.require_auth();
+        Self::require_upgrade_admin(&env, &caller)?;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("INFLOW_RP"), &reporter);
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::High,
+            symbol_short!("inflow_rp"),
+            reporter,
+        );
+        Ok(())
+    }
+
+    pub fn get_inflow_reporter(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("INFLOW_RP"))
+    }
+
+    /// Moves goals and schedules from the legacy instance maps (`GOALS`,
+    /// `OWN_GOAL`, `SAV_SCH`) into per-entry persistent storage.
+    ///
+    /// Works in batches of at most `max_items` entries (0 -> DEFAULT_PAGE_LIMIT,
+    /// capped at MAX_PAGE_LIMIT) so large deployments can migrate across
+    /// several transactions. Until migration finishes, reads fall back to the
+    /// legacy maps, so the contract stays usable throughout. A legacy entry
+    /// already shadowed by a persistent one (written since the upgrade) is
+    /// dropped rather than copied.
+    ///
+    /// # Returns
+    /// Number of legacy entries still waiting to be migrated; 0 when done.
+    ///
+    /// # Errors
+    /// - `AdminNotSet` / `Unauthorized` if caller is not the upgrade admin
+    ///
+    /// # Events
+    /// - Emits a System `migrated` event with `(moved, remaining)`.
+    pub fn migrate_storage(env: Env, caller: Address, max_items: u32) -> Result<u32, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_upgrade_admin(&env, &caller)?;
+        Self::extend_instance_ttl(&env);
+
+        let budget = Self::clamp_limit(max_items);
+        let mut moved = 0u32;
+
+        let mut legacy_goals: Map<u32, LegacySavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_GOALS)
+            .unwrap_or_else(|| Map::new(&env));
+        for goal_id in legacy_goals.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            if let Some(legacy) = legacy_goals.get(goal_id) {
+                let owner = legacy.owner.clone();
+                let mut goal = match env
+                    .storage()
+                    .persistent()
+                    .get::<_, SavingsGoal>(&DataKey::Goal(goal_id))
+                {
+                    Some(existing) => existing,
+                    None => Self::upgrade_legacy_goal(&env, legacy),
+                };
+                // Legacy goals may carry duplicate tags and were never in the
+                // tag index.
+                goal.tags = Self::dedup_tags(&env, &goal.tags);
+                Self::save_goal(&env, &goal);
+                Self::index_goal_tags(&env, &goal);
+                let key = DataKey::OwnerGoals(owner);
+                let mut ids = Self::load_id_index(&env, &key, None);
+                Self::insert_sorted(&mut ids, goal_id);
+                Self::save_id_index(&env, &key, &ids);
+            }
+            legacy_goals.remove(goal_id);
+            moved += 1;
+        }
+        if legacy_goals.is_empty() {
+            env.storage().instance().remove(&Self::STORAGE_GOALS);
+            env.storage().instance().remove(&Self::STORAGE_OWNER_GOAL_IDS);
+        } else {
+            env.storage()
+                .instance()
+                .set(&Self::STORAGE_GOALS, &legacy_goals);
+        }
+        // Legacy goals were never counted in the storage stats.
+        Self::adjust_storage_stats(&env, moved as i32, 0, 0);
+
+        let mut legacy_schedules: Map<u32, LegacySavingsSchedule> = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_SCHEDULES)
+            .unwrap_or_else(|| Map::new(&env));
+        for schedule_id in legacy_schedules.keys().iter() {
+            if moved >= budget {
+                break;
+            }
+            if let Some(legacy) = legacy_schedules.get(schedule_id) {
+                let schedule = match env
+                    .storage()
+                    .persistent()
+                    .get::<_, SavingsSchedule>(&DataKey::Schedule(schedule_id))
+                {
+                    Some(existing) => existing,
+                    None => Self::upgrade_legacy_schedule(legacy),
+                };
+                Self::adopt_legacy_schedule(&env, &schedule);
+                // Legacy schedules were never in the due-time index.
+                if schedule.active {
+                    Self::index_due_schedule(&env, schedule_id, Self::due_at(&schedule));
+                }
+            }
+            legacy_schedules.remove(schedule_id);
+            moved += 1;
+        }
+        if legacy_schedules.is_empty() {
+            env.storage().instance().remove(&Self::STORAGE_SCHEDULES);
+        } else {
+            env.storage()
+                .instance()
+                .set(&Self::STORAGE_SCHEDULES, &legacy_schedules);
+        }
+
+        let remaining = legacy_goals.len() + legacy_schedules.len();
+        RemitwiseEvents::emit(
+            &env,
+            EventCategory::System,
+            EventPriority::Medium,
+            symbol_short!("migrated"),
+            (moved, remaining),
+        );
+        Ok(remaining)
     }
 
     // -----------------------------------------------------------------------
@@ -484,15 +1139,16 @@ impl SavingsGoalContract {
     /// Requirements:
     /// - At least one tag must be provided.
     /// - Each tag length must be between 1 and 32 characters.
-    fn validate_tags(tags: &Vec<String>) {
+    fn validate_tags(tags: &Vec<String>) -> Result<(), SavingsGoalError> {
         if tags.is_empty() {
-            panic!("Tags cannot be empty");
+            return Err(SavingsGoalError::InvalidTags);
         }
         for tag in tags.iter() {
-            if tag.len() == 0 || tag.len() > 32 {
-                panic!("Tag must be between 1 and 32 characters");
+            if tag.is_empty() || tag.len() > 32 {
+                return Err(SavingsGoalError::InvalidTags);
             }
         }
+        Ok(())
     }
 
     /// Adds tags to a goal's metadata.
@@ -502,49 +1158,50 @@ impl SavingsGoalContract {
     /// - Only the goal owner can add tags.
     ///
     /// Notes:
-    /// - Duplicate tags are preserved as provided.
-    /// - Emits `(savings, tags_add)` with `(goal_id, caller, tags)`.
+    /// - Tags are deduplicated: tags already on the goal, and repeats within
+    ///   `tags`, are ignored.
+    /// - Emits `(savings, tags_add)` with `(goal_id, caller, tags)`, listing
+    ///   only the tags actually added.
     pub fn add_tags_to_goal(
         env: Env,
         caller: Address,
         goal_id: u32,
         tags: Vec<String>,
-    ) {
+    ) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        Self::validate_tags(&tags);
+        Self::record_activity(&env, &caller);
+        Self::validate_tags(&tags)?;
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("add_tags"), &caller, false);
-            panic!("Only the goal owner can add tags");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
+        let mut added = Vec::new(&env);
         for tag in tags.iter() {
-            goal.tags.push_back(tag);
-        }
+            if goal.tags.contains(&tag) {
+                continue;
+            }
+            goal.tags.push_back(tag.clone());
+            Self::index_goal_tag(&env, &caller, &tag, goal_id);
+            added.push_back(tag);
+        }
 
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         RemitwiseEvents::emit(
             &env,
             EventCategory::State,
             EventPriority::Medium,
             symbol_short!("tags_add"),
-            (goal_id, caller.clone(), tags.clone()),
+            (goal_id, caller.clone(), added),
         );
 
         Self::append_audit(&env, symbol_short!("add_tags"), &caller, true);
+        Ok(())
     }
 
     /// Removes tags from a goal's metadata.
@@ -561,43 +1218,30 @@ impl SavingsGoalContract {
         caller: Address,
         goal_id: u32,
         tags: Vec<String>,
-    ) {
+    ) -> Result<(), SavingsGoalError> {
         caller.require_auth();
-        Self::validate_tags(&tags);
+        Self::record_activity(&env, &caller);
+        Self::validate_tags(&tags)?;
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = goals.get(goal_id).expect("Goal not found");
+        let mut goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("rem_tags"), &caller, false);
-            panic!("Only the goal owner can remove tags");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
         let mut new_tags = Vec::new(&env);
         for existing_tag in goal.tags.iter() {
-            let mut should_keep = true;
-            for remove_tag in tags.iter() {
-                if existing_tag == remove_tag {
-                    should_keep = false;
-                    break;
-                }
-            }
-            if should_keep {
+            if tags.contains(&existing_tag) {
+                Self::unindex_goal_tag(&env, &caller, &existing_tag, goal_id);
+            } else {
                 new_tags.push_back(existing_tag);
             }
         }
 
         goal.tags = new_tags;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         RemitwiseEvents::emit(
             &env,
@@ -608,6 +1252,125 @@ impl SavingsGoalContract {
         );
 
         Self::append_audit(&env, symbol_short!("rem_tags"), &caller, true);
+        Ok(())
+    }
+
+    /// Returns a page of the owner's active goals carrying `tag`, in creation
+    /// order. Cursor rules match `get_goals`.
+    pub fn get_goals_by_tag(
+        env: Env,
+        owner: Address,
+        tag: String,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<GoalPage, SavingsGoalError> {
+        let ids = Self::load_id_index(&env, &DataKey::TagGoals(owner.clone(), tag), None);
+        Self::page_goals(&env, &owner, &ids, cursor, limit)
+    }
+
+    /// Returns the distinct tags in use on the owner's active goals.
+    pub fn get_owner_tags(env: Env, owner: Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OwnerTags(owner))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Returns saved vs target totals for the owner's active goals carrying
+    /// `tag`.
+    pub fn get_tag_totals(env: Env, owner: Address, tag: String) -> TagTotals {
+        let ids = Self::load_id_index(&env, &DataKey::TagGoals(owner, tag.clone()), None);
+        let mut totals = TagTotals {
+            tag,
+            goal_count: 0,
+            total_saved: 0,
+            total_target: 0,
+        };
+        for goal_id in ids.iter() {
+            if let Some(goal) = Self::load_goal(&env, goal_id) {
+                totals.goal_count += 1;
+                totals.total_saved = totals.total_saved.saturating_add(goal.current_amount);
+                totals.total_target = totals.total_target.saturating_add(goal.target_amount);
+            }
+        }
+        totals
+    }
+
+    /// Adds `goal_id` to the tag's goal index, registering the tag for the
+    /// owner on first use.
+    fn index_goal_tag(env: &Env, owner: &Address, tag: &String, goal_id: u32) {
+        let key = DataKey::TagGoals(owner.clone(), tag.clone());
+        let mut ids = Self::load_id_index(env, &key, None);
+        if ids.is_empty() {
+            let registry_key = DataKey::OwnerTags(owner.clone());
+            let mut registry: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&registry_key)
+                .unwrap_or_else(|| Vec::new(env));
+            if !registry.contains(tag) {
+                registry.push_back(tag.clone());
+                env.storage().persistent().set(&registry_key, &registry);
+                env.storage().persistent().extend_ttl(
+                    &registry_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+            }
+        }
+        Self::insert_sorted(&mut ids, goal_id);
+        Self::save_id_index(env, &key, &ids);
+    }
+
+    /// Removes `goal_id` from the tag's goal index, dropping the tag from
+    /// the owner's registry once no goal carries it.
+    fn unindex_goal_tag(env: &Env, owner: &Address, tag: &String, goal_id: u32) {
+        let key = DataKey::TagGoals(owner.clone(), tag.clone());
+        let mut ids = Self::load_id_index(env, &key, None);
+        let Some(i) = ids.first_index_of(goal_id) else {
+            return;
+        };
+        ids.remove(i);
+        Self::save_id_index(env, &key, &ids);
+        if ids.is_empty() {
+            let registry_key = DataKey::OwnerTags(owner.clone());
+            let mut registry: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&registry_key)
+                .unwrap_or_else(|| Vec::new(env));
+            if let Some(j) = registry.first_index_of(tag) {
+                registry.remove(j);
+            }
+            if registry.is_empty() {
+                env.storage().persistent().remove(&registry_key);
+            } else {
+                env.storage().persistent().set(&registry_key, &registry);
+            }
+        }
+    }
+
+    fn index_goal_tags(env: &Env, goal: &SavingsGoal) {
+        for tag in goal.tags.iter() {
+            Self::index_goal_tag(env, &goal.owner, &tag, goal.id);
+        }
+    }
+
+    fn unindex_goal_tags(env: &Env, goal: &SavingsGoal) {
+        for tag in goal.tags.iter() {
+            Self::unindex_goal_tag(env, &goal.owner, &tag, goal.id);
+        }
+    }
+
+    /// Drops repeated tags, keeping first occurrences in order.
+    fn dedup_tags(env: &Env, tags: &Vec<String>) -> Vec<String> {
+        let mut unique = Vec::new(env);
+        for tag in tags.iter() {
+            if !unique.contains(&tag) {
+                unique.push_back(tag);
+            }
+        }
+        unique
     }
 
     // -----------------------------------------------------------------------
@@ -632,23 +1395,38 @@ impl SavingsGoalContract {
         name: String,
         target_amount: i128,
         target_date: u64,
-    ) -> Result<u32, SavingsGoalsError> {
+    ) -> Result<u32, SavingsGoalError> {
         owner.require_auth();
-        Self::require_not_paused(&env, pause_functions::CREATE_GOAL);
+        Self::record_activity(&env, &owner);
+        Self::require_not_paused(&env, pause_functions::CREATE_GOAL)?;
 
         if target_amount <= 0 {
             Self::append_audit(&env, symbol_short!("create"), &owner, false);
-            return Err(SavingsGoalsError::InvalidAmount);
+            return Err(SavingsGoalError::InvalidAmount);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        Ok(Self::insert_goal(
+            &env,
+            &owner,
+            name,
+            target_amount,
+            target_date,
+            Vec::new(&env),
+        ))
+    }
 
+    /// Stores a new goal for `owner` and emits the creation events. Inputs
+    /// are validated by the caller.
+    fn insert_goal(
+        env: &Env,
+        owner: &Address,
+        name: String,
+        target_amount: i128,
+        target_date: u64,
+        tags: Vec<String>,
+    ) -> u32 {
         let next_id = env
             .storage()
             .instance()
@@ -659,23 +1437,26 @@ impl SavingsGoalContract {
         let goal = SavingsGoal {
             id: next_id,
             owner: owner.clone(),
-            name: name.clone(),
+            name,
             target_amount,
             current_amount: 0,
             target_date,
             locked: true,
             unlock_date: None,
-            tags: Vec::new(&env),
+            tags,
+            completed_at: None,
+            completion_policy: CompletionPolicy::KeepOpen,
+            beneficiary: None,
+            inactivity_period: 0,
         };
 
-        goals.set(next_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(env, &goal);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
-        Self::append_owner_goal_id(&env, &owner, next_id);
+        Self::append_owner_goal_id(env, owner, next_id);
+        Self::index_goal_tags(env, &goal);
+        Self::adjust_storage_stats(env, 1, 0, 0);
 
         let event = GoalCreatedEvent {
             goal_id: next_id,
@@ -685,21 +1466,21 @@ impl SavingsGoalContract {
             timestamp: env.ledger().timestamp(),
         };
         RemitwiseEvents::emit(
-            &env,
+            env,
             EventCategory::State,
             EventPriority::Medium,
             symbol_short!("created"),
             event,
         );
         RemitwiseEvents::emit(
-            &env,
+            env,
             EventCategory::State,
             EventPriority::Medium,
             symbol_short!("goal_new"),
-            (next_id, owner),
+            (next_id, owner.clone()),
         );
 
-        Ok(next_id)
+        next_id
     }
 
     /// Adds funds to an existing savings goal.
@@ -725,49 +1506,49 @@ impl SavingsGoalContract {
         caller: Address,
         goal_id: u32,
         amount: i128,
-    ) -> Result<i128, SavingsGoalsError> {
+    ) -> Result<i128, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
 
         if amount <= 0 {
             Self::append_audit(&env, symbol_short!("add"), &caller, false);
-            return Err(SavingsGoalsError::InvalidAmount);
+            return Err(SavingsGoalError::InvalidAmount);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("add"), &caller, false);
-                return Err(SavingsGoalsError::GoalNotFound);
+                return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         // Access control: verify caller is the owner
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("add"), &caller, false);
-            panic!("Only the goal owner can add funds");
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        if !Self::accepts_deposits(&goal) {
+            Self::append_audit(&env, symbol_short!("add"), &caller, false);
+            return Err(SavingsGoalError::GoalCompleted);
         }
 
         goal.current_amount = goal
             .current_amount
             .checked_add(amount)
-            .ok_or(SavingsGoalsError::Overflow)?;
+            .ok_or(SavingsGoalError::Overflow)?;
         let new_total = goal.current_amount;
-        let was_completed = new_total >= goal.target_amount;
-        let previously_completed = (new_total - amount) >= goal.target_amount;
-
-        goals.set(goal_id, goal.clone());
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        if let Some(token) = Self::savings_token(&env) {
+            TokenClient::new(&env, &token).transfer(
+                &caller,
+                &env.current_contract_address(),
+                &amount,
+            );
+        }
 
         let funds_event = FundsAddedEvent {
             goal_id,
@@ -776,83 +1557,78 @@ impl SavingsGoalContract {
             timestamp: env.ledger().timestamp(),
         };
         RemitwiseEvents::emit(&env, EventCategory::Transaction, EventPriority::Medium, symbol_short!("funds_add"), funds_event);
-
-        if was_completed && !previously_completed {
-            let completed_event = GoalCompletedEvent {
-                goal_id,
-                name: goal.name.clone(),
-                final_amount: new_total,
-                timestamp: env.ledger().timestamp(),
-            };
-            env.events().publish((GOAL_COMPLETED,), completed_event);
-        }
-
-        Self::append_audit(&env, symbol_short!("add"), &caller, true);
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::FundsAdded),
             (goal_id, caller.clone(), amount),
         );
 
-        if was_completed && !previously_completed {
-            env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                (goal_id, caller),
-            );
+        if Self::settle_completion(&env, &mut goal)? && !Self::accepts_deposits(&goal) {
+            Self::deactivate_goal_schedules(&env, &caller, goal_id);
         }
 
-        Ok(new_total)
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("add"), &caller, true);
+
+        Ok(goal.current_amount)
     }
 
     pub fn batch_add_to_goals(
         env: Env,
         caller: Address,
         contributions: Vec<ContributionItem>,
-    ) -> Result<u32, SavingsGoalsError> {
+    ) -> Result<u32, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL);
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
         if contributions.len() > MAX_BATCH_SIZE {
-            return Err(SavingsGoalsError::InvalidAmount);
+            return Err(SavingsGoalError::InvalidAmount);
         }
-        let goals_map: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut total = 0i128;
         for item in contributions.iter() {
             if item.amount <= 0 {
-                return Err(SavingsGoalsError::InvalidAmount);
+                return Err(SavingsGoalError::InvalidAmount);
             }
-            let goal = match goals_map.get(item.goal_id) {
+            total = total
+                .checked_add(item.amount)
+                .ok_or(SavingsGoalError::Overflow)?;
+            let goal = match Self::load_goal(&env, item.goal_id) {
                 Some(g) => g,
-                None => return Err(SavingsGoalsError::GoalNotFound),
+                None => return Err(SavingsGoalError::GoalNotFound),
             };
             if goal.owner != caller {
-                return Err(SavingsGoalsError::Unauthorized);
+                return Err(SavingsGoalError::Unauthorized);
+            }
+            if !Self::accepts_deposits(&goal) {
+                return Err(SavingsGoalError::GoalCompleted);
             }
         }
         Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        if let Some(token) = Self::savings_token(&env) {
+            TokenClient::new(&env, &token).transfer(
+                &caller,
+                &env.current_contract_address(),
+                &total,
+            );
+        }
         let mut count = 0u32;
         for item in contributions.iter() {
-            let mut goal = match goals.get(item.goal_id) {
+            let mut goal = match Self::load_goal(&env, item.goal_id) {
                 Some(g) => g,
-                None => return Err(SavingsGoalsError::GoalNotFound),
+                None => return Err(SavingsGoalError::GoalNotFound),
             };
             if goal.owner != caller {
-                return Err(SavingsGoalsError::Unauthorized);
+                return Err(SavingsGoalError::Unauthorized);
             }
-            goal.current_amount = match goal.current_amount.checked_add(item.amount) {
-                Some(v) => v,
-                None => panic!("overflow"),
-            };
+            // An earlier item in this batch may have completed the goal.
+            if !Self::accepts_deposits(&goal) {
+                return Err(SavingsGoalError::GoalCompleted);
+            }
+            goal.current_amount = goal
+                .current_amount
+                .checked_add(item.amount)
+                .ok_or(SavingsGoalError::Overflow)?;
             let new_total = goal.current_amount;
-            let was_completed = new_total >= goal.target_amount;
-            let previously_completed = (new_total - item.amount) >= goal.target_amount;
-            goals.set(item.goal_id, goal.clone());
             let funds_event = FundsAddedEvent {
                 goal_id: item.goal_id,
                 amount: item.amount,
@@ -866,30 +1642,18 @@ impl SavingsGoalContract {
                 symbol_short!("funds_add"),
                 funds_event,
             );
-            if was_completed && !previously_completed {
-                let completed_event = GoalCompletedEvent {
-                    goal_id: item.goal_id,
-                    name: goal.name.clone(),
-                    final_amount: new_total,
-                    timestamp: env.ledger().timestamp(),
-                };
-                env.events().publish((GOAL_COMPLETED,), completed_event);
-            }
             env.events().publish(
                 (symbol_short!("savings"), SavingsEvent::FundsAdded),
                 (item.goal_id, caller.clone(), item.amount),
             );
-            if was_completed && !previously_completed {
-                env.events().publish(
-                    (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                    (item.goal_id, caller.clone()),
-                );
+            if Self::settle_completion(&env, &mut goal)?
+                && !Self::accepts_deposits(&goal)
+            {
+                Self::deactivate_goal_schedules(&env, &caller, item.goal_id);
             }
+            Self::save_goal(&env, &goal);
             count += 1;
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
         RemitwiseEvents::emit(
             &env,
             EventCategory::Transaction,
@@ -948,64 +1712,55 @@ impl SavingsGoalContract {
         caller: Address,
         goal_id: u32,
         amount: i128,
-    ) -> Result<i128, SavingsGoalsError> {
+    ) -> Result<i128, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::WITHDRAW);
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
 
         if amount <= 0 {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::InvalidAmount);
+            return Err(SavingsGoalError::InvalidAmount);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalNotFound);
+                return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::Unauthorized);
+            return Err(SavingsGoalError::Unauthorized);
         }
 
-        if goal.locked {
+        if !Self::is_withdrawable(&env, &goal) {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::GoalLocked);
-        }
-
-        if let Some(unlock_date) = goal.unlock_date {
-            let current_time = env.ledger().timestamp();
-            if current_time < unlock_date {
-                Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-                return Err(SavingsGoalsError::GoalLocked);
-            }
+            return Err(SavingsGoalError::GoalLocked);
         }
 
         if amount > goal.current_amount {
             Self::append_audit(&env, symbol_short!("withdraw"), &caller, false);
-            return Err(SavingsGoalsError::InsufficientBalance);
+            return Err(SavingsGoalError::InsufficientBalance);
         }
 
         goal.current_amount = goal
             .current_amount
             .checked_sub(amount)
-            .ok_or(SavingsGoalsError::Overflow)?;
+            .ok_or(SavingsGoalError::Overflow)?;
         let new_amount = goal.current_amount;
 
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
+        if let Some(token) = Self::savings_token(&env) {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &caller,
+                &amount,
+            );
+        }
 
         Self::append_audit(&env, symbol_short!("withdraw"), &caller, true);
         env.events().publish(
@@ -1016,6 +1771,249 @@ impl SavingsGoalContract {
         Ok(new_amount)
     }
 
+    /// Moves funds from one of the caller's goals to another in a single
+    /// call.
+    ///
+    /// The source follows the `withdraw_from_goal` rules (unlocked, time-lock
+    /// expired) and the destination the `add_to_goal` rules (still accepting
+    /// deposits). Funds stay in the contract, so no token transfer happens.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the owner of both goals (must authorize)
+    /// * `from_goal_id` - ID of the goal to move funds out of
+    /// * `to_goal_id` - ID of the goal to move funds into
+    /// * `amount` - Amount to move in stroops (must be > 0)
+    ///
+    /// # Returns
+    /// `Ok(remaining_amount)` - The amount left in the source goal
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `DuplicateGoal` - If source and destination are the same goal
+    /// * `GoalNotFound` - If either goal does not exist
+    /// * `Unauthorized` - If caller does not own both goals
+    /// * `GoalLocked` - If the source is locked or its time-lock has not expired
+    /// * `InsufficientBalance` - If amount > source current_amount
+    /// * `GoalCompleted` - If the destination no longer accepts deposits
+    /// * `Overflow` - If the destination total would overflow i128
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::FundsTransferred` with `(from_goal_id, to_goal_id, amount)`.
+    pub fn transfer_between_goals(
+        env: Env,
+        caller: Address,
+        from_goal_id: u32,
+        to_goal_id: u32,
+        amount: i128,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+
+        let fail = |err: SavingsGoalError| {
+            Self::append_audit(&env, symbol_short!("transfer"), &caller, false);
+            Err(err)
+        };
+
+        if amount <= 0 {
+            return fail(SavingsGoalError::InvalidAmount);
+        }
+        if from_goal_id == to_goal_id {
+            return fail(SavingsGoalError::DuplicateGoal);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let (mut from, mut to) = match (
+            Self::load_goal(&env, from_goal_id),
+            Self::load_goal(&env, to_goal_id),
+        ) {
+            (Some(f), Some(t)) => (f, t),
+            _ => return fail(SavingsGoalError::GoalNotFound),
+        };
+        if from.owner != caller || to.owner != caller {
+            return fail(SavingsGoalError::Unauthorized);
+        }
+        if !Self::is_withdrawable(&env, &from) {
+            return fail(SavingsGoalError::GoalLocked);
+        }
+        if amount > from.current_amount {
+            return fail(SavingsGoalError::InsufficientBalance);
+        }
+        if !Self::accepts_deposits(&to) {
+            return fail(SavingsGoalError::GoalCompleted);
+        }
+        to.current_amount = match to.current_amount.checked_add(amount) {
+            Some(v) => v,
+            None => return fail(SavingsGoalError::Overflow),
+        };
+
+        from.current_amount -= amount;
+        // Saved first: a RedirectOverflow policy on the destination may
+        // forward funds back into the source.
+        Self::save_goal(&env, &from);
+        if Self::settle_completion(&env, &mut to)? && !Self::accepts_deposits(&to) {
+            Self::deactivate_goal_schedules(&env, &caller, to_goal_id);
+        }
+        Self::save_goal(&env, &to);
+
+        Self::append_audit(&env, symbol_short!("transfer"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsTransferred),
+            (from_goal_id, to_goal_id, amount),
+        );
+
+        Ok(from.current_amount)
+    }
+
+    /// Pools the balances of the listed goals and redistributes the total in
+    /// proportion to `weights`.
+    ///
+    /// Each goal's share is `total * weight / sum(weights)`, rounded down; the
+    /// rounding remainder goes to the first goal with a non-zero weight.
+    /// Goals whose balance shrinks must be withdrawable and goals whose
+    /// balance grows must still accept deposits. Funds stay in the contract.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the owner of every listed goal (must authorize)
+    /// * `weights` - Goals to rebalance with their relative weights (max `MAX_BATCH_SIZE`)
+    ///
+    /// # Returns
+    /// `Ok(total)` - The pooled amount that was redistributed
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If `weights` is empty, too long, or all weights are zero
+    /// * `DuplicateGoal` - If a goal is listed more than once
+    /// * `GoalNotFound` - If a goal does not exist
+    /// * `Unauthorized` - If caller does not own every goal
+    /// * `GoalLocked` - If a goal that would lose funds is locked or time-locked
+    /// * `GoalCompleted` - If a goal that would gain funds no longer accepts deposits
+    /// * `Overflow` - If the pooled total overflows i128
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::GoalsRebalanced` with `(caller, total)`.
+    pub fn rebalance_goals(
+        env: Env,
+        caller: Address,
+        weights: Vec<GoalWeight>,
+    ) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::WITHDRAW)?;
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+
+        let fail = |err: SavingsGoalError| {
+            Self::append_audit(&env, symbol_short!("rebalance"), &caller, false);
+            Err(err)
+        };
+
+        if weights.is_empty() || weights.len() > MAX_BATCH_SIZE {
+            return fail(SavingsGoalError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut seen: Vec<u32> = Vec::new(&env);
+        let mut goals: Vec<SavingsGoal> = Vec::new(&env);
+        let mut total = 0i128;
+        let mut weight_sum = 0u64;
+        for item in weights.iter() {
+            if seen.contains(item.goal_id) {
+                return fail(SavingsGoalError::DuplicateGoal);
+            }
+            seen.push_back(item.goal_id);
+            let goal = match Self::load_goal(&env, item.goal_id) {
+                Some(g) => g,
+                None => return fail(SavingsGoalError::GoalNotFound),
+            };
+            if goal.owner != caller {
+                return fail(SavingsGoalError::Unauthorized);
+            }
+            total = match total.checked_add(goal.current_amount) {
+                Some(v) => v,
+                None => return fail(SavingsGoalError::Overflow),
+            };
+            weight_sum += item.weight as u64;
+            goals.push_back(goal);
+        }
+        if weight_sum == 0 {
+            return fail(SavingsGoalError::InvalidAmount);
+        }
+
+        // Shares use `total / sum * weight + (total % sum) * weight / sum`,
+        // which cannot overflow for any i128 total.
+        let sum = weight_sum as i128;
+        let mut deltas: Vec<i128> = Vec::new(&env);
+        let mut allocated = 0i128;
+        for item in weights.iter() {
+            let weight = item.weight as i128;
+            let share = total / sum * weight + total % sum * weight / sum;
+            allocated += share;
+            deltas.push_back(share);
+        }
+        let remainder = total - allocated;
+        let mut remainder_given = false;
+        for (i, item) in weights.iter().enumerate() {
+            let i = i as u32;
+            let mut share = deltas.get(i).unwrap_or(0);
+            if !remainder_given && item.weight > 0 {
+                share += remainder;
+                remainder_given = true;
+            }
+            let goal = goals.get(i).ok_or(SavingsGoalError::GoalNotFound)?;
+            let delta = share - goal.current_amount;
+            if delta < 0 && !Self::is_withdrawable(&env, &goal) {
+                return fail(SavingsGoalError::GoalLocked);
+            }
+            if delta > 0 && !Self::accepts_deposits(&goal) {
+                return fail(SavingsGoalError::GoalCompleted);
+            }
+            deltas.set(i, delta);
+        }
+
+        // Apply decreases before increases, reloading each goal so a
+        // RedirectOverflow policy triggered by an increase is not overwritten.
+        for gaining in [false, true] {
+            for (i, item) in weights.iter().enumerate() {
+                let delta = deltas.get(i as u32).unwrap_or(0);
+                if delta == 0 || (delta > 0) != gaining {
+                    continue;
+                }
+                let mut goal =
+                    Self::load_goal(&env, item.goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+                goal.current_amount = goal
+                    .current_amount
+                    .checked_add(delta)
+                    .ok_or(SavingsGoalError::Overflow)?;
+                if gaining
+                    && Self::settle_completion(&env, &mut goal)?
+                    && !Self::accepts_deposits(&goal)
+                {
+                    Self::deactivate_goal_schedules(&env, &caller, item.goal_id);
+                }
+                Self::save_goal(&env, &goal);
+            }
+        }
+
+        Self::append_audit(&env, symbol_short!("rebalance"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalsRebalanced),
+            (caller, total),
+        );
+
+        Ok(total)
+    }
+
+    /// Whether the owner may currently take funds out of a goal: it is
+    /// unlocked and any time-lock has expired.
+    fn is_withdrawable(env: &Env, goal: &SavingsGoal) -> bool {
+        !goal.locked
+            && goal
+                .unlock_date
+                .is_none_or(|unlock_date| env.ledger().timestamp() >= unlock_date)
+    }
+
     /// Locks a goal to prevent manual withdrawals.
     ///
     /// # Arguments
@@ -1024,35 +2022,27 @@ impl SavingsGoalContract {
     ///
     /// # Events
     /// - Emits `SavingsEvent::GoalLocked`.
-    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    pub fn lock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::LOCK);
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::LOCK)?;
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-                panic!("Goal not found");
+                return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("lock"), &caller, false);
-            panic!("Only the goal owner can lock this goal");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
         goal.locked = true;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("lock"), &caller, true);
         env.events().publish(
@@ -1060,7 +2050,7 @@ impl SavingsGoalContract {
             (goal_id, caller),
         );
 
-        true
+        Ok(true)
     }
 
     /// Unlocks a goal for manual withdrawals.
@@ -1071,35 +2061,27 @@ impl SavingsGoalContract {
     ///
     /// # Events
     /// - Emits `SavingsEvent::GoalUnlocked`.
-    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> bool {
+    pub fn unlock_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::require_not_paused(&env, pause_functions::UNLOCK);
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::UNLOCK)?;
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-                panic!("Goal not found");
+                return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("unlock"), &caller, false);
-            panic!("Only the goal owner can unlock this goal");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
         goal.locked = false;
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("unlock"), &caller, true);
         env.events().publish(
@@ -1107,16 +2089,11 @@ impl SavingsGoalContract {
             (goal_id, caller),
         );
 
-        true
+        Ok(true)
     }
 
     pub fn get_goal(env: Env, goal_id: u32) -> Option<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-        goals.get(goal_id)
+        Self::load_goal(&env, goal_id)
     }
 
     // -----------------------------------------------------------------------
@@ -1124,8 +2101,8 @@ impl SavingsGoalContract {
     // -----------------------------------------------------------------------
 
     /// @notice Returns a deterministic page of goals for one owner.
-    /// @dev Paging order is anchored to the owner-goal ID index (append-only,
-    ///      ascending by creation ID), not map iteration order.
+    /// @dev Paging order is anchored to the owner-goal ID index (ascending by
+    ///      creation ID), not storage iteration order.
     /// @dev `cursor` is exclusive and must match an existing goal ID in the
     ///      owner's index when non-zero; invalid cursors are rejected.
     ///
@@ -1137,29 +2114,35 @@ impl SavingsGoalContract {
     /// # Returns
     /// `GoalPage { items, next_cursor, count }`.
     /// `next_cursor == 0` means no more pages.
-    pub fn get_goals(env: Env, owner: Address, cursor: u32, limit: u32) -> GoalPage {
-        let limit = Self::clamp_limit(limit);
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+    ///
+    /// # Errors
+    /// * `InvalidCursor` - If `cursor` is non-zero and not in the owner's index
+    pub fn get_goals(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<GoalPage, SavingsGoalError> {
+        let ids = Self::owner_goal_ids(&env, &owner);
+        Self::page_goals(&env, &owner, &ids, cursor, limit)
+    }
 
-        let owner_goal_ids: Map<Address, Vec<u32>> = env
-            .storage()
-            .instance()
-            .get(&Self::STORAGE_OWNER_GOAL_IDS)
-            .unwrap_or_else(|| Map::new(&env));
-        let ids = owner_goal_ids
-            .get(owner.clone())
-            .unwrap_or_else(|| Vec::new(&env));
+    /// Pages through `ids`, an ascending index of `owner`'s goal IDs.
+    fn page_goals(
+        env: &Env,
+        owner: &Address,
+        ids: &Vec<u32>,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<GoalPage, SavingsGoalError> {
+        let limit = Self::clamp_limit(limit);
 
         if ids.is_empty() {
-            return GoalPage {
-                items: Vec::new(&env),
+            return Ok(GoalPage {
+                items: Vec::new(env),
                 next_cursor: 0,
                 count: 0,
-            };
+            });
         }
 
         let mut start_index: u32 = 0;
@@ -1173,7 +2156,7 @@ impl SavingsGoalContract {
                 }
             }
             if !found {
-                panic!("Invalid cursor");
+                return Err(SavingsGoalError::InvalidCursor);
             }
         }
 
@@ -1182,45 +2165,36 @@ impl SavingsGoalContract {
             end_index = ids.len();
         }
 
-        let mut result = Vec::new(&env);
+        let mut result = Vec::new(env);
         for i in start_index..end_index {
-            let goal_id = ids
-                .get(i)
-                .unwrap_or_else(|| panic!("Pagination index out of sync"));
-            let goal = goals
-                .get(goal_id)
-                .unwrap_or_else(|| panic!("Pagination index out of sync"));
-            if goal.owner != owner {
-                panic!("Pagination index owner mismatch");
+            let goal_id = ids.get(i).ok_or(SavingsGoalError::GoalNotFound)?;
+            let goal = Self::load_goal(env, goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+            if goal.owner != *owner {
+                return Err(SavingsGoalError::GoalNotFound);
             }
             result.push_back(goal);
         }
 
         let next_cursor = if end_index < ids.len() {
             ids.get(end_index - 1)
-                .unwrap_or_else(|| panic!("Pagination index out of sync"))
+                .ok_or(SavingsGoalError::GoalNotFound)?
         } else {
             0
         };
 
-        GoalPage {
+        Ok(GoalPage {
             items: result,
             next_cursor,
             count: end_index - start_index,
-        }
+        })
     }
 
     /// Backward-compatible: returns ALL goals for owner in one Vec.
     /// Prefer the paginated `get_goals` for production use.
     pub fn get_all_goals(env: Env, owner: Address) -> Vec<SavingsGoal> {
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
         let mut result = Vec::new(&env);
-        for (_, goal) in goals.iter() {
-            if goal.owner == owner {
+        for goal_id in Self::owner_goal_ids(&env, &owner).iter() {
+            if let Some(goal) = Self::load_goal(&env, goal_id) {
                 result.push_back(goal);
             }
         }
@@ -1228,45 +2202,1435 @@ impl SavingsGoalContract {
     }
 
     pub fn is_goal_completed(env: Env, goal_id: u32) -> bool {
-        let storage = env.storage().instance();
-        let goals: Map<u32, SavingsGoal> = storage
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or(Map::new(&env));
-        if let Some(goal) = goals.get(goal_id) {
-            goal.current_amount >= goal.target_amount
+        if let Some(goal) = Self::load_goal(&env, goal_id) {
+            goal.completed_at.is_some() || goal.current_amount >= goal.target_amount
         } else {
             false
         }
     }
 
     // -----------------------------------------------------------------------
-    // Snapshot, audit, schedule
+    // Completion policy
     // -----------------------------------------------------------------------
 
-    pub fn get_nonce(env: Env, address: Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address))
-            .unwrap_or(0)
-    }
-
+    /// Sets what happens when the goal reaches its target.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `goal_id` - ID of the goal
+    /// * `policy` - New `CompletionPolicy`
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id (or the redirect target) does not exist
+    /// * `Unauthorized` - If caller does not own the goal or the redirect target
+    /// * `InvalidAmount` - If the goal redirects overflow to itself
+    ///
+    /// # Notes
+    /// - Setting a policy on an already completed goal applies it immediately,
+    ///   e.g. redirecting the current overflow or releasing the balance.
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::CompletionPolicySet`.
+    pub fn set_completion_policy(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        policy: CompletionPolicy,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("policy"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("policy"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        if let CompletionPolicy::RedirectOverflow(target_id) = policy {
+            if target_id == goal_id {
+                Self::append_audit(&env, symbol_short!("policy"), &caller, false);
+                return Err(SavingsGoalError::InvalidAmount);
+            }
+            let target = match Self::load_goal(&env, target_id) {
+                Some(g) => g,
+                None => {
+                    Self::append_audit(&env, symbol_short!("policy"), &caller, false);
+                    return Err(SavingsGoalError::GoalNotFound);
+                }
+            };
+            if target.owner != caller {
+                Self::append_audit(&env, symbol_short!("policy"), &caller, false);
+                return Err(SavingsGoalError::Unauthorized);
+            }
+        }
+
+        goal.completion_policy = policy.clone();
+        if goal.completed_at.is_some() {
+            Self::apply_completion_policy(&env, &mut goal)?;
+            if !Self::accepts_deposits(&goal) {
+                Self::deactivate_goal_schedules(&env, &caller, goal_id);
+            }
+        }
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("policy"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::CompletionPolicySet),
+            (goal_id, policy),
+        );
+        Ok(true)
+    }
+
+    /// Whether a goal may still receive deposits and schedule executions.
+    fn accepts_deposits(goal: &SavingsGoal) -> bool {
+        if goal.completed_at.is_none() {
+            return true;
+        }
+        matches!(
+            goal.completion_policy,
+            CompletionPolicy::KeepOpen | CompletionPolicy::RedirectOverflow(_)
+        )
+    }
+
+    /// Stamps `completed_at` the first time a goal reaches its target and
+    /// applies its completion policy. The caller persists `goal`; any other
+    /// goal touched by the policy is saved here.
+    ///
+    /// Returns `true` when this call completed the goal.
+    fn settle_completion(env: &Env, goal: &mut SavingsGoal) -> Result<bool, SavingsGoalError> {
+        let newly_completed =
+            goal.completed_at.is_none() && goal.current_amount >= goal.target_amount;
+        if newly_completed {
+            goal.completed_at = Some(env.ledger().timestamp());
+            Self::emit_goal_completed(env, goal, goal.current_amount);
+        }
+        if goal.completed_at.is_some() {
+            Self::apply_completion_policy(env, goal)?;
+        }
+        Ok(newly_completed)
+    }
+
+    /// Runs the checks `settle_completion` can fail on for `goal` without
+    /// writing anything, so callers can reject a deposit before moving funds.
+    fn check_completion(env: &Env, goal: &SavingsGoal) -> Result<(), SavingsGoalError> {
+        if goal.completed_at.is_none() && goal.current_amount < goal.target_amount {
+            return Ok(());
+        }
+        if let CompletionPolicy::RedirectOverflow(target_id) = goal.completion_policy.clone() {
+            let overflow = goal.current_amount - goal.target_amount;
+            if overflow <= 0 {
+                return Ok(());
+            }
+            if let Some(target) = Self::load_goal(env, target_id) {
+                if target.owner == goal.owner && target.id != goal.id {
+                    target
+                        .current_amount
+                        .checked_add(overflow)
+                        .ok_or(SavingsGoalError::Overflow)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_completion_policy(env: &Env, goal: &mut SavingsGoal) -> Result<(), SavingsGoalError> {
+        match goal.completion_policy.clone() {
+            CompletionPolicy::KeepOpen | CompletionPolicy::StopSchedules => {}
+            CompletionPolicy::RedirectOverflow(target_id) => {
+                let overflow = goal.current_amount - goal.target_amount;
+                if overflow <= 0 {
+                    return Ok(());
+                }
+                // A redirect target that was removed or changed hands keeps
+                // the overflow on the completed goal.
+                let mut target = match Self::load_goal(env, target_id) {
+                    Some(t) if t.owner == goal.owner && t.id != goal.id => t,
+                    _ => return Ok(()),
+                };
+                target.current_amount = target
+                    .current_amount
+                    .checked_add(overflow)
+                    .ok_or(SavingsGoalError::Overflow)?;
+                goal.current_amount = goal.target_amount;
+                // Redirects do not cascade: the target's own policy is applied
+                // on its next deposit.
+                if target.completed_at.is_none() && target.current_amount >= target.target_amount {
+                    target.completed_at = Some(env.ledger().timestamp());
+                    Self::emit_goal_completed(env, &target, target.current_amount);
+                }
+                Self::save_goal(env, &target);
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::OverflowRedirected),
+                    (goal.id, target_id, overflow),
+                );
+            }
+            CompletionPolicy::AutoRelease(payout) => {
+                let released = goal.current_amount;
+                if released <= 0 {
+                    return Ok(());
+                }
+                goal.current_amount = 0;
+                if let Some(token) = Self::savings_token(env) {
+                    TokenClient::new(env, &token).transfer(
+                        &env.current_contract_address(),
+                        &payout,
+                        &released,
+                    );
+                }
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::FundsReleased),
+                    (goal.id, payout, released),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn emit_goal_completed(env: &Env, goal: &SavingsGoal, final_amount: i128) {
+        let completed_event = GoalCompletedEvent {
+            goal_id: goal.id,
+            name: goal.name.clone(),
+            final_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((GOAL_COMPLETED,), completed_event);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalCompleted),
+            (goal.id, goal.owner.clone()),
+        );
+    }
+
+    /// Deactivates every active schedule funding `goal_id`.
+    fn deactivate_goal_schedules(env: &Env, owner: &Address, goal_id: u32) {
+        Self::deactivate_schedules_matching(env, owner, |id| id == goal_id);
+    }
+
+    /// Deactivates the owner's active schedules whose goal ID matches.
+    fn deactivate_schedules_matching(env: &Env, owner: &Address, matches: impl Fn(u32) -> bool) {
+        for schedule_id in Self::owner_schedule_ids(env, owner).iter() {
+            if let Some(mut schedule) = Self::load_schedule(env, schedule_id) {
+                let goal_id = schedule.goal_id;
+                if matches(goal_id) && schedule.active {
+                    Self::unindex_due_schedule(env, schedule_id, Self::due_at(&schedule));
+                    schedule.active = false;
+                    Self::save_schedule(env, &schedule);
+                    env.events().publish(
+                        (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
+                        (schedule_id, goal_id),
+                    );
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Templates
+    // -----------------------------------------------------------------------
+
+    /// Saves a reusable goal blueprint for the owner.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the template owner (must authorize)
+    /// * `name` - Name given to goals created from the template
+    /// * `target_amount` - Recommended target (must be > 0)
+    /// * `duration` - Seconds from goal creation to its target date (must be > 0)
+    /// * `tags` - Tags applied to each goal (may be empty; deduplicated)
+    /// * `schedule_amount` - Auto-schedule amount per interval; 0 for no schedule
+    /// * `schedule_interval` - Seconds between auto-schedule executions
+    ///   (must be > 0 when `schedule_amount` is set)
+    ///
+    /// # Returns
+    /// `Ok(template_id)`
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If target, duration or schedule settings are invalid
+    /// * `TemplateLimitReached` - If the owner already has `MAX_TEMPLATES_PER_OWNER` templates
+    /// * `InvalidTags` - If a tag is empty or longer than 32 characters
+    /// * `InvalidName` - If the name is empty or longer than `MAX_TEMPLATE_NAME_LEN` bytes
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::TemplateCreated` with `(template_id, owner)`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_goal_template(
+        env: Env,
+        owner: Address,
+        name: String,
+        target_amount: i128,
+        duration: u64,
+        tags: Vec<String>,
+        schedule_amount: i128,
+        schedule_interval: u64,
+    ) -> Result<u32, SavingsGoalError> {
+        owner.require_auth();
+        Self::record_activity(&env, &owner);
+
+        if target_amount <= 0
+            || duration == 0
+            || schedule_amount < 0
+            || (schedule_amount > 0 && schedule_interval == 0)
+        {
+            Self::append_audit(&env, symbol_short!("template"), &owner, false);
+            return Err(SavingsGoalError::InvalidAmount);
+        }
+        if name.is_empty() || name.len() > MAX_TEMPLATE_NAME_LEN {
+            Self::append_audit(&env, symbol_short!("template"), &owner, false);
+            return Err(SavingsGoalError::InvalidName);
+        }
+        if !tags.is_empty() {
+            if let Err(e) = Self::validate_tags(&tags) {
+                Self::append_audit(&env, symbol_short!("template"), &owner, false);
+                return Err(e);
+            }
+        }
+
+        let owner_key = DataKey::OwnerTemplates(owner.clone());
+        let mut ids = Self::load_id_index(&env, &owner_key, None);
+        if ids.len() >= MAX_TEMPLATES_PER_OWNER {
+            Self::append_audit(&env, symbol_short!("template"), &owner, false);
+            return Err(SavingsGoalError::TemplateLimitReached);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let template_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_TMPL"))
+            .unwrap_or(0u32)
+            + 1;
+        let template = GoalTemplate {
+            id: template_id,
+            owner: owner.clone(),
+            name,
+            target_amount,
+            duration,
+            tags: Self::dedup_tags(&env, &tags),
+            schedule_amount,
+            schedule_interval: if schedule_amount > 0 { schedule_interval } else { 0 },
+            uses: 0,
+        };
+        let key = DataKey::Template(template_id);
+        env.storage().persistent().set(&key, &template);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        ids.push_back(template_id);
+        Self::save_id_index(&env, &owner_key, &ids);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_TMPL"), &template_id);
+
+        Self::append_audit(&env, symbol_short!("template"), &owner, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::TemplateCreated),
+            (template_id, owner),
+        );
+        Ok(template_id)
+    }
+
+    /// Deletes one of the caller's templates. Goals already created from it
+    /// are unaffected.
+    ///
+    /// # Errors
+    /// * `TemplateNotFound` - If template_id does not exist
+    /// * `Unauthorized` - If caller is not the template owner
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::TemplateDeleted` with `(template_id, owner)`.
+    pub fn delete_goal_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        Self::load_owned_template(&env, &caller, template_id)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Template(template_id));
+        let owner_key = DataKey::OwnerTemplates(caller.clone());
+        let mut ids = Self::load_id_index(&env, &owner_key, None);
+        if let Some(i) = ids.first_index_of(template_id) {
+            ids.remove(i);
+        }
+        Self::save_id_index(&env, &owner_key, &ids);
+
+        Self::append_audit(&env, symbol_short!("template"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::TemplateDeleted),
+            (template_id, caller),
+        );
+        Ok(true)
+    }
+
+    pub fn get_goal_template(env: Env, template_id: u32) -> Option<GoalTemplate> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Template(template_id))
+    }
+
+    /// Returns the owner's templates in creation order.
+    pub fn get_goal_templates(env: Env, owner: Address) -> Vec<GoalTemplate> {
+        let ids = Self::load_id_index(&env, &DataKey::OwnerTemplates(owner), None);
+        let mut templates = Vec::new(&env);
+        for template_id in ids.iter() {
+            if let Some(template) = Self::get_goal_template(env.clone(), template_id) {
+                templates.push_back(template);
+            }
+        }
+        templates
+    }
+
+    /// Creates a goal from one of the caller's templates, together with its
+    /// auto-schedule when the template defines one.
+    ///
+    /// The goal gets the template's tags, a target date `duration` seconds
+    /// from now, and the template's name numbered by use (`"Rent #1"`,
+    /// `"Rent #2"`, ...) so goals from the same template can be told apart. The schedule's first run is one
+    /// `schedule_interval` from now.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the template owner (must authorize)
+    /// * `template_id` - ID of the template
+    /// * `target_amount` - Overrides the template's recommended target when set
+    ///
+    /// # Returns
+    /// `Ok(goal_id)` - ID of the new goal
+    ///
+    /// # Errors
+    /// * `TemplateNotFound` - If template_id does not exist
+    /// * `Unauthorized` - If caller is not the template owner
+    /// * `InvalidAmount` - If the target override is ≤ 0
+    ///
+    /// # Events
+    /// - Emits the usual goal and schedule creation events, then
+    ///   `SavingsEvent::TemplateApplied` with `(template_id, goal_id)`.
+    pub fn create_goal_from_template(
+        env: Env,
+        caller: Address,
+        template_id: u32,
+        target_amount: Option<i128>,
+    ) -> Result<u32, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::CREATE_GOAL)?;
+
+        let mut template = Self::load_owned_template(&env, &caller, template_id)?;
+        let target_amount = target_amount.unwrap_or(template.target_amount);
+        if target_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("create"), &caller, false);
+            return Err(SavingsGoalError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        template.uses = template.uses.saturating_add(1);
+        let key = DataKey::Template(template_id);
+        env.storage().persistent().set(&key, &template);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+
+        let now = env.ledger().timestamp();
+        let goal_id = Self::insert_goal(
+            &env,
+            &caller,
+            Self::numbered_name(&env, &template.name, template.uses),
+            target_amount,
+            now.saturating_add(template.duration),
+            template.tags,
+        );
+        if template.schedule_amount > 0 {
+            Self::insert_schedule(
+                &env,
+                &caller,
+                goal_id,
+                template.schedule_amount,
+                now.saturating_add(template.schedule_interval),
+                template.schedule_interval,
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("create"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::TemplateApplied),
+            (template_id, goal_id),
+        );
+        Ok(goal_id)
+    }
+
+    /// Returns `name` followed by ` #<n>`. Template names are at most
+    /// `MAX_TEMPLATE_NAME_LEN` bytes, so the result always fits the buffer.
+    fn numbered_name(env: &Env, name: &String, n: u32) -> String {
+        let mut buf = [0u8; MAX_TEMPLATE_NAME_LEN as usize + 12];
+        let mut len = name.len() as usize;
+        name.copy_into_slice(&mut buf[..len]);
+        buf[len] = b' ';
+        buf[len + 1] = b'#';
+        len += 2;
+
+        let mut digits = [0u8; 10];
+        let mut count = 0;
+        let mut rest = n;
+        loop {
+            digits[count] = b'0' + (rest % 10) as u8;
+            count += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        for digit in digits[..count].iter().rev() {
+            buf[len] = *digit;
+            len += 1;
+        }
+        String::from_bytes(env, &buf[..len])
+    }
+
+    fn load_owned_template(
+        env: &Env,
+        caller: &Address,
+        template_id: u32,
+    ) -> Result<GoalTemplate, SavingsGoalError> {
+        let template: GoalTemplate = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Template(template_id))
+        {
+            Some(t) => t,
+            None => {
+                Self::append_audit(env, symbol_short!("template"), caller, false);
+                return Err(SavingsGoalError::TemplateNotFound);
+            }
+        };
+        if template.owner != *caller {
+            Self::append_audit(env, symbol_short!("template"), caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        Ok(template)
+    }
+
+    // -----------------------------------------------------------------------
+    // Inflow rules
+    // -----------------------------------------------------------------------
+
+    /// Sets how much of each reported inflow goes to the goal. Replaces any
+    /// previous rule on the goal.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `goal_id` - ID of the goal
+    /// * `rule` - `Percentage(bps)` with 1..=10_000 bps, or `RoundUp(unit)` with unit > 0
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `InvalidAmount` - If the rule is out of range, or the owner's
+    ///   percentage rules would add up to more than 100%
+    /// * `InflowRuleLimitReached` - If the owner already has
+    ///   `MAX_INFLOW_RULES_PER_OWNER` rules
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::InflowRuleSet` with `(goal_id, rule)`.
+    pub fn set_inflow_rule(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        rule: InflowRule,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let fail = |err: SavingsGoalError| {
+            Self::append_audit(&env, symbol_short!("inflow"), &caller, false);
+            Err(err)
+        };
+
+        match Self::load_goal(&env, goal_id) {
+            Some(goal) if goal.owner == caller => {}
+            Some(_) => return fail(SavingsGoalError::Unauthorized),
+            None => return fail(SavingsGoalError::GoalNotFound),
+        }
+        let mut percentage_bps = match rule {
+            InflowRule::Percentage(bps) if bps > 0 && bps <= BASIS_POINTS => bps,
+            InflowRule::RoundUp(unit) if unit > 0 => 0,
+            _ => return fail(SavingsGoalError::InvalidAmount),
+        };
+
+        let owner_key = DataKey::OwnerInflowGoals(caller.clone());
+        let mut ids = Self::load_id_index(&env, &owner_key, None);
+        for other_id in ids.iter() {
+            if other_id == goal_id {
+                continue;
+            }
+            if let Some(InflowRule::Percentage(bps)) = Self::get_inflow_rule(env.clone(), other_id) {
+                percentage_bps += bps;
+            }
+        }
+        if percentage_bps > BASIS_POINTS {
+            return fail(SavingsGoalError::InvalidAmount);
+        }
+        if !ids.contains(goal_id) && ids.len() >= MAX_INFLOW_RULES_PER_OWNER {
+            return fail(SavingsGoalError::InflowRuleLimitReached);
+        }
+
+        let key = DataKey::InflowRule(goal_id);
+        env.storage().persistent().set(&key, &rule);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        Self::insert_sorted(&mut ids, goal_id);
+        Self::save_id_index(&env, &owner_key, &ids);
+
+        Self::append_audit(&env, symbol_short!("inflow"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InflowRuleSet),
+            (goal_id, rule),
+        );
+        Ok(true)
+    }
+
+    /// Removes the goal's inflow rule.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::InflowRuleCleared` when a rule was set.
+    pub fn clear_inflow_rule(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        match Self::load_goal(&env, goal_id) {
+            Some(goal) if goal.owner == caller => {}
+            Some(_) => {
+                Self::append_audit(&env, symbol_short!("inflow"), &caller, false);
+                return Err(SavingsGoalError::Unauthorized);
+            }
+            None => {
+                Self::append_audit(&env, symbol_short!("inflow"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        }
+
+        Self::remove_inflow_rule(&env, &caller, goal_id);
+        Self::append_audit(&env, symbol_short!("inflow"), &caller, true);
+        Ok(true)
+    }
+
+    pub fn get_inflow_rule(env: Env, goal_id: u32) -> Option<InflowRule> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::InflowRule(goal_id))
+    }
+
+    /// Returns the owner's inflow rules in goal creation order.
+    pub fn get_inflow_rules(env: Env, owner: Address) -> Vec<GoalInflowRule> {
+        let ids = Self::load_id_index(&env, &DataKey::OwnerInflowGoals(owner), None);
+        let mut rules = Vec::new(&env);
+        for goal_id in ids.iter() {
+            if let Some(rule) = Self::get_inflow_rule(env.clone(), goal_id) {
+                rules.push_back(GoalInflowRule { goal_id, rule });
+            }
+        }
+        rules
+    }
+
+    /// Applies the owner's inflow rules to an incoming payment of `amount`.
+    ///
+    /// Each rule is evaluated against the full `amount`. Goals that no longer
+    /// accept deposits are skipped. When a savings token is set, the total is
+    /// pulled from the owner in one `transfer_from` using the allowance
+    /// granted to this contract. If that pull fails, nothing is applied and
+    /// `InflowFailed` is emitted instead of failing the reporter's call.
+    ///
+    /// # Arguments
+    /// * `owner` - Owner who received the inflow
+    /// * `amount` - Inflow amount (must be > 0)
+    ///
+    /// # Returns
+    /// `Ok(total)` - Total amount added across the owner's goals
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If no inflow reporter is configured
+    /// * `InvalidAmount` - If amount ≤ 0
+    ///
+    /// # Panics
+    /// * If the configured inflow reporter does not authorize the call
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::FundsAdded` per funded goal, then
+    ///   `SavingsEvent::InflowApplied` with `(owner, amount, total)`.
+    pub fn record_inflow(env: Env, owner: Address, amount: i128) -> Result<i128, SavingsGoalError> {
+        let reporter = match Self::get_inflow_reporter(env.clone()) {
+            Some(r) => r,
+            None => return Err(SavingsGoalError::Unauthorized),
+        };
+        reporter.require_auth();
+        Self::require_not_paused(&env, pause_functions::ADD_TO_GOAL)?;
+        if amount <= 0 {
+            return Err(SavingsGoalError::InvalidAmount);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let owner_key = DataKey::OwnerInflowGoals(owner.clone());
+        let ids = Self::load_id_index(&env, &owner_key, None);
+        let mut live_ids = Vec::new(&env);
+        let mut contributions: Vec<ContributionItem> = Vec::new(&env);
+        let mut total = 0i128;
+        for goal_id in ids.iter() {
+            let rule = Self::get_inflow_rule(env.clone(), goal_id);
+            // A snapshot import may have removed the goal or given its ID to
+            // another owner; drop it from this owner's index.
+            let goal = match (rule.as_ref(), Self::load_goal(&env, goal_id)) {
+                (Some(_), Some(goal)) if goal.owner == owner => goal,
+                (_, None) => {
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::InflowRule(goal_id));
+                    continue;
+                }
+                _ => continue,
+            };
+            live_ids.push_back(goal_id);
+            if !Self::accepts_deposits(&goal) {
+                continue;
+            }
+            let contribution = match rule {
+                Some(InflowRule::Percentage(bps)) => {
+                    let bps = bps as i128;
+                    let base = BASIS_POINTS as i128;
+                    amount / base * bps + amount % base * bps / base
+                }
+                Some(InflowRule::RoundUp(unit)) => match amount % unit {
+                    0 => 0,
+                    rem => unit - rem,
+                },
+                None => 0,
+            };
+            if contribution > 0 {
+                total = total
+                    .checked_add(contribution)
+                    .ok_or(SavingsGoalError::Overflow)?;
+                contributions.push_back(ContributionItem {
+                    goal_id,
+                    amount: contribution,
+                });
+            }
+        }
+        if live_ids.len() != ids.len() {
+            Self::save_id_index(&env, &owner_key, &live_ids);
+        }
+        if total == 0 {
+            return Ok(0);
+        }
+
+        if let Err(reason) = Self::pull_owner_funds(&env, &owner, total) {
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::InflowFailed),
+                (owner, total, reason),
+            );
+            return Ok(0);
+        }
+
+        // Goals are reloaded one at a time so a RedirectOverflow policy
+        // feeding another goal in this inflow is not overwritten.
+        for item in contributions.iter() {
+            let mut goal =
+                Self::load_goal(&env, item.goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
+            goal.current_amount = goal
+                .current_amount
+                .checked_add(item.amount)
+                .ok_or(SavingsGoalError::Overflow)?;
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::FundsAdded),
+                (item.goal_id, owner.clone(), item.amount),
+            );
+            if Self::settle_completion(&env, &mut goal)? && !Self::accepts_deposits(&goal) {
+                Self::deactivate_goal_schedules(&env, &owner, item.goal_id);
+            }
+            Self::save_goal(&env, &goal);
+        }
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InflowApplied),
+            (owner, amount, total),
+        );
+        Ok(total)
+    }
+
+    fn remove_inflow_rule(env: &Env, owner: &Address, goal_id: u32) {
+        let key = DataKey::InflowRule(goal_id);
+        if !env.storage().persistent().has(&key) {
+            return;
+        }
+        env.storage().persistent().remove(&key);
+        let owner_key = DataKey::OwnerInflowGoals(owner.clone());
+        let mut ids = Self::load_id_index(env, &owner_key, None);
+        if let Some(i) = ids.first_index_of(goal_id) {
+            ids.remove(i);
+        }
+        Self::save_id_index(env, &owner_key, &ids);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::InflowRuleCleared),
+            (goal_id, owner.clone()),
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Beneficiaries
+    // -----------------------------------------------------------------------
+
+    /// Names a beneficiary who may claim the goal, or its funds, once the
+    /// owner has made no authorized call for `inactivity_period` seconds.
+    ///
+    /// Any authorized call by the owner, or an explicit `heartbeat`, resets
+    /// the inactivity timer. Replaces any previous beneficiary.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `goal_id` - ID of the goal
+    /// * `beneficiary` - Address allowed to claim the goal
+    /// * `inactivity_period` - Seconds of owner inactivity before a claim is allowed
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `InvalidBeneficiary` - If the beneficiary is the owner or the period
+    ///   is shorter than `MIN_INACTIVITY_PERIOD`
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::BeneficiarySet` with `(goal_id, beneficiary, inactivity_period)`.
+    pub fn set_goal_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        beneficiary: Address,
+        inactivity_period: u64,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if beneficiary == caller || inactivity_period < MIN_INACTIVITY_PERIOD {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalError::InvalidBeneficiary);
+        }
+
+        goal.beneficiary = Some(beneficiary.clone());
+        goal.inactivity_period = inactivity_period;
+        Self::save_goal(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("benefic"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::BeneficiarySet),
+            (goal_id, beneficiary, inactivity_period),
+        );
+        Ok(true)
+    }
+
+    /// Removes the goal's beneficiary.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::BeneficiaryCleared` when a beneficiary was set.
+    pub fn clear_goal_beneficiary(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("benefic"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        if goal.beneficiary.take().is_some() {
+            goal.inactivity_period = 0;
+            Self::save_goal(&env, &goal);
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::BeneficiaryCleared),
+                (goal_id, caller.clone()),
+            );
+        }
+        Self::append_audit(&env, symbol_short!("benefic"), &caller, true);
+        Ok(true)
+    }
+
+    /// Resets the caller's inactivity timer without changing anything else.
+    ///
+    /// # Returns
+    /// The recorded activity timestamp.
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::Heartbeat` with `(caller, timestamp)`.
+    pub fn heartbeat(env: Env, caller: Address) -> u64 {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let now = env.ledger().timestamp();
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::Heartbeat),
+            (caller, now),
+        );
+        now
+    }
+
+    /// Returns the timestamp of the owner's last authorized call, if any.
+    pub fn get_last_activity(env: Env, owner: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LastActivity(owner))
+    }
+
+    /// Returns true when the goal's beneficiary may claim it now.
+    pub fn is_goal_claimable(env: Env, goal_id: u32) -> bool {
+        Self::load_goal(&env, goal_id).is_some_and(|goal| Self::claim_open(&env, &goal))
+    }
+
+    /// Transfers ownership of the goal to its beneficiary after the owner's
+    /// inactivity period has elapsed.
+    ///
+    /// # Arguments
+    /// * `caller` - The goal's beneficiary (must authorize)
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal's beneficiary
+    /// * `NotClaimable` - If the owner has been active within the period
+    ///
+    /// # Notes
+    /// - Schedules funding the goal are deactivated: they pull from the
+    ///   previous owner.
+    /// - `RedirectOverflow` and `AutoRelease` policies point at the previous
+    ///   owner's goals or payout address, so they are reset to `KeepOpen`.
+    /// - The beneficiary is cleared; the new owner may name their own.
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::GoalClaimed` with `(goal_id, previous_owner, new_owner)`.
+    pub fn claim_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CLAIM)?;
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_claimable_goal(&env, &caller, goal_id)?;
+        let previous_owner = goal.owner.clone();
+
+        Self::remove_owner_goal_id(&env, &previous_owner, goal_id);
+        Self::unindex_goal_tags(&env, &goal);
+        Self::deactivate_goal_schedules(&env, &previous_owner, goal_id);
+        Self::remove_inflow_rule(&env, &previous_owner, goal_id);
+
+        goal.owner = caller.clone();
+        goal.beneficiary = None;
+        goal.inactivity_period = 0;
+        if matches!(
+            goal.completion_policy,
+            CompletionPolicy::RedirectOverflow(_) | CompletionPolicy::AutoRelease(_)
+        ) {
+            goal.completion_policy = CompletionPolicy::KeepOpen;
+        }
+        Self::save_goal(&env, &goal);
+        Self::insert_owner_goal_id(&env, &caller, goal_id);
+        Self::index_goal_tags(&env, &goal);
+
+        Self::append_audit(&env, symbol_short!("claim"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalClaimed),
+            (goal_id, previous_owner, caller),
+        );
+        Ok(true)
+    }
+
+    /// Pays the goal's balance out to its beneficiary after the owner's
+    /// inactivity period has elapsed. The goal stays with its owner.
+    ///
+    /// The owner's manual lock does not apply, but an unexpired time-lock
+    /// does.
+    ///
+    /// # Arguments
+    /// * `caller` - The goal's beneficiary (must authorize)
+    /// * `goal_id` - ID of the goal
+    ///
+    /// # Returns
+    /// `Ok(amount)` - The amount paid out
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal's beneficiary
+    /// * `NotClaimable` - If the owner has been active within the period
+    /// * `GoalLocked` - If the goal's time-lock has not expired
+    /// * `InsufficientBalance` - If the goal is empty
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::GoalFundsClaimed` with `(goal_id, beneficiary, amount)`.
+    pub fn claim_goal_funds(env: Env, caller: Address, goal_id: u32) -> Result<i128, SavingsGoalError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::CLAIM)?;
+        Self::record_activity(&env, &caller);
+        Self::extend_instance_ttl(&env);
+
+        let mut goal = Self::load_claimable_goal(&env, &caller, goal_id)?;
+        if goal
+            .unlock_date
+            .is_some_and(|unlock_date| env.ledger().timestamp() < unlock_date)
+        {
+            Self::append_audit(&env, symbol_short!("claim"), &caller, false);
+            return Err(SavingsGoalError::GoalLocked);
+        }
+        let amount = goal.current_amount;
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("claim"), &caller, false);
+            return Err(SavingsGoalError::InsufficientBalance);
+        }
+
+        goal.current_amount = 0;
+        Self::save_goal(&env, &goal);
+        if let Some(token) = Self::savings_token(&env) {
+            TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &caller,
+                &amount,
+            );
+        }
+
+        Self::append_audit(&env, symbol_short!("claim"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalFundsClaimed),
+            (goal_id, caller, amount),
+        );
+        Ok(amount)
+    }
+
+    /// Loads a goal `caller` may claim as its beneficiary right now.
+    fn load_claimable_goal(
+        env: &Env,
+        caller: &Address,
+        goal_id: u32,
+    ) -> Result<SavingsGoal, SavingsGoalError> {
+        let goal = match Self::load_goal(env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(env, symbol_short!("claim"), caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+        if goal.beneficiary.as_ref() != Some(caller) {
+            Self::append_audit(env, symbol_short!("claim"), caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if !Self::claim_open(env, &goal) {
+            Self::append_audit(env, symbol_short!("claim"), caller, false);
+            return Err(SavingsGoalError::NotClaimable);
+        }
+        Ok(goal)
+    }
+
+    /// Whether the goal has a beneficiary and its owner has been inactive for
+    /// the full period.
+    fn claim_open(env: &Env, goal: &SavingsGoal) -> bool {
+        if goal.beneficiary.is_none() {
+            return false;
+        }
+        let last_activity: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastActivity(goal.owner.clone()));
+        last_activity.is_some_and(|last| {
+            env.ledger().timestamp() >= last.saturating_add(goal.inactivity_period)
+        })
+    }
+
+    /// Records an authorized call by `owner`, resetting the inactivity timer
+    /// on their goals' beneficiaries.
+    fn record_activity(env: &Env, owner: &Address) {
+        let key = DataKey::LastActivity(owner.clone());
+        env.storage()
+            .persistent()
+            .set(&key, &env.ledger().timestamp());
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // Archival
+    // -----------------------------------------------------------------------
+
+    /// Moves a completed or emptied goal out of the active set into its own
+    /// persistent entry.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the goal owner (must authorize)
+    /// * `goal_id` - ID of the goal to archive
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If goal_id does not exist
+    /// * `Unauthorized` - If caller is not the goal owner
+    /// * `GoalNotArchivable` - If the goal is neither completed nor empty
+    ///
+    /// # Notes
+    /// - Schedules funding the goal are deactivated.
+    /// - The goal leaves the owner's `get_goals` index, so a cursor pointing at
+    ///   it is no longer valid.
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::GoalArchived`.
+    pub fn archive_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let goal = match Self::load_goal(&env, goal_id) {
+            Some(g) => g,
+            None => {
+                Self::append_audit(&env, symbol_short!("archive"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+        if goal.owner != caller {
+            Self::append_audit(&env, symbol_short!("archive"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if goal.completed_at.is_none() && goal.current_amount != 0 {
+            Self::append_audit(&env, symbol_short!("archive"), &caller, false);
+            return Err(SavingsGoalError::GoalNotArchivable);
+        }
+
+        let archived = ArchivedGoal {
+            id: goal.id,
+            owner: goal.owner.clone(),
+            name: goal.name.clone(),
+            target_amount: goal.target_amount,
+            current_amount: goal.current_amount,
+            target_date: goal.target_date,
+            completed_at: goal.completed_at,
+            archived_at: env.ledger().timestamp(),
+            tags: goal.tags.clone(),
+            completion_policy: goal.completion_policy.clone(),
+            beneficiary: goal.beneficiary.clone(),
+            inactivity_period: goal.inactivity_period,
+        };
+        Self::store_archived_goal(&env, &archived);
+
+        let owner_key = DataKey::OwnerArchivedGoals(caller.clone());
+        let mut archived_ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&owner_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        archived_ids.push_back(goal_id);
+        env.storage().persistent().set(&owner_key, &archived_ids);
+        env.storage()
+            .persistent()
+            .extend_ttl(&owner_key, ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+
+        Self::delete_goal(&env, goal_id);
+        Self::remove_owner_goal_id(&env, &caller, goal_id);
+        Self::unindex_goal_tags(&env, &goal);
+        Self::deactivate_goal_schedules(&env, &caller, goal_id);
+        Self::remove_inflow_rule(&env, &caller, goal_id);
+        Self::adjust_storage_stats(&env, -1, 1, goal.current_amount);
+
+        Self::append_audit(&env, symbol_short!("archive"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalArchived),
+            (goal_id, caller),
+        );
+        Ok(true)
+    }
+
+    /// Moves an archived goal back into the active set.
+    ///
+    /// The goal is restored locked, without a time-lock, and re-enters the
+    /// owner's `get_goals` index at its creation-order position. Schedules
+    /// deactivated on archival stay inactive.
+    ///
+    /// # Errors
+    /// * `GoalNotFound` - If no archived goal exists for goal_id
+    /// * `Unauthorized` - If caller is not the goal owner
+    ///
+    /// # Events
+    /// - Emits `SavingsEvent::GoalRestored`.
+    pub fn restore_goal(env: Env, caller: Address, goal_id: u32) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::RESTORE)?;
+        Self::extend_instance_ttl(&env);
+
+        let archived: ArchivedGoal = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::ArchivedGoal(goal_id))
+        {
+            Some(a) => a,
+            None => {
+                Self::append_audit(&env, symbol_short!("restore"), &caller, false);
+                return Err(SavingsGoalError::GoalNotFound);
+            }
+        };
+        if archived.owner != caller {
+            Self::append_audit(&env, symbol_short!("restore"), &caller, false);
+            return Err(SavingsGoalError::Unauthorized);
+        }
+
+        let goal = SavingsGoal {
+            id: archived.id,
+            owner: archived.owner.clone(),
+            name: archived.name.clone(),
+            target_amount: archived.target_amount,
+            current_amount: archived.current_amount,
+            target_date: archived.target_date,
+            locked: true,
+            unlock_date: None,
+            tags: Self::dedup_tags(&env, &archived.tags),
+            completed_at: archived.completed_at,
+            completion_policy: archived.completion_policy.clone(),
+            beneficiary: archived.beneficiary.clone(),
+            inactivity_period: archived.inactivity_period,
+        };
+        Self::save_goal(&env, &goal);
+        Self::insert_owner_goal_id(&env, &caller, goal_id);
+        Self::index_goal_tags(&env, &goal);
+
+        Self::remove_archived_goal(&env, &caller, goal_id);
+        Self::adjust_storage_stats(&env, 1, -1, -archived.current_amount);
+
+        Self::append_audit(&env, symbol_short!("restore"), &caller, true);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::GoalRestored),
+            (goal_id, caller),
+        );
+        Ok(true)
+    }
+
+    /// Permanently deletes the caller's archived goals archived before
+    /// `before_timestamp`.
+    ///
+    /// # Returns
+    /// Number of archived goals deleted.
+    pub fn cleanup_archived_goals(
+        env: Env,
+        caller: Address,
+        before_timestamp: u64,
+    ) -> Result<u32, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+        Self::require_not_paused(&env, pause_functions::ARCHIVE)?;
+        Self::extend_instance_ttl(&env);
+
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerArchivedGoals(caller.clone()))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut deleted = 0u32;
+        let mut deleted_amount = 0i128;
+        for id in ids.iter() {
+            let archived: Option<ArchivedGoal> =
+                env.storage().persistent().get(&DataKey::ArchivedGoal(id));
+            if let Some(goal) = archived {
+                // Deleting a token-backed balance would strand the tokens.
+                let strands_funds = goal.current_amount > 0 && Self::savings_token(&env).is_some();
+                if goal.archived_at < before_timestamp && !strands_funds {
+                    Self::remove_archived_goal(&env, &caller, id);
+                    deleted += 1;
+                    deleted_amount = deleted_amount.saturating_add(goal.current_amount);
+                }
+            }
+        }
+        Self::adjust_storage_stats(&env, 0, -(deleted as i32), -deleted_amount);
+
+        Self::append_audit(&env, symbol_short!("cleanup"), &caller, true);
+        RemitwiseEvents::emit_batch(&env, EventCategory::System, symbol_short!("cleaned"), deleted);
+        Ok(deleted)
+    }
+
+    /// Returns a page of archived goals for `owner`, in archival order.
+    ///
+    /// # Arguments
+    /// * `owner`  - whose archived goals to return
+    /// * `cursor` - start after this goal ID (pass 0 for the first page)
+    /// * `limit`  - max items per page (0 -> DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
+    ///
+    /// # Errors
+    /// * `InvalidCursor` - If `cursor` is non-zero and not an archived goal of `owner`
+    pub fn get_archived_goals(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<ArchivedGoalPage, SavingsGoalError> {
+        let limit = Self::clamp_limit(limit);
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OwnerArchivedGoals(owner))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut start_index: u32 = 0;
+        if cursor != 0 {
+            match ids.first_index_of(cursor) {
+                Some(i) => start_index = i + 1,
+                None => return Err(SavingsGoalError::InvalidCursor),
+            }
+        }
+        let end_index = (start_index + limit).min(ids.len());
+
+        let mut items = Vec::new(&env);
+        for i in start_index..end_index {
+            if let Some(id) = ids.get(i) {
+                if let Some(goal) = env.storage().persistent().get(&DataKey::ArchivedGoal(id)) {
+                    items.push_back(goal);
+                }
+            }
+        }
+
+        let next_cursor = if end_index < ids.len() {
+            ids.get(end_index - 1).unwrap_or(0)
+        } else {
+            0
+        };
+        let count = items.len();
+        Ok(ArchivedGoalPage {
+            items,
+            next_cursor,
+            count,
+        })
+    }
+
+    pub fn get_archived_goal(env: Env, goal_id: u32) -> Option<ArchivedGoal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArchivedGoal(goal_id))
+    }
+
+    pub fn get_storage_stats(env: Env) -> StorageStats {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("STOR_STAT"))
+            .unwrap_or(StorageStats {
+                active_goals: 0,
+                archived_goals: 0,
+                total_archived_amount: 0,
+                last_updated: 0,
+            })
+    }
+
+    fn store_archived_goal(env: &Env, goal: &ArchivedGoal) {
+        let key = DataKey::ArchivedGoal(goal.id);
+        env.storage().persistent().set(&key, goal);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    }
+
+    fn remove_archived_goal(env: &Env, owner: &Address, goal_id: u32) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ArchivedGoal(goal_id));
+        let owner_key = DataKey::OwnerArchivedGoals(owner.clone());
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&owner_key)
+            .unwrap_or_else(|| Vec::new(env));
+        if let Some(i) = ids.first_index_of(goal_id) {
+            ids.remove(i);
+        }
+        if ids.is_empty() {
+            env.storage().persistent().remove(&owner_key);
+        } else {
+            env.storage().persistent().set(&owner_key, &ids);
+        }
+    }
+
+    /// Applies deltas to the stored `StorageStats` counters.
+    fn adjust_storage_stats(env: &Env, active_delta: i32, archived_delta: i32, amount_delta: i128) {
+        let mut stats = Self::get_storage_stats(env.clone());
+        stats.active_goals = stats.active_goals.saturating_add_signed(active_delta);
+        stats.archived_goals = stats.archived_goals.saturating_add_signed(archived_delta);
+        stats.total_archived_amount = stats.total_archived_amount.saturating_add(amount_delta);
+        stats.last_updated = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("STOR_STAT"), &stats);
+    }
+
+    // -----------------------------------------------------------------------
+    // Snapshot, audit, schedule
+    // -----------------------------------------------------------------------
+
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        let nonces: Option<Map<Address, u64>> =
+            env.storage().instance().get(&symbol_short!("NONCES"));
+        nonces
+            .as_ref()
+            .and_then(|m: &Map<Address, u64>| m.get(address))
+            .unwrap_or(0)
+    }
+
+    /// Exports the caller's active goals, read through their owner index.
     pub fn export_snapshot(env: Env, caller: Address) -> GoalsExportSnapshot {
         caller.require_auth();
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+        Self::record_activity(&env, &caller);
         let next_id = env
             .storage()
             .instance()
             .get(&symbol_short!("NEXT_ID"))
             .unwrap_or(0u32);
         let mut list = Vec::new(&env);
-        for i in 1..=next_id {
-            if let Some(g) = goals.get(i) {
+        for goal_id in Self::owner_goal_ids(&env, &caller).iter() {
+            if let Some(g) = Self::load_goal(&env, goal_id) {
                 list.push_back(g);
             }
         }
@@ -1283,6 +3647,21 @@ impl SavingsGoalContract {
         }
     }
 
+    /// Replaces the caller's active goals with the goals in `snapshot`.
+    ///
+    /// Every goal in the snapshot must belong to the caller and use an ID
+    /// that is already allocated and either free or held by one of the
+    /// caller's active goals. Other owners' goals are never touched, and the
+    /// cost depends only on the caller's goals. Schedules and inflow rules of
+    /// goals the snapshot drops are deactivated and cleared.
+    ///
+    /// # Errors
+    /// * `InvalidNonce` - If `nonce` is not the caller's current nonce
+    /// * `UnsupportedVersion` / `ChecksumMismatch` - If the snapshot is invalid
+    /// * `Unauthorized` - If a goal belongs to someone else, or its ID is held
+    ///   by another owner's goal
+    /// * `GoalNotFound` - If a goal ID was never allocated
+    /// * `DuplicateGoal` - If an ID appears twice or belongs to an archived goal
     pub fn import_snapshot(
         env: Env,
         caller: Address,
@@ -1290,7 +3669,7 @@ impl SavingsGoalContract {
         snapshot: GoalsExportSnapshot,
     ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
-        Self::require_nonce(&env, &caller, nonce);
+        Self::require_nonce(&env, &caller, nonce)?;
 
         // Accept any schema_version within the supported range for backward/forward compat.
         if snapshot.schema_version < MIN_SUPPORTED_SCHEMA_VERSION
@@ -1309,28 +3688,76 @@ impl SavingsGoalContract {
             return Err(SavingsGoalError::ChecksumMismatch);
         }
 
-        Self::extend_instance_ttl(&env);
-        let mut goals: Map<u32, SavingsGoal> = Map::new(&env);
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = Map::new(&env);
+        let next_id: u32 = env
+            .storage()
+            .instance()
+            .get(&Self::STORAGE_NEXT_ID)
+            .unwrap_or(0);
+        let mut imported_ids: Vec<u32> = Vec::new(&env);
         for g in snapshot.goals.iter() {
-            goals.set(g.id, g.clone());
-            let mut ids = owner_goal_ids
-                .get(g.owner.clone())
-                .unwrap_or_else(|| Vec::new(&env));
-            ids.push_back(g.id);
-            owner_goal_ids.set(g.owner.clone(), ids);
+            let checked = if g.owner != caller {
+                Err(SavingsGoalError::Unauthorized)
+            } else if g.id == 0 || g.id > next_id {
+                Err(SavingsGoalError::GoalNotFound)
+            } else if imported_ids.binary_search(g.id).is_ok()
+                || env.storage().persistent().has(&DataKey::ArchivedGoal(g.id))
+            {
+                Err(SavingsGoalError::DuplicateGoal)
+            } else {
+                match Self::load_goal(&env, g.id) {
+                    Some(existing) if existing.owner != caller => {
+                        Err(SavingsGoalError::Unauthorized)
+                    }
+                    _ => Ok(()),
+                }
+            };
+            if let Err(e) = checked {
+                Self::append_audit(&env, symbol_short!("import"), &caller, false);
+                return Err(e);
+            }
+            Self::insert_sorted(&mut imported_ids, g.id);
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NEXT_ID"), &snapshot.next_id);
-        env.storage()
+
+        Self::extend_instance_ttl(&env);
+        // Drop the caller's current goals, releasing whatever pointed at the
+        // ones the snapshot does not bring back.
+        let mut dropped = 0u32;
+        for goal_id in Self::owner_goal_ids(&env, &caller).iter() {
+            if let Some(g) = Self::load_goal(&env, goal_id) {
+                Self::unindex_goal_tags(&env, &g);
+                if imported_ids.binary_search(goal_id).is_err() {
+                    Self::remove_inflow_rule(&env, &caller, goal_id);
+                }
+                // Legacy goals were never counted in the storage stats.
+                if env.storage().persistent().has(&DataKey::Goal(goal_id)) {
+                    dropped += 1;
+                }
+                Self::delete_goal(&env, goal_id);
+            }
+        }
+        Self::deactivate_schedules_matching(&env, &caller, |goal_id| {
+            imported_ids.binary_search(goal_id).is_err()
+        });
+        if let Some(mut legacy) = env
+            .storage()
             .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+            .get::<_, Map<Address, Vec<u32>>>(&Self::STORAGE_OWNER_GOAL_IDS)
+        {
+            legacy.remove(caller.clone());
+            env.storage()
+                .instance()
+                .set(&Self::STORAGE_OWNER_GOAL_IDS, &legacy);
+        }
 
-        Self::increment_nonce(&env, &caller);
+        for mut g in snapshot.goals.iter() {
+            g.tags = Self::dedup_tags(&env, &g.tags);
+            Self::save_goal(&env, &g);
+            Self::index_goal_tags(&env, &g);
+        }
+        Self::save_id_index(&env, &DataKey::OwnerGoals(caller.clone()), &imported_ids);
+        Self::adjust_storage_stats(&env, imported_ids.len() as i32 - dropped as i32, 0, 0);
+
+        Self::increment_nonce(&env, &caller)?;
         Self::append_audit(&env, symbol_short!("import"), &caller, true);
         Ok(true)
     }
@@ -1353,19 +3780,17 @@ impl SavingsGoalContract {
         out
     }
 
-    fn require_nonce(env: &Env, address: &Address, expected: u64) {
+    fn require_nonce(env: &Env, address: &Address, expected: u64) -> Result<(), SavingsGoalError> {
         let current = Self::get_nonce(env.clone(), address.clone());
         if expected != current {
-            panic!("Invalid nonce: expected {}, got {}", current, expected);
+            return Err(SavingsGoalError::InvalidNonce);
         }
+        Ok(())
     }
 
-    fn increment_nonce(env: &Env, address: &Address) {
+    fn increment_nonce(env: &Env, address: &Address) -> Result<(), SavingsGoalError> {
         let current = Self::get_nonce(env.clone(), address.clone());
-        let next = match current.checked_add(1) {
-            Some(v) => v,
-            None => panic!("nonce overflow"),
-        };
+        let next = current.checked_add(1).ok_or(SavingsGoalError::Overflow)?;
         let mut nonces: Map<Address, u64> = env
             .storage()
             .instance()
@@ -1375,6 +3800,7 @@ impl SavingsGoalContract {
         env.storage()
             .instance()
             .set(&symbol_short!("NONCES"), &nonces);
+        Ok(())
     }
 
     fn compute_goals_checksum(version: u32, next_id: u32, goals: &Vec<SavingsGoal>) -> u64 {
@@ -1415,25 +3841,57 @@ impl SavingsGoalContract {
         env.storage().instance().set(&symbol_short!("AUDIT"), &log);
     }
 
-    #[allow(dead_code)]
-    fn get_owner_goal_ids_map(env: &Env) -> Option<Map<Address, Vec<u32>>> {
-        env.storage().instance().get(&Self::STORAGE_OWNER_GOAL_IDS)
+    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        // New IDs are always the highest, so insertion is an append.
+        Self::insert_owner_goal_id(env, owner, goal_id);
     }
 
-    fn append_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
-        let mut owner_goal_ids: Map<Address, Vec<u32>> = env
+    fn remove_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let mut ids = Self::owner_goal_ids(env, owner);
+        if let Some(i) = ids.first_index_of(goal_id) {
+            ids.remove(i);
+        }
+        Self::save_id_index(env, &DataKey::OwnerGoals(owner.clone()), &ids);
+
+        // Drop the ID from the legacy index too, or the merge in
+        // `owner_goal_ids` would bring it back.
+        if let Some(mut legacy) = env
             .storage()
             .instance()
-            .get(&Self::STORAGE_OWNER_GOAL_IDS)
-            .unwrap_or_else(|| Map::new(env));
-        let mut ids = owner_goal_ids
-            .get(owner.clone())
-            .unwrap_or_else(|| Vec::new(env));
-        ids.push_back(goal_id);
-        owner_goal_ids.set(owner.clone(), ids);
-        env.storage()
+            .get::<_, Map<Address, Vec<u32>>>(&Self::STORAGE_OWNER_GOAL_IDS)
+        {
+            if let Some(mut legacy_ids) = legacy.get(owner.clone()) {
+                if let Some(i) = legacy_ids.first_index_of(goal_id) {
+                    legacy_ids.remove(i);
+                    legacy.set(owner.clone(), legacy_ids);
+                    env.storage()
+                        .instance()
+                        .set(&Self::STORAGE_OWNER_GOAL_IDS, &legacy);
+                }
+            }
+        }
+    }
+
+    /// Inserts `goal_id` into the owner index keeping ascending creation order.
+    fn insert_owner_goal_id(env: &Env, owner: &Address, goal_id: u32) {
+        let mut ids = Self::owner_goal_ids(env, owner);
+        Self::insert_sorted(&mut ids, goal_id);
+        Self::save_id_index(env, &DataKey::OwnerGoals(owner.clone()), &ids);
+    }
+
+    /// Deletes a goal's persistent entry and any legacy copy of it.
+    fn delete_goal(env: &Env, goal_id: u32) {
+        env.storage().persistent().remove(&DataKey::Goal(goal_id));
+        if let Some(mut legacy) = env
+            .storage()
             .instance()
-            .set(&Self::STORAGE_OWNER_GOAL_IDS, &owner_goal_ids);
+            .get::<_, Map<u32, LegacySavingsGoal>>(&Self::STORAGE_GOALS)
+        {
+            if legacy.contains_key(goal_id) {
+                legacy.remove(goal_id);
+                env.storage().instance().set(&Self::STORAGE_GOALS, &legacy);
+            }
+        }
     }
 
     /// Extend the TTL of instance storage
@@ -1451,45 +3909,43 @@ impl SavingsGoalContract {
     /// * `goal_id` - ID of the goal
     /// * `unlock_date` - Unix timestamp when the goal becomes withdrawable
     ///
-    /// # Panics
-    /// - If caller is not the owner or goal not found.
-    pub fn set_time_lock(env: Env, caller: Address, goal_id: u32, unlock_date: u64) -> bool {
+    /// # Errors
+    /// - `GoalNotFound` / `Unauthorized` if goal not found or caller is not the owner.
+    /// - `InvalidDate` if `unlock_date` is not in the future.
+    pub fn set_time_lock(
+        env: Env,
+        caller: Address,
+        goal_id: u32,
+        unlock_date: u64,
+    ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
+        Self::record_activity(&env, &caller);
         Self::extend_instance_ttl(&env);
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut goal = match goals.get(goal_id) {
+        let mut goal = match Self::load_goal(&env, goal_id) {
             Some(g) => g,
             None => {
                 Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-                panic!("Goal not found");
+                return Err(SavingsGoalError::GoalNotFound);
             }
         };
 
         if goal.owner != caller {
             Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Only the goal owner can set time-lock");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
         let current_time = env.ledger().timestamp();
         if unlock_date <= current_time {
             Self::append_audit(&env, symbol_short!("timelock"), &caller, false);
-            panic!("Unlock date must be in the future");
+            return Err(SavingsGoalError::InvalidDate);
         }
 
         goal.unlock_date = Some(unlock_date);
-        goals.set(goal_id, goal);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+        Self::save_goal(&env, &goal);
 
         Self::append_audit(&env, symbol_short!("timelock"), &caller, true);
-        true
+        Ok(true)
     }
 
     /// Creates a recurring savings schedule.
@@ -1503,6 +3959,11 @@ impl SavingsGoalContract {
     ///
     /// # Returns
     /// - ID of the new schedule
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if `amount` is not positive
+    /// - `GoalNotFound` / `Unauthorized` if the goal is missing or not owned by `owner`
+    /// - `InvalidDate` if `next_due` is not in the future
     pub fn create_savings_schedule(
         env: Env,
         owner: Address,
@@ -1510,41 +3971,40 @@ impl SavingsGoalContract {
         amount: i128,
         next_due: u64,
         interval: u64,
-    ) -> u32 {
+    ) -> Result<u32, SavingsGoalError> {
         owner.require_auth();
+        Self::record_activity(&env, &owner);
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            return Err(SavingsGoalError::InvalidAmount);
         }
 
-        let goals: Map<u32, SavingsGoal> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let goal = match goals.get(goal_id) {
-            Some(g) => g,
-            None => panic!("Goal not found"),
-        };
+        let goal = Self::load_goal(&env, goal_id).ok_or(SavingsGoalError::GoalNotFound)?;
 
         if goal.owner != owner {
-            panic!("Only the goal owner can create schedules");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
         let current_time = env.ledger().timestamp();
         if next_due <= current_time {
-            panic!("Next due date must be in the future");
+            return Err(SavingsGoalError::InvalidDate);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        Ok(Self::insert_schedule(&env, &owner, goal_id, amount, next_due, interval))
+    }
 
+    /// Stores a new active schedule for `owner`, indexes it by due time and
+    /// emits `ScheduleCreated`. Inputs are validated by the caller.
+    fn insert_schedule(
+        env: &Env,
+        owner: &Address,
+        goal_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> u32 {
         let next_schedule_id = env
             .storage()
             .instance()
@@ -1561,22 +4021,26 @@ impl SavingsGoalContract {
             interval,
             recurring: interval > 0,
             active: true,
-            created_at: current_time,
+            created_at: env.ledger().timestamp(),
             last_executed: None,
             missed_count: 0,
+            retry_at: None,
+            consecutive_failures: 0,
+            paused: false,
         };
 
-        schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(env, &schedule);
+        Self::index_due_schedule(env, next_schedule_id, next_due);
+        let mut owner_ids = Self::owner_schedule_ids(env, owner);
+        Self::insert_sorted(&mut owner_ids, next_schedule_id);
+        Self::save_id_index(env, &DataKey::OwnerSchedules(owner.clone()), &owner_ids);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_SSCH"), &next_schedule_id);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleCreated),
-            (next_schedule_id, owner),
+            (next_schedule_id, owner.clone()),
         );
 
         next_schedule_id
@@ -1589,93 +4053,144 @@ impl SavingsGoalContract {
         amount: i128,
         next_due: u64,
         interval: u64,
-    ) -> bool {
+    ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
+        Self::record_activity(&env, &caller);
 
         if amount <= 0 {
-            panic!("Amount must be positive");
+            return Err(SavingsGoalError::InvalidAmount);
         }
 
         let current_time = env.ledger().timestamp();
         if next_due <= current_time {
-            panic!("Next due date must be in the future");
+            return Err(SavingsGoalError::InvalidDate);
         }
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        let mut schedule =
+            Self::load_schedule(&env, schedule_id).ok_or(SavingsGoalError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
-            panic!("Only the schedule owner can modify it");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
+        if schedule.active {
+            Self::unindex_due_schedule(&env, schedule_id, Self::due_at(&schedule));
+            Self::index_due_schedule(&env, schedule_id, next_due);
+        }
         schedule.amount = amount;
         schedule.next_due = next_due;
         schedule.interval = interval;
         schedule.recurring = interval > 0;
+        schedule.retry_at = None;
+        schedule.consecutive_failures = 0;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleModified),
             (schedule_id, caller),
         );
 
-        true
+        Ok(true)
     }
 
-    pub fn cancel_savings_schedule(env: Env, caller: Address, schedule_id: u32) -> bool {
+    pub fn cancel_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+    ) -> Result<bool, SavingsGoalError> {
         caller.require_auth();
+        Self::record_activity(&env, &caller);
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+        let mut schedule =
+            Self::load_schedule(&env, schedule_id).ok_or(SavingsGoalError::ScheduleNotFound)?;
 
         if schedule.owner != caller {
-            panic!("Only the schedule owner can cancel it");
+            return Err(SavingsGoalError::Unauthorized);
         }
 
+        if schedule.active {
+            Self::unindex_due_schedule(&env, schedule_id, Self::due_at(&schedule));
+        }
         schedule.active = false;
+        schedule.paused = false;
 
-        schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
+        Self::save_schedule(&env, &schedule);
 
         env.events().publish(
             (symbol_short!("savings"), SavingsEvent::ScheduleCancelled),
             (schedule_id, caller),
         );
 
-        true
+        Ok(true)
     }
 
-    /// Executes all active and due savings schedules.
+    /// Reactivates a schedule paused after repeated failed pulls.
     ///
-    /// # Drift Handling
-    /// - If execution is delayed, the schedule will "catch up" by skipping missed intervals
-    ///   and incrementing `missed_count`.
-    /// - `next_due` is set to the next future interval anchor.
+    /// # Arguments
+    /// * `caller` - Address of the schedule owner
+    /// * `schedule_id` - ID of the paused schedule
+    /// * `next_due` - Next execution time; must be in the future
     ///
     /// # Events
-    /// - Emits `SavingsEvent::ScheduleExecuted` for each successful execution.
-    /// - Emits `SavingsEvent::ScheduleMissed` for each interval missed.
-    /// Executes all active and due savings schedules.
+    /// - Emits `SavingsEvent::ScheduleResumed`.
+    pub fn resume_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        next_due: u64,
+    ) -> Result<bool, SavingsGoalError> {
+        caller.require_auth();
+        Self::record_activity(&env, &caller);
+
+        if next_due <= env.ledger().timestamp() {
+            return Err(SavingsGoalError::InvalidDate);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut schedule =
+            Self::load_schedule(&env, schedule_id).ok_or(SavingsGoalError::ScheduleNotFound)?;
+        if schedule.owner != caller {
+            return Err(SavingsGoalError::Unauthorized);
+        }
+        if !schedule.paused {
+            return Err(SavingsGoalError::ScheduleNotPaused);
+        }
+
+        schedule.active = true;
+        schedule.paused = false;
+        schedule.consecutive_failures = 0;
+        schedule.retry_at = None;
+        schedule.next_due = next_due;
+        Self::index_due_schedule(&env, schedule_id, next_due);
+        Self::save_schedule(&env, &schedule);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleResumed),
+            (schedule_id, caller),
+        );
+
+        Ok(true)
+    }
+
+    /// Executes up to `max_items` due savings schedules, earliest first.
+    ///
+    /// Only schedules in the due-time index are visited, so cost depends on
+    /// how many schedules are due, not on how many exist. The position reached
+    /// is kept in contract storage: when `has_more` is set, calling again
+    /// continues where this call stopped.
+    ///
+    /// Until `migrate_storage` has finished, due schedules still in the legacy
+    /// instance map are moved to per-entry storage and executed first.
+    ///
+    /// # Arguments
+    /// * `max_items` - most schedules to execute, skip or fail in this call
+    ///   (0 -> DEFAULT_PAGE_LIMIT, capped at MAX_PAGE_LIMIT)
     ///
     /// # Drift Handling
     /// - If execution is delayed, the schedule will "catch up" by skipping missed intervals
@@ -1685,102 +4200,320 @@ impl SavingsGoalContract {
     /// # Events
     /// - Emits `SavingsEvent::ScheduleExecuted` for each successful execution.
     /// - Emits `SavingsEvent::ScheduleMissed` for each interval missed.
-    pub fn execute_due_savings_schedules(env: Env) -> Vec<u32> {
+    /// - Emits `SavingsEvent::ScheduleFailed` for each schedule that failed.
+    pub fn execute_due_savings_schedules(env: Env, max_items: u32) -> ExecutionReport {
         Self::extend_instance_ttl(&env);
 
         let current_time = env.ledger().timestamp();
-        let mut executed = Vec::new(&env);
+        let mut report = ExecutionReport {
+            executed: Vec::new(&env),
+            skipped: Vec::new(&env),
+            failed: Vec::new(&env),
+            has_more: false,
+        };
+        let budget = Self::clamp_limit(max_items);
+        let (mut visited, mut stopped_early) =
+            Self::execute_legacy_schedules(&env, current_time, budget, &mut report);
 
-        let mut schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut head: u64 = match env.storage().instance().get(&symbol_short!("DUE_HEAD")) {
+            Some(h) => h,
+            None => {
+                report.has_more = stopped_early;
+                return report;
+            }
+        };
 
-        let mut goals: Map<u32, SavingsGoal> = env
-            .storage()
+        let current_bucket = current_time / SCHEDULE_BUCKET_SECONDS;
+        let mut scanned = 0u32;
+
+        while head <= current_bucket && scanned < MAX_DUE_BUCKET_SCAN {
+            let ids = Self::load_id_index(&env, &DataKey::DueSchedules(head), None);
+            for schedule_id in ids.iter() {
+                if visited >= budget {
+                    stopped_early = true;
+                    break;
+                }
+                match Self::load_schedule(&env, schedule_id) {
+                    Some(schedule) if schedule.active => {
+                        if Self::due_at(&schedule) > current_time {
+                            continue;
+                        }
+                        visited += 1;
+                        Self::execute_schedule(&env, schedule, current_time, &mut report);
+                    }
+                    // Stale entry; drop it.
+                    _ => Self::remove_from_due_bucket(&env, head, schedule_id),
+                }
+            }
+            if stopped_early || head == current_bucket {
+                break;
+            }
+            // Every schedule in a past bucket was due and has left it.
+            head += 1;
+            scanned += 1;
+        }
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("GOALS"))
-            .unwrap_or_else(|| Map::new(&env));
+            .set(&symbol_short!("DUE_HEAD"), &head);
+        report.has_more = stopped_early || head < current_bucket;
+        report
+    }
 
-        for (schedule_id, mut schedule) in schedules.iter() {
-            if !schedule.active || schedule.next_due > current_time {
+    /// Executes due schedules that are still in the legacy instance map.
+    ///
+    /// Only migrated schedules are in the due-time index, so these would
+    /// otherwise never run before `migrate_storage` reached them. Each one is
+    /// moved to per-entry storage before it runs. Returns how many schedules
+    /// were visited and whether the budget ran out first.
+    fn execute_legacy_schedules(
+        env: &Env,
+        current_time: u64,
+        budget: u32,
+        report: &mut ExecutionReport,
+    ) -> (u32, bool) {
+        let mut legacy: Map<u32, LegacySavingsSchedule> =
+            match env.storage().instance().get(&Self::STORAGE_SCHEDULES) {
+                Some(m) => m,
+                None => return (0, false),
+            };
+        let mut visited = 0u32;
+        let mut stopped_early = false;
+        for schedule_id in legacy.keys().iter() {
+            let schedule = match Self::load_schedule(env, schedule_id) {
+                Some(s) => s,
+                None => continue,
+            };
+            if !schedule.active || Self::due_at(&schedule) > current_time {
                 continue;
             }
+            if visited >= budget {
+                stopped_early = true;
+                break;
+            }
+            Self::adopt_legacy_schedule(env, &schedule);
+            legacy.remove(schedule_id);
+            visited += 1;
+            Self::execute_schedule(env, schedule, current_time, report);
+        }
+        if legacy.is_empty() {
+            env.storage().instance().remove(&Self::STORAGE_SCHEDULES);
+        } else {
+            env.storage()
+                .instance()
+                .set(&Self::STORAGE_SCHEDULES, &legacy);
+        }
+        (visited, stopped_early)
+    }
 
-            if let Some(mut goal) = goals.get(schedule.goal_id) {
-                goal.current_amount = match goal.current_amount.checked_add(schedule.amount) {
-                    Some(v) => v,
-                    None => panic!("overflow"),
-                };
+    /// Stores a schedule taken out of the legacy map in its own entry and
+    /// adds it to its owner's schedule index.
+    fn adopt_legacy_schedule(env: &Env, schedule: &SavingsSchedule) {
+        Self::save_schedule(env, schedule);
+        let key = DataKey::OwnerSchedules(schedule.owner.clone());
+        let mut ids = Self::load_id_index(env, &key, None);
+        Self::insert_sorted(&mut ids, schedule.id);
+        Self::save_id_index(env, &key, &ids);
+    }
 
-                let is_completed = goal.current_amount >= goal.target_amount;
-                goals.set(schedule.goal_id, goal.clone());
+    /// Executes one due schedule and records the outcome in `report`.
+    fn execute_schedule(
+        env: &Env,
+        mut schedule: SavingsSchedule,
+        current_time: u64,
+        report: &mut ExecutionReport,
+    ) {
+        let schedule_id = schedule.id;
+        Self::unindex_due_schedule(env, schedule_id, Self::due_at(&schedule));
 
-                env.events().publish(
-                    (symbol_short!("savings"), SavingsEvent::FundsAdded),
-                    (schedule.goal_id, goal.owner.clone(), schedule.amount),
-                );
+        let mut goal = match Self::load_goal(env, schedule.goal_id) {
+            Some(g) => g,
+            None => {
+                Self::fail_schedule(env, schedule, ScheduleFailureReason::GoalNotFound, report);
+                return;
+            }
+        };
+        // Completed goals whose policy closes them stop their schedules
+        // instead of being funded further.
+        if !Self::accepts_deposits(&goal) {
+            schedule.active = false;
+            Self::save_schedule(env, &schedule);
+            report.skipped.push_back(schedule_id);
+            return;
+        }
 
-                if is_completed {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::GoalCompleted),
-                        (schedule.goal_id, goal.owner),
-                    );
-                }
+        let new_total = match goal.current_amount.checked_add(schedule.amount) {
+            Some(v) => v,
+            None => {
+                Self::fail_schedule(env, schedule, ScheduleFailureReason::Overflow, report);
+                return;
             }
+        };
+        goal.current_amount = new_total;
+        // Every check that could fail after the pull runs first: a failed
+        // schedule does not save the goal, so pulled tokens would be stranded.
+        if Self::check_completion(env, &goal).is_err() {
+            Self::fail_schedule(env, schedule, ScheduleFailureReason::Overflow, report);
+            return;
+        }
+        if let Err(reason) = Self::pull_schedule_funds(env, &schedule) {
+            Self::retry_schedule(env, schedule, reason, current_time, report);
+            return;
+        }
 
-            schedule.last_executed = Some(current_time);
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::FundsAdded),
+            (schedule.goal_id, goal.owner.clone(), schedule.amount),
+        );
 
-            if schedule.recurring && schedule.interval > 0 {
-                let mut missed = 0u32;
-                let mut next = schedule.next_due + schedule.interval;
-                while next <= current_time {
-                    missed += 1;
-                    next += schedule.interval;
-                }
-                schedule.missed_count += missed;
-                schedule.next_due = next;
+        if Self::settle_completion(env, &mut goal).is_err() {
+            Self::fail_schedule(env, schedule, ScheduleFailureReason::Overflow, report);
+            return;
+        }
+        if !Self::accepts_deposits(&goal) {
+            schedule.active = false;
+        }
+        Self::save_goal(env, &goal);
+
+        schedule.last_executed = Some(current_time);
+        schedule.retry_at = None;
+        schedule.consecutive_failures = 0;
+
+        if schedule.recurring && schedule.interval > 0 {
+            let mut missed = 0u32;
+            let mut next = schedule.next_due + schedule.interval;
+            while next <= current_time {
+                missed += 1;
+                next += schedule.interval;
+            }
+            schedule.missed_count += missed;
+            schedule.next_due = next;
 
-                if missed > 0 {
-                    env.events().publish(
-                        (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
-                        (schedule_id, missed),
-                    );
-                }
-            } else {
-                schedule.active = false;
+            if missed > 0 {
+                env.events().publish(
+                    (symbol_short!("savings"), SavingsEvent::ScheduleMissed),
+                    (schedule_id, missed),
+                );
             }
+        } else {
+            schedule.active = false;
+        }
+
+        if schedule.active {
+            Self::index_due_schedule(env, schedule_id, schedule.next_due);
+        }
+        Self::save_schedule(env, &schedule);
+        report.executed.push_back(schedule_id);
+
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
+            schedule_id,
+        );
+    }
+
+    fn fail_schedule(
+        env: &Env,
+        mut schedule: SavingsSchedule,
+        reason: ScheduleFailureReason,
+        report: &mut ExecutionReport,
+    ) {
+        schedule.active = false;
+        Self::save_schedule(env, &schedule);
+        report.failed.push_back(ScheduleFailure {
+            schedule_id: schedule.id,
+            reason,
+        });
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleFailed),
+            (schedule.id, reason),
+        );
+    }
 
-            schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+    /// Records a failed pull: retries after an exponential backoff, or
+    /// pauses the schedule once `MAX_SCHEDULE_FAILURES` is reached.
+    fn retry_schedule(
+        env: &Env,
+        mut schedule: SavingsSchedule,
+        reason: ScheduleFailureReason,
+        current_time: u64,
+        report: &mut ExecutionReport,
+    ) {
+        let schedule_id = schedule.id;
+        schedule.consecutive_failures = schedule.consecutive_failures.saturating_add(1);
+        report.failed.push_back(ScheduleFailure {
+            schedule_id,
+            reason,
+        });
+        env.events().publish(
+            (symbol_short!("savings"), SavingsEvent::ScheduleFailed),
+            (schedule_id, reason),
+        );
 
+        if schedule.consecutive_failures >= MAX_SCHEDULE_FAILURES {
+            schedule.active = false;
+            schedule.paused = true;
+            schedule.retry_at = None;
+            env.events().publish(
+                (symbol_short!("savings"), SavingsEvent::SchedulePaused),
+                (schedule_id, schedule.consecutive_failures),
+            );
+        } else {
+            let backoff = SCHEDULE_RETRY_BASE_SECONDS
+                .saturating_mul(1u64 << (schedule.consecutive_failures - 1).min(32));
+            let retry_at = current_time.saturating_add(backoff);
+            schedule.retry_at = Some(retry_at);
+            Self::index_due_schedule(env, schedule_id, retry_at);
             env.events().publish(
-                (symbol_short!("savings"), SavingsEvent::ScheduleExecuted),
-                schedule_id,
+                (symbol_short!("savings"), SavingsEvent::ScheduleRetry),
+                (schedule_id, retry_at, schedule.consecutive_failures),
             );
         }
+        Self::save_schedule(env, &schedule);
+    }
 
-        env.storage()
-            .instance()
-            .set(&symbol_short!("SAV_SCH"), &schedules);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("GOALS"), &goals);
+    /// Pulls a schedule's amount from its owner through the owner's token
+    /// allowance. A no-op while no savings token is configured.
+    fn pull_schedule_funds(
+        env: &Env,
+        schedule: &SavingsSchedule,
+    ) -> Result<(), ScheduleFailureReason> {
+        Self::pull_owner_funds(env, &schedule.owner, schedule.amount)
+    }
 
-        executed
+    /// Pulls `amount` of the savings token from `owner` using the allowance
+    /// granted to this contract. A no-op when no token is configured.
+    fn pull_owner_funds(
+        env: &Env,
+        owner: &Address,
+        amount: i128,
+    ) -> Result<(), ScheduleFailureReason> {
+        let token = match Self::savings_token(env) {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        let client = TokenClient::new(env, &token);
+        let contract = env.current_contract_address();
+        if client.allowance(owner, &contract) < amount {
+            return Err(ScheduleFailureReason::InsufficientAllowance);
+        }
+        if client.balance(owner) < amount {
+            return Err(ScheduleFailureReason::InsufficientBalance);
+        }
+        match client.try_transfer_from(&contract, owner, &contract, &amount) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(ScheduleFailureReason::TransferFailed),
+        }
     }
 
-    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// When the schedule is next visited by the executor.
+    fn due_at(schedule: &SavingsSchedule) -> u64 {
+        schedule.retry_at.unwrap_or(schedule.next_due)
+    }
 
+    pub fn get_savings_schedules(env: Env, owner: Address) -> Vec<SavingsSchedule> {
         let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
+        for schedule_id in Self::owner_schedule_ids(&env, &owner).iter() {
+            if let Some(schedule) = Self::load_schedule(&env, schedule_id) {
                 result.push_back(schedule);
             }
         }
@@ -1788,12 +4521,7 @@ impl SavingsGoalContract {
     }
 
     pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
-        let schedules: Map<u32, SavingsSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("SAV_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-        schedules.get(schedule_id)
+        Self::load_schedule(&env, schedule_id)
     }
 }
 
@@ -4,10 +4,8 @@ use super::*;
 use soroban_sdk::testutils::storage::Instance as _;
 use soroban_sdk::IntoVal;
 use soroban_sdk::{
-    testutils::{Address as AddressTrait, Events as _, Ledger, LedgerInfo},
-    Address, Env, String, Symbol, TryFromVal,
     testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
-    Address, Env, IntoVal, String, Symbol, TryFromVal, Vec as SorobanVec,
+    Address, Env, String, Symbol, TryFromVal, Vec as SorobanVec,
 };
 
 use testutils::set_ledger_time;
@@ -60,7 +58,7 @@ fn test_create_goal_allows_past_target_date() {
 // ============================================================================
 // init() idempotency and NEXT_ID behavior
 //
-// init() bootstraps storage (NEXT_ID and GOALS) only when keys are missing.
+// init() bootstraps storage (NEXT_ID) only when the key is missing.
 // In production or integration, init() may be called more than once (e.g. by
 // different entrypoints or upgrade paths). These tests lock in that:
 // - A second init() must not remove or alter existing goals.
@@ -655,7 +653,7 @@ fn test_execute_due_savings_schedules() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &0);
 
     set_ledger_time(&env, 1, 3500);
-    let executed = client.execute_due_savings_schedules();
+    let executed = client.execute_due_savings_schedules(&0).executed;
 
     assert_eq!(executed.len(), 1);
     assert_eq!(executed.get(0).unwrap(), schedule_id);
@@ -679,7 +677,7 @@ fn test_execute_recurring_savings_schedule() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &86400);
 
     set_ledger_time(&env, 1, 3500);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0);
 
     let schedule = client.get_savings_schedule(&schedule_id).unwrap();
     assert!(schedule.active);
@@ -704,7 +702,7 @@ fn test_execute_missed_savings_schedules() {
     let schedule_id = client.create_savings_schedule(&owner, &goal_id, &500, &3000, &86400);
 
     set_ledger_time(&env, 1, 3000 + 86400 * 3 + 100);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0);
 
     let schedule = client.get_savings_schedule(&schedule_id).unwrap();
     assert_eq!(schedule.missed_count, 3);
@@ -726,7 +724,7 @@ fn test_savings_schedule_goal_completion() {
     client.create_savings_schedule(&owner, &goal_id, &1000, &3000, &0);
 
     set_ledger_time(&env, 1, 3500);
-    client.execute_due_savings_schedules();
+    client.execute_due_savings_schedules(&0);
 
     let goal = client.get_goal(&goal_id).unwrap();
     assert_eq!(goal.current_amount, 1000);
@@ -798,7 +796,7 @@ fn test_lock_goal_unauthorized_panics() {
     client.unlock_goal(&user, &id);
 
     let res = client.try_lock_goal(&other, &id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
 }
 
 #[test]
@@ -819,7 +817,7 @@ fn test_unlock_goal_unauthorized_panics() {
     );
 
     let res = client.try_unlock_goal(&other, &id);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
 }
 
 #[test]
@@ -883,7 +881,7 @@ fn test_lock_nonexistent_goal_panics() {
     env.mock_all_auths();
 
     let res = client.try_lock_goal(&user, &99);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(SavingsGoalError::GoalNotFound)));
 }
 
 #[test]
@@ -1307,6 +1305,10 @@ fn test_instance_ttl_refreshed_on_add_to_goal() {
 
 /// Verify data persists across repeated operations spanning multiple
 /// ledger advancements, proving TTL is continuously renewed.
+///
+/// Goals live in their own persistent entries, so the instance bump does
+/// not cover them: a write only renews the goal it touches. Each goal is
+/// written at least once per `max_entry_ttl` window here.
 #[test]
 fn test_savings_data_persists_across_ledger_advancements() {
     let env = Env::default();
@@ -1351,6 +1353,7 @@ fn test_savings_data_persists_across_ledger_advancements() {
     });
 
     client.add_to_goal(&user, &id1, &3000);
+    client.add_to_goal(&user, &id2, &4000);
 
     // Phase 3: Advance to seq 1,020,000 (TTL = 8,400 < 17,280)
     env.ledger().set(LedgerInfo {
@@ -1365,7 +1368,7 @@ fn test_savings_data_persists_across_ledger_advancements() {
     });
 
     // Add more funds to second goal
-    client.add_to_goal(&user, &id2, &10000);
+    client.add_to_goal(&user, &id2, &6000);
 
     // All goals should be accessible with correct data
     let goal1 = client.get_goal(&id1);
@@ -1575,7 +1578,11 @@ fn test_get_goals_rejects_invalid_cursor() {
     setup_goals(&env, &client, &owner, 4);
 
     let res = client.try_get_goals(&owner, &999_999, &2);
-    assert!(res.is_err(), "non-zero cursor must exist for this owner");
+    assert_eq!(
+        res.err(),
+        Some(Ok(SavingsGoalError::InvalidCursor)),
+        "non-zero cursor must exist for this owner"
+    );
 }
 
 #[test]
@@ -1594,7 +1601,11 @@ fn test_get_goals_rejects_cursor_from_another_owner() {
     let owner_b_first_page = client.get_goals(&owner_b, &0, &1);
     let foreign_cursor = owner_b_first_page.items.get(0).unwrap().id;
     let res = client.try_get_goals(&owner_a, &foreign_cursor, &2);
-    assert!(res.is_err(), "cursor must be bound to the requested owner");
+    assert_eq!(
+        res.err(),
+        Some(Ok(SavingsGoalError::InvalidCursor)),
+        "cursor must be bound to the requested owner"
+    );
 }
 
 #[test]
@@ -1877,16 +1888,6 @@ fn test_get_all_goals_filters_by_owner() {
     }
 }
 
-    // Verify owner_a's goals do not appear in owner_b's goals and vice versa
-    for goal_a_id in goal_a_ids {
-        for goal in goals_b.iter() {
-            assert_ne!(
-                goal.id, goal_a_id,
-                "Owner B's goal list should not contain owner A's goals"
-            );
-        }
-    }
-
     #[test]
     fn test_lock_goal_idempotent_already_locked() {
         let env = Env::default();
@@ -2348,8 +2349,7 @@ fn test_import_snapshot_max_u32_schema_version_rejected() {
 /// Nonce replay protection prevents an attacker from replaying a captured
 /// import transaction. Each successful import must consume the nonce.
 #[test]
-#[should_panic]
-fn test_import_snapshot_nonce_replay_panics() {
+fn test_import_snapshot_nonce_replay_rejected() {
     let env = Env::default();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, SavingsGoalContract);
@@ -2365,8 +2365,11 @@ fn test_import_snapshot_nonce_replay_panics() {
     let ok = client.import_snapshot(&owner, &0, &snapshot);
     assert!(ok);
 
-    // Second import with the same nonce 0 — must panic (nonce mismatch).
-    client.import_snapshot(&owner, &0, &snapshot);
+    // Second import with the same nonce 0 — rejected (nonce mismatch).
+    assert_eq!(
+        client.try_import_snapshot(&owner, &0, &snapshot),
+        Err(Ok(SavingsGoalError::InvalidNonce))
+    );
 }
 
 /// Nonce wrong value: supplying an incorrect nonce must panic before any
@@ -2376,8 +2379,7 @@ fn test_import_snapshot_nonce_replay_panics() {
 /// The nonce check is the first guard in import_snapshot. An incorrect nonce
 /// must abort the call immediately, leaving state unchanged.
 #[test]
-#[should_panic]
-fn test_import_snapshot_wrong_nonce_panics() {
+fn test_import_snapshot_wrong_nonce_rejected() {
     let env = Env::default();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, SavingsGoalContract);
@@ -2388,8 +2390,11 @@ fn test_import_snapshot_wrong_nonce_panics() {
     client.create_goal(&owner, &String::from_str(&env, "Goal"), &1000, &2000000000);
 
     let snapshot = client.export_snapshot(&owner);
-    // Nonce is 0 but we supply 42 — must panic.
-    client.import_snapshot(&owner, &42, &snapshot);
+    // Nonce is 0 but we supply 42 — rejected.
+    assert_eq!(
+        client.try_import_snapshot(&owner, &42, &snapshot),
+        Err(Ok(SavingsGoalError::InvalidNonce))
+    );
 }
 
 /// Sequential imports: nonce increments correctly across multiple successful
@@ -2413,19 +2418,21 @@ fn test_import_snapshot_sequential_nonce_increments() {
 
     // Import 2: nonce 1 → nonce becomes 2
     assert!(client.import_snapshot(&owner, &1, &snapshot));
-    assert_eq!(client.get_nonce(&owner), 2, "nonce must be 2 after second import");
+    assert_eq!(
+        client.get_nonce(&owner),
+        2,
+        "nonce must be 2 after second import"
+    );
 }
 
-/// Ownership remap: importing a snapshot whose goals are owned by a different
-/// address must succeed. The import caller authorizes the operation; goal
-/// ownership inside the snapshot is preserved as-is.
+/// Ownership: importing a snapshot whose goals are owned by a different
+/// address is rejected. An import only ever replaces the caller's own goals.
 ///
 /// # Security note
-/// import_snapshot does not re-assign goal ownership. The caller is
-/// responsible for ensuring the snapshot content is trusted. This test
-/// documents and locks in that behavior.
+/// import_snapshot does not re-assign goal ownership, and it must not let one
+/// address overwrite another's goals.
 #[test]
-fn test_import_snapshot_preserves_original_goal_owner() {
+fn test_import_snapshot_rejects_goals_of_another_owner() {
     let env = Env::default();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, SavingsGoalContract);
@@ -2443,46 +2450,122 @@ fn test_import_snapshot_preserves_original_goal_owner() {
 
     // Export as original_owner, then import as a different caller (importer).
     let snapshot = client.export_snapshot(&original_owner);
-    let ok = client.import_snapshot(&importer, &0, &snapshot);
-    assert!(ok, "import by a different caller must succeed");
-
-    // Goal ownership must remain with original_owner, not importer.
-    let goal = client.get_goal(&1).expect("goal must exist after import");
     assert_eq!(
-        goal.owner, original_owner,
-        "goal owner must be preserved from snapshot, not remapped to importer"
+        client.try_import_snapshot(&importer, &0, &snapshot),
+        Err(Ok(SavingsGoalError::Unauthorized))
     );
+
+    let goal = client.get_goal(&1).expect("goal must be untouched");
+    assert_eq!(goal.owner, original_owner);
+    assert_eq!(client.get_nonce(&importer), 0);
 }
 
-/// Multi-owner snapshot: a snapshot containing goals from multiple owners
-/// must restore all goals with their original owners intact.
+/// Multi-owner: each owner exports and imports only their own goals, and an
+/// import leaves every other owner's goals in place.
 #[test]
-fn test_import_snapshot_multi_owner_goals_preserved() {
+fn test_import_snapshot_leaves_other_owners_goals() {
     let env = Env::default();
     env.mock_all_auths();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
     let owner_a = Address::generate(&env);
     let owner_b = Address::generate(&env);
-    let admin = Address::generate(&env);
 
     client.init();
-    let id_a = client.create_goal(&owner_a, &String::from_str(&env, "A Goal"), &3000, &2000000000);
-    let id_b = client.create_goal(&owner_b, &String::from_str(&env, "B Goal"), &6000, &2000000000);
+    let id_a = client.create_goal(
+        &owner_a,
+        &String::from_str(&env, "A Goal"),
+        &3000,
+        &2000000000,
+    );
+    let id_b = client.create_goal(
+        &owner_b,
+        &String::from_str(&env, "B Goal"),
+        &6000,
+        &2000000000,
+    );
+
+    let snapshot = client.export_snapshot(&owner_a);
+    assert_eq!(
+        snapshot.goals.len(),
+        1,
+        "snapshot holds only the caller's goals"
+    );
+
+    client.create_goal(
+        &owner_a,
+        &String::from_str(&env, "A Later"),
+        &1000,
+        &2000000000,
+    );
+    assert!(client.import_snapshot(&owner_a, &0, &snapshot));
+
+    assert_eq!(client.get_goal(&id_a).unwrap().owner, owner_a);
+    assert!(client.get_goal(&3).is_none(), "A's later goal is replaced");
+    let goal_b = client
+        .get_goal(&id_b)
+        .expect("goal B must survive A's import");
+    assert_eq!(goal_b.owner, owner_b);
+    assert_eq!(client.get_goals(&owner_b, &0, &10).count, 1);
+    assert_eq!(client.get_storage_stats().active_goals, 2);
+
+    // A cannot claim B's goal ID through a crafted snapshot.
+    let mut goals = SorobanVec::new(&env);
+    let mut stolen = goal_b.clone();
+    stolen.owner = owner_a.clone();
+    goals.push_back(stolen);
+    let forged = GoalsExportSnapshot {
+        schema_version: 1,
+        checksum: ((1u64 + 3 + id_b as u64 + 6000) * 31),
+        next_id: 3,
+        goals,
+    };
+    assert_eq!(
+        client.try_import_snapshot(&owner_a, &1, &forged),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+}
+
+/// Dropped goals release their schedules, due-index entries and inflow rules.
+#[test]
+fn test_import_snapshot_releases_dropped_goal_schedules_and_rules() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    set_ledger_time(&env, 1, 1_000);
+    let kept = client.create_goal(&owner, &String::from_str(&env, "Keep"), &5000, &2000000000);
+    let snapshot = client.export_snapshot(&owner);
 
-    // Admin exports the full snapshot (all goals regardless of owner).
-    let snapshot = client.export_snapshot(&admin);
-    assert_eq!(snapshot.goals.len(), 2, "snapshot must contain both goals");
+    let dropped = client.create_goal(&owner, &String::from_str(&env, "Drop"), &5000, &2000000000);
+    let kept_schedule = client.create_savings_schedule(&owner, &kept, &100, &2_000, &0);
+    let dropped_schedule = client.create_savings_schedule(&owner, &dropped, &100, &2_000, &0);
+    client.set_inflow_rule(&owner, &dropped, &InflowRule::RoundUp(10));
 
-    // Re-import as admin.
-    let ok = client.import_snapshot(&admin, &0, &snapshot);
-    assert!(ok);
+    assert!(client.import_snapshot(&owner, &0, &snapshot));
 
-    let goal_a = client.get_goal(&id_a).expect("goal A must survive import");
-    let goal_b = client.get_goal(&id_b).expect("goal B must survive import");
+    assert!(client.get_goal(&dropped).is_none());
+    assert!(
+        !client
+            .get_savings_schedule(&dropped_schedule)
+            .unwrap()
+            .active
+    );
+    assert!(client.get_savings_schedule(&kept_schedule).unwrap().active);
+    assert_eq!(client.get_inflow_rule(&dropped), None);
+    assert_eq!(client.get_inflow_rules(&owner).len(), 0);
 
-    assert_eq!(goal_a.owner, owner_a, "goal A owner must be preserved");
-    assert_eq!(goal_b.owner, owner_b, "goal B owner must be preserved");
+    set_ledger_time(&env, 2, 3_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(
+        report.executed,
+        SorobanVec::from_array(&env, [kept_schedule])
+    );
+    assert_eq!(report.failed.len(), 0);
+    assert_eq!(client.get_goal(&kept).unwrap().current_amount, 100);
 }
 
 /// Import overwrites existing state: goals present before import that are not
@@ -2778,7 +2861,7 @@ fn test_savings_schedule_drift_and_missed_intervals() {
     let current_time = next_due + interval * 2 + 100;
     set_ledger_time(&env, 1, current_time);
     
-    let executed_ids = client.execute_due_savings_schedules();
+    let executed_ids = client.execute_due_savings_schedules(&0).executed;
     assert_eq!(executed_ids.len(), 1);
     assert_eq!(executed_ids.get(0).unwrap(), schedule_id);
 
@@ -2817,12 +2900,12 @@ fn test_savings_schedule_exact_timestamp_execution() {
 
     // 1. Test at next_due - 1 (should NOT execute)
     set_ledger_time(&env, 1, next_due - 1);
-    let executed_ids = client.execute_due_savings_schedules();
+    let executed_ids = client.execute_due_savings_schedules(&0).executed;
     assert_eq!(executed_ids.len(), 0, "Schedule should not execute before next_due");
 
     // 2. Test at next_due (should execute)
     set_ledger_time(&env, 1, next_due);
-    let executed_ids = client.execute_due_savings_schedules();
+    let executed_ids = client.execute_due_savings_schedules(&0).executed;
     assert_eq!(executed_ids.len(), 1, "Schedule should execute exactly at next_due");
     assert_eq!(executed_ids.get(0).unwrap(), schedule_id);
 
@@ -2846,7 +2929,7 @@ fn test_add_tags_to_goal_unauthorized() {
     tags.push_back(String::from_str(&env, "urgent"));
 
     let res = client.try_add_tags_to_goal(&other, &goal_id, &tags);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
 }
 
 #[test]
@@ -2865,7 +2948,7 @@ fn test_remove_tags_from_goal_unauthorized() {
     client.add_tags_to_goal(&user, &goal_id, &tags);
 
     let res = client.try_remove_tags_from_goal(&other, &goal_id, &tags);
-    assert!(res.is_err());
+    assert_eq!(res, Err(Ok(SavingsGoalError::Unauthorized)));
 }
 
 #[test]
@@ -2934,8 +3017,7 @@ fn test_remove_tags_from_goal_non_owner_auth_failure() {
 }
 
 #[test]
-#[should_panic(expected = "Tags cannot be empty")]
-fn test_add_tags_to_goal_empty_tags_panics() {
+fn test_add_tags_to_goal_empty_tags_rejected() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -2945,12 +3027,14 @@ fn test_add_tags_to_goal_empty_tags_panics() {
     env.mock_all_auths();
     let goal_id = client.create_goal(&user, &String::from_str(&env, "Empty"), &1000, &2000000000);
     let tags = SorobanVec::new(&env);
-    client.add_tags_to_goal(&user, &goal_id, &tags);
+    assert_eq!(
+        client.try_add_tags_to_goal(&user, &goal_id, &tags),
+        Err(Ok(SavingsGoalError::InvalidTags))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Tag must be between 1 and 32 characters")]
-fn test_add_tags_to_goal_invalid_tag_length_panics() {
+fn test_add_tags_to_goal_invalid_tag_length_rejected() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -2965,12 +3049,14 @@ fn test_add_tags_to_goal_invalid_tag_length_panics() {
         &env,
         "this-tag-is-definitely-longer-than-thirty-two-chars",
     ));
-    client.add_tags_to_goal(&user, &goal_id, &tags);
+    assert_eq!(
+        client.try_add_tags_to_goal(&user, &goal_id, &tags),
+        Err(Ok(SavingsGoalError::InvalidTags))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Tag must be between 1 and 32 characters")]
-fn test_add_tags_to_goal_empty_string_tag_panics() {
+fn test_add_tags_to_goal_empty_string_tag_rejected() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -2987,12 +3073,14 @@ fn test_add_tags_to_goal_empty_string_tag_panics() {
 
     let mut tags = SorobanVec::new(&env);
     tags.push_back(String::from_str(&env, ""));
-    client.add_tags_to_goal(&user, &goal_id, &tags);
+    assert_eq!(
+        client.try_add_tags_to_goal(&user, &goal_id, &tags),
+        Err(Ok(SavingsGoalError::InvalidTags))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Goal not found")]
-fn test_add_tags_to_goal_nonexistent_goal_panics() {
+fn test_add_tags_to_goal_nonexistent_goal_rejected() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -3002,12 +3090,14 @@ fn test_add_tags_to_goal_nonexistent_goal_panics() {
     env.mock_all_auths();
     let mut tags = SorobanVec::new(&env);
     tags.push_back(String::from_str(&env, "urgent"));
-    client.add_tags_to_goal(&user, &999, &tags);
+    assert_eq!(
+        client.try_add_tags_to_goal(&user, &999, &tags),
+        Err(Ok(SavingsGoalError::GoalNotFound))
+    );
 }
 
 #[test]
-#[should_panic(expected = "Goal not found")]
-fn test_remove_tags_from_goal_nonexistent_goal_panics() {
+fn test_remove_tags_from_goal_nonexistent_goal_rejected() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -3017,7 +3107,10 @@ fn test_remove_tags_from_goal_nonexistent_goal_panics() {
     env.mock_all_auths();
     let mut tags = SorobanVec::new(&env);
     tags.push_back(String::from_str(&env, "urgent"));
-    client.remove_tags_from_goal(&user, &999, &tags);
+    assert_eq!(
+        client.try_remove_tags_from_goal(&user, &999, &tags),
+        Err(Ok(SavingsGoalError::GoalNotFound))
+    );
 }
 
 #[test]
@@ -3054,7 +3147,7 @@ fn test_add_and_remove_tags_to_goal_success() {
 }
 
 #[test]
-fn test_add_tags_to_goal_duplicates_allowed() {
+fn test_add_tags_to_goal_deduplicates() {
     let env = Env::default();
     let contract_id = env.register_contract(None, SavingsGoalContract);
     let client = SavingsGoalContractClient::new(&env, &contract_id);
@@ -3069,17 +3162,14 @@ fn test_add_tags_to_goal_duplicates_allowed() {
     tags.push_back(String::from_str(&env, "duplicate"));
     tags.push_back(String::from_str(&env, "duplicate"));
     client.add_tags_to_goal(&user, &goal_id, &tags);
+    client.add_tags_to_goal(&user, &goal_id, &tags);
 
     let goal = client.get_goal(&goal_id).unwrap();
-    assert_eq!(goal.tags.len(), 2);
+    assert_eq!(goal.tags.len(), 1);
     assert_eq!(
         goal.tags.get(0).unwrap(),
         String::from_str(&env, "duplicate")
     );
-    assert_eq!(
-        goal.tags.get(1).unwrap(),
-        String::from_str(&env, "duplicate")
-    );
 }
 
 #[test]
@@ -3150,3 +3240,1950 @@ fn test_tag_operations_emit_events() {
     assert!(found_tags_add, "tags_add event was not emitted");
     assert!(found_tags_rem, "tags_rem event was not emitted");
 }
+
+// ============================================================================
+// Completion policy tests
+//
+// Goals stamp `completed_at` the first time they reach their target and then
+// follow their `CompletionPolicy`:
+// - KeepOpen keeps accepting deposits (legacy behavior, default).
+// - StopSchedules deactivates schedules and rejects further deposits.
+// - RedirectOverflow forwards anything above the target to another goal.
+// - AutoRelease releases the full balance to a payout address.
+// ============================================================================
+
+#[test]
+fn test_goal_completion_sets_completed_at_once() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Rent"), &1000, &2000000000);
+    assert_eq!(client.get_goal(&goal_id).unwrap().completed_at, None);
+    assert_eq!(
+        client.get_goal(&goal_id).unwrap().completion_policy,
+        CompletionPolicy::KeepOpen
+    );
+
+    client.add_to_goal(&user, &goal_id, &1000);
+    assert_eq!(client.get_goal(&goal_id).unwrap().completed_at, Some(1_000));
+
+    // KeepOpen still accepts deposits and keeps the first completion time.
+    set_ledger_time(&env, 2, 5_000);
+    let total = client.add_to_goal(&user, &goal_id, &250);
+    assert_eq!(total, 1250);
+    assert_eq!(client.get_goal(&goal_id).unwrap().completed_at, Some(1_000));
+    assert!(client.is_goal_completed(&goal_id));
+}
+
+#[test]
+fn test_completion_policy_stop_schedules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Laptop"), &1000, &2000000000);
+    client.set_completion_policy(&user, &goal_id, &CompletionPolicy::StopSchedules);
+    let schedule_id = client.create_savings_schedule(&user, &goal_id, &100, &2_000, &86400);
+
+    client.add_to_goal(&user, &goal_id, &1000);
+    assert!(!client.get_savings_schedule(&schedule_id).unwrap().active);
+
+    let res = client.try_add_to_goal(&user, &goal_id, &1);
+    assert!(res.is_err());
+
+    set_ledger_time(&env, 2, 3_000);
+    let executed = client.execute_due_savings_schedules(&0).executed;
+    assert_eq!(executed.len(), 0);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 1000);
+}
+
+#[test]
+fn test_completion_policy_schedule_completion_stops_schedule() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Phone"), &500, &2000000000);
+    client.set_completion_policy(&user, &goal_id, &CompletionPolicy::StopSchedules);
+    let schedule_id = client.create_savings_schedule(&user, &goal_id, &500, &2_000, &100);
+
+    set_ledger_time(&env, 2, 2_000);
+    client.execute_due_savings_schedules(&0);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.current_amount, 500);
+    assert_eq!(goal.completed_at, Some(2_000));
+    assert!(!client.get_savings_schedule(&schedule_id).unwrap().active);
+
+    set_ledger_time(&env, 3, 2_100);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 0);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 500);
+}
+
+#[test]
+fn test_completion_policy_redirect_overflow() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let school = client.create_goal(&user, &String::from_str(&env, "School"), &1000, &2000000000);
+    let emergency =
+        client.create_goal(&user, &String::from_str(&env, "Emergency"), &5000, &2000000000);
+    client.set_completion_policy(&user, &school, &CompletionPolicy::RedirectOverflow(emergency));
+
+    let total = client.add_to_goal(&user, &school, &1300);
+    assert_eq!(total, 1000);
+    assert_eq!(client.get_goal(&emergency).unwrap().current_amount, 300);
+
+    // Later deposits into the completed goal flow straight to the target.
+    client.add_to_goal(&user, &school, &200);
+    assert_eq!(client.get_goal(&school).unwrap().current_amount, 1000);
+    assert_eq!(client.get_goal(&emergency).unwrap().current_amount, 500);
+}
+
+#[test]
+fn test_completion_policy_auto_release() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let payout = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Fees"), &1000, &2000000000);
+    client.set_completion_policy(&user, &goal_id, &CompletionPolicy::AutoRelease(payout.clone()));
+
+    let remaining = client.add_to_goal(&user, &goal_id, &1200);
+    assert_eq!(remaining, 0);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert!(goal.completed_at.is_some());
+    assert!(client.is_goal_completed(&goal_id));
+
+    let released = env.events().all().iter().any(|event| {
+        let topics = event.1;
+        if topics.len() < 2 {
+            return false;
+        }
+        let kind = SavingsEvent::try_from_val(&env, &topics.get(1).unwrap());
+        matches!(kind, Ok(SavingsEvent::FundsReleased))
+    });
+    assert!(released, "FundsReleased event was not emitted");
+
+    let res = client.try_add_to_goal(&user, &goal_id, &10);
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_set_completion_policy_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Mine"), &1000, &2000000000);
+    let other_goal = client.create_goal(&other, &String::from_str(&env, "Theirs"), &1000, &2000000000);
+
+    let res = client.try_set_completion_policy(
+        &user,
+        &goal_id,
+        &CompletionPolicy::RedirectOverflow(goal_id),
+    );
+    assert!(res.is_err());
+
+    let res = client.try_set_completion_policy(
+        &user,
+        &goal_id,
+        &CompletionPolicy::RedirectOverflow(other_goal),
+    );
+    assert!(res.is_err());
+
+    let res = client.try_set_completion_policy(&other, &goal_id, &CompletionPolicy::StopSchedules);
+    assert!(res.is_err());
+}
+
+// ============================================================================
+// Goal archival tests
+//
+// Completed or emptied goals can be archived into per-goal persistent entries,
+// listed with `get_archived_goals`, restored and cleaned up by their owner.
+// ============================================================================
+
+#[test]
+fn test_archive_and_restore_completed_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let keep = client.create_goal(&user, &String::from_str(&env, "Keep"), &1000, &2000000000);
+    let done = client.create_goal(&user, &String::from_str(&env, "Done"), &500, &2000000000);
+    client.add_to_goal(&user, &done, &500);
+
+    set_ledger_time(&env, 2, 2_000);
+    assert!(client.archive_goal(&user, &done));
+    assert!(client.get_goal(&done).is_none());
+    assert_eq!(client.get_goals(&user, &0, &10).count, 1);
+
+    let archived = client.get_archived_goal(&done).unwrap();
+    assert_eq!(archived.current_amount, 500);
+    assert_eq!(archived.archived_at, 2_000);
+    assert_eq!(archived.completed_at, Some(1_000));
+
+    let stats = client.get_storage_stats();
+    assert_eq!(stats.active_goals, 1);
+    assert_eq!(stats.archived_goals, 1);
+    assert_eq!(stats.total_archived_amount, 500);
+
+    assert!(client.restore_goal(&user, &done));
+    assert!(client.get_archived_goal(&done).is_none());
+    let page = client.get_goals(&user, &0, &10);
+    assert_eq!(page.count, 2);
+    assert_eq!(page.items.get(0).unwrap().id, keep);
+    assert_eq!(page.items.get(1).unwrap().id, done);
+    assert_eq!(client.get_goal(&done).unwrap().current_amount, 500);
+
+    let stats = client.get_storage_stats();
+    assert_eq!(stats.active_goals, 2);
+    assert_eq!(stats.archived_goals, 0);
+    assert_eq!(stats.total_archived_amount, 0);
+}
+
+#[test]
+fn test_archive_goal_rejects_in_progress_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Half"), &1000, &2000000000);
+    client.add_to_goal(&user, &goal_id, &400);
+
+    let res = client.try_archive_goal(&user, &goal_id);
+    assert!(res.is_err());
+    assert!(client.get_goal(&goal_id).is_some());
+
+    // An emptied goal can be archived.
+    let empty = client.create_goal(&user, &String::from_str(&env, "Empty"), &1000, &2000000000);
+    assert!(client.archive_goal(&user, &empty));
+}
+
+#[test]
+fn test_archive_and_restore_goal_non_owner_fails() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Mine"), &1000, &2000000000);
+
+    assert!(client.try_archive_goal(&other, &goal_id).is_err());
+    client.archive_goal(&user, &goal_id);
+    assert!(client.try_restore_goal(&other, &goal_id).is_err());
+    assert!(client.get_archived_goal(&goal_id).is_some());
+}
+
+#[test]
+fn test_archive_goal_deactivates_schedules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&user, &String::from_str(&env, "Sched"), &1000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&user, &goal_id, &100, &2_000, &86400);
+
+    client.archive_goal(&user, &goal_id);
+    assert!(!client.get_savings_schedule(&schedule_id).unwrap().active);
+}
+
+#[test]
+fn test_get_archived_goals_pagination() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    for _ in 0..5 {
+        let id = client.create_goal(&user, &String::from_str(&env, "Old"), &1000, &2000000000);
+        client.archive_goal(&user, &id);
+    }
+    let theirs = client.create_goal(&other, &String::from_str(&env, "Theirs"), &1000, &2000000000);
+    client.archive_goal(&other, &theirs);
+
+    let page1 = client.get_archived_goals(&user, &0, &2);
+    assert_eq!(page1.count, 2);
+    assert_eq!(page1.next_cursor, page1.items.get(1).unwrap().id);
+
+    let page2 = client.get_archived_goals(&user, &page1.next_cursor, &2);
+    assert_eq!(page2.count, 2);
+
+    let page3 = client.get_archived_goals(&user, &page2.next_cursor, &2);
+    assert_eq!(page3.count, 1);
+    assert_eq!(page3.next_cursor, 0);
+
+    let others = client.get_archived_goals(&other, &0, &10);
+    assert_eq!(others.count, 1);
+    assert_eq!(others.items.get(0).unwrap().id, theirs);
+}
+
+#[test]
+fn test_cleanup_archived_goals_is_owner_scoped() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let a = client.create_goal(&user, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&other, &String::from_str(&env, "B"), &1000, &2000000000);
+    client.archive_goal(&user, &a);
+    client.archive_goal(&other, &b);
+
+    set_ledger_time(&env, 2, 5_000);
+    assert_eq!(client.cleanup_archived_goals(&user, &5_000), 1);
+    assert!(client.get_archived_goal(&a).is_none());
+    assert!(client.get_archived_goal(&b).is_some());
+    assert_eq!(client.get_archived_goals(&user, &0, &10).count, 0);
+    assert_eq!(client.get_storage_stats().archived_goals, 1);
+}
+
+// ============================================================================
+// Per-entry storage and legacy migration tests
+// ============================================================================
+
+/// Writes goals and one schedule in the pre-migration instance layout:
+/// goal 1 (500/1000) and goal 2 (1000/1000, reached) for `owner`, goal 3 for
+/// `other`, and schedule 1 funding goal 1.
+fn seed_legacy_layout(env: &Env, contract_id: &Address, owner: &Address, other: &Address) {
+    env.as_contract(contract_id, || {
+        let legacy_goal = |id: u32, who: &Address, current: i128| LegacySavingsGoal {
+            id,
+            owner: who.clone(),
+            name: String::from_str(env, "Legacy"),
+            target_amount: 1000,
+            current_amount: current,
+            target_date: 2000000000,
+            locked: true,
+            unlock_date: None,
+            tags: SorobanVec::new(env),
+        };
+        let mut goals: Map<u32, LegacySavingsGoal> = Map::new(env);
+        goals.set(1, legacy_goal(1, owner, 500));
+        goals.set(2, legacy_goal(2, owner, 1000));
+        goals.set(3, legacy_goal(3, other, 0));
+
+        let mut owner_ids: Map<Address, SorobanVec<u32>> = Map::new(env);
+        let mut ids = SorobanVec::new(env);
+        ids.push_back(1);
+        ids.push_back(2);
+        owner_ids.set(owner.clone(), ids);
+        let mut ids = SorobanVec::new(env);
+        ids.push_back(3);
+        owner_ids.set(other.clone(), ids);
+
+        let mut schedules: Map<u32, LegacySavingsSchedule> = Map::new(env);
+        schedules.set(
+            1,
+            LegacySavingsSchedule {
+                id: 1,
+                owner: owner.clone(),
+                goal_id: 1,
+                amount: 100,
+                next_due: 5_000,
+                interval: 0,
+                recurring: false,
+                active: true,
+                created_at: 1_000,
+                last_executed: None,
+                missed_count: 0,
+            },
+        );
+
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("GOALS"), &goals);
+        storage.set(&symbol_short!("OWN_GOAL"), &owner_ids);
+        storage.set(&symbol_short!("SAV_SCH"), &schedules);
+        storage.set(&symbol_short!("NEXT_ID"), &3u32);
+        storage.set(&symbol_short!("NEXT_SSCH"), &1u32);
+    });
+}
+
+#[test]
+fn test_legacy_layout_readable_before_migration() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 2_000);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+
+    let goal = client.get_goal(&1).unwrap();
+    assert_eq!(goal.current_amount, 500);
+    assert_eq!(goal.completed_at, None);
+    assert_eq!(goal.completion_policy, CompletionPolicy::KeepOpen);
+    assert!(client.get_goal(&2).unwrap().completed_at.is_some());
+
+    let page = client.get_goals(&owner, &0, &10);
+    assert_eq!(page.count, 2);
+    assert_eq!(page.items.get(0).unwrap().id, 1);
+    assert_eq!(page.items.get(1).unwrap().id, 2);
+    assert_eq!(client.get_savings_schedules(&owner).len(), 1);
+
+    // Writes land in per-entry storage and shadow the legacy copy.
+    assert_eq!(client.add_to_goal(&owner, &1, &200), 700);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 700);
+
+    // New goals continue the legacy ID sequence.
+    let id = client.create_goal(&owner, &String::from_str(&env, "New"), &1000, &2000000000);
+    assert_eq!(id, 4);
+    let page = client.get_goals(&owner, &0, &10);
+    assert_eq!(page.count, 3);
+    assert_eq!(page.items.get(2).unwrap().id, 4);
+}
+
+#[test]
+fn test_migrate_storage_moves_legacy_entries_in_batches() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 2_000);
+    client.set_upgrade_admin(&admin, &admin);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+
+    // 3 goals + 1 schedule, two entries per call.
+    assert_eq!(client.migrate_storage(&admin, &2), 2);
+    assert_eq!(client.migrate_storage(&admin, &2), 0);
+    assert_eq!(client.migrate_storage(&admin, &2), 0);
+
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        assert!(!storage.has(&symbol_short!("GOALS")));
+        assert!(!storage.has(&symbol_short!("OWN_GOAL")));
+        assert!(!storage.has(&symbol_short!("SAV_SCH")));
+        assert!(env.storage().persistent().has(&DataKey::Goal(3)));
+        assert!(env.storage().persistent().has(&DataKey::Schedule(1)));
+    });
+
+    let page = client.get_goals(&owner, &0, &10);
+    assert_eq!(page.count, 2);
+    assert_eq!(page.items.get(0).unwrap().id, 1);
+    assert_eq!(page.items.get(1).unwrap().id, 2);
+    assert_eq!(client.get_goals(&other, &0, &10).count, 1);
+    assert_eq!(client.get_savings_schedules(&owner).get(0).unwrap().goal_id, 1);
+    assert_eq!(client.get_storage_stats().active_goals, 3);
+
+    set_ledger_time(&env, 2, 6_000);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 1);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 600);
+}
+
+#[test]
+fn test_execute_runs_legacy_schedules_before_migration() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 2_000);
+    client.set_upgrade_admin(&admin, &admin);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+
+    // Not due yet: the schedule stays in the legacy map.
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 0);
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().instance().has(&symbol_short!("SAV_SCH")));
+    });
+
+    set_ledger_time(&env, 2, 6_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed, SorobanVec::from_array(&env, [1u32]));
+    assert!(!report.has_more);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 600);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&symbol_short!("SAV_SCH")));
+        assert!(env.storage().persistent().has(&DataKey::Schedule(1)));
+    });
+    assert_eq!(client.get_savings_schedules(&owner).len(), 1);
+
+    // Migration moves the goals and does not run the schedule again.
+    assert_eq!(client.migrate_storage(&admin, &0), 0);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 0);
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 600);
+}
+
+#[test]
+fn test_migrate_storage_keeps_entries_written_since_upgrade() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 2_000);
+    client.set_upgrade_admin(&admin, &admin);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+
+    client.add_to_goal(&owner, &1, &250);
+    client.cancel_savings_schedule(&owner, &1);
+    assert_eq!(client.migrate_storage(&admin, &0), 0);
+
+    assert_eq!(client.get_goal(&1).unwrap().current_amount, 750);
+    assert!(!client.get_savings_schedule(&1).unwrap().active);
+}
+
+#[test]
+fn test_archive_legacy_goal_does_not_resurface() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 2_000);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+
+    client.archive_goal(&owner, &2);
+    assert!(client.get_goal(&2).is_none());
+    let page = client.get_goals(&owner, &0, &10);
+    assert_eq!(page.count, 1);
+    assert_eq!(page.items.get(0).unwrap().id, 1);
+}
+
+#[test]
+fn test_migrate_storage_requires_upgrade_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    assert_eq!(
+        client.try_migrate_storage(&stranger, &10),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+}
+
+// ============================================================================
+// Bounded schedule execution tests
+// ============================================================================
+
+#[test]
+fn test_execute_due_respects_max_items_and_resumes() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Batch"), &100_000, &2000000000);
+    for _ in 0..5 {
+        client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &0);
+    }
+
+    set_ledger_time(&env, 2, 3_500);
+    let first = client.execute_due_savings_schedules(&2);
+    assert_eq!(first.executed.len(), 2);
+    assert!(first.has_more);
+    let second = client.execute_due_savings_schedules(&2);
+    assert_eq!(second.executed.len(), 2);
+    assert!(second.has_more);
+    let last = client.execute_due_savings_schedules(&2);
+    assert_eq!(last.executed.len(), 1);
+    assert!(!last.has_more);
+
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 500);
+    assert_eq!(client.execute_due_savings_schedules(&2).executed.len(), 0);
+}
+
+#[test]
+fn test_execute_due_leaves_future_schedules_untouched() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Mixed"), &100_000, &2000000000);
+    let due = client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &0);
+    let later = client.create_savings_schedule(&owner, &goal_id, &100, &3_600, &0);
+    let next_year = client.create_savings_schedule(&owner, &goal_id, &100, &31_536_000, &0);
+
+    set_ledger_time(&env, 2, 3_500);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    assert_eq!(report.executed.get(0).unwrap(), due);
+    assert!(!report.has_more);
+    assert!(client.get_savings_schedule(&later).unwrap().active);
+    assert!(client.get_savings_schedule(&next_year).unwrap().active);
+
+    set_ledger_time(&env, 3, 3_600);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    assert_eq!(report.executed.get(0).unwrap(), later);
+}
+
+#[test]
+fn test_execute_due_reports_orphaned_schedule_as_failed() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Gone"), &100_000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &86_400);
+
+    // The goal's entry disappears (e.g. its TTL ran out) under a live schedule.
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().remove(&DataKey::Goal(goal_id));
+    });
+
+    set_ledger_time(&env, 2, 3_500);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 0);
+    assert_eq!(report.failed.len(), 1);
+    let failure = report.failed.get(0).unwrap();
+    assert_eq!(failure.schedule_id, schedule_id);
+    assert_eq!(failure.reason, ScheduleFailureReason::GoalNotFound);
+    assert!(!client.get_savings_schedule(&schedule_id).unwrap().active);
+
+    // The failed schedule left the due index.
+    set_ledger_time(&env, 3, 3_000 + 86_400 * 2);
+    assert_eq!(client.execute_due_savings_schedules(&0).failed.len(), 0);
+}
+
+#[test]
+fn test_cancel_and_modify_keep_due_index_in_sync() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Sync"), &100_000, &2000000000);
+    let cancelled = client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &0);
+    let moved = client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &0);
+    client.cancel_savings_schedule(&owner, &cancelled);
+    client.modify_savings_schedule(&owner, &moved, &100, &200_000, &0);
+
+    set_ledger_time(&env, 2, 3_500);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::DueSchedules(0)));
+    });
+
+    set_ledger_time(&env, 3, 200_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    assert_eq!(report.executed.get(0).unwrap(), moved);
+}
+
+#[test]
+fn test_execute_due_catches_up_after_long_gap() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Gap"), &100_000, &2000000000);
+    client.create_savings_schedule(&owner, &goal_id, &100, &3_000, &0);
+
+    // 100 days later the schedule is found first; the empty days after it
+    // take several calls to walk past.
+    set_ledger_time(&env, 2, SCHEDULE_BUCKET_SECONDS * 100);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    assert!(report.has_more);
+
+    let mut calls = 1;
+    while client.execute_due_savings_schedules(&0).has_more {
+        calls += 1;
+    }
+    assert_eq!(calls, 100 / MAX_DUE_BUCKET_SCAN);
+    let late = client.create_savings_schedule(
+        &owner,
+        &goal_id,
+        &100,
+        &(SCHEDULE_BUCKET_SECONDS * 100 + 10),
+        &0,
+    );
+    set_ledger_time(&env, 3, SCHEDULE_BUCKET_SECONDS * 100 + 10);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.get(0).unwrap(), late);
+    assert!(!report.has_more);
+}
+
+// ============================================================================
+// Token-backed savings tests
+// ============================================================================
+
+/// Registers a contract with a savings token configured and mints `amount`
+/// to `owner`.
+fn setup_token_backed<'a>(
+    env: &'a Env,
+    owner: &Address,
+    amount: i128,
+) -> (Address, SavingsGoalContractClient<'a>, soroban_sdk::token::TokenClient<'a>) {
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+
+    client.init();
+    env.mock_all_auths();
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &token_id).mint(owner, &amount);
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_savings_token(&admin, &token_id);
+    let token = soroban_sdk::token::TokenClient::new(env, &token_id);
+    (contract_id, client, token)
+}
+
+#[test]
+fn test_token_backed_deposit_and_withdrawal_move_tokens() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Backed"), &5_000, &2000000000);
+    client.add_to_goal(&owner, &goal_id, &300);
+    assert_eq!(token.balance(&owner), 700);
+    assert_eq!(token.balance(&contract_id), 300);
+
+    client.unlock_goal(&owner, &goal_id);
+    client.withdraw_from_goal(&owner, &goal_id, &100);
+    assert_eq!(token.balance(&owner), 800);
+    assert_eq!(token.balance(&contract_id), 200);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 200);
+}
+
+#[test]
+fn test_schedule_pulls_funds_through_allowance() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+    set_ledger_time(&env, 1, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Pulled"), &5_000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &200, &3_000, &86_400);
+    token.approve(&owner, &contract_id, &1_000, &10_000);
+
+    set_ledger_time(&env, 2, 3_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    assert_eq!(report.executed.get(0).unwrap(), schedule_id);
+    assert_eq!(token.balance(&owner), 800);
+    assert_eq!(token.balance(&contract_id), 200);
+    assert_eq!(token.allowance(&owner, &contract_id), 800);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 200);
+}
+
+#[test]
+fn test_failed_pull_retries_with_backoff_then_pauses() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+    set_ledger_time(&env, 1, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Retry"), &5_000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &200, &3_000, &86_400);
+
+    // No allowance: first failure, retry after the base delay.
+    set_ledger_time(&env, 2, 3_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 0);
+    let failure = report.failed.get(0).unwrap();
+    assert_eq!(failure.reason, ScheduleFailureReason::InsufficientAllowance);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert!(schedule.active);
+    assert_eq!(schedule.consecutive_failures, 1);
+    assert_eq!(schedule.retry_at, Some(3_000 + SCHEDULE_RETRY_BASE_SECONDS));
+    assert_eq!(schedule.next_due, 3_000);
+
+    // Not retried before retry_at.
+    set_ledger_time(&env, 3, 3_000 + SCHEDULE_RETRY_BASE_SECONDS - 1);
+    assert_eq!(client.execute_due_savings_schedules(&0).failed.len(), 0);
+
+    // Second failure doubles the delay.
+    let second_at = 3_000 + SCHEDULE_RETRY_BASE_SECONDS;
+    set_ledger_time(&env, 4, second_at);
+    assert_eq!(client.execute_due_savings_schedules(&0).failed.len(), 1);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.retry_at, Some(second_at + SCHEDULE_RETRY_BASE_SECONDS * 2));
+
+    // Third consecutive failure pauses the schedule.
+    set_ledger_time(&env, 5, second_at + SCHEDULE_RETRY_BASE_SECONDS * 2);
+    assert_eq!(client.execute_due_savings_schedules(&0).failed.len(), 1);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert!(!schedule.active);
+    assert!(schedule.paused);
+    assert_eq!(schedule.consecutive_failures, MAX_SCHEDULE_FAILURES);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+
+    // Approve and resume: the next run pulls funds and clears the failures.
+    token.approve(&owner, &contract_id, &1_000, &10_000);
+    client.resume_savings_schedule(&owner, &schedule_id, &100_000);
+    set_ledger_time(&env, 6, 100_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 1);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert!(schedule.active);
+    assert_eq!(schedule.consecutive_failures, 0);
+    assert_eq!(schedule.next_due, 100_000 + 86_400);
+    assert_eq!(token.balance(&contract_id), 200);
+}
+
+#[test]
+fn test_successful_retry_keeps_schedule_cadence() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 0);
+    set_ledger_time(&env, 1, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Cadence"), &5_000, &2000000000);
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &200, &3_000, &86_400);
+    token.approve(&owner, &contract_id, &1_000, &10_000);
+
+    set_ledger_time(&env, 2, 3_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(
+        report.failed.get(0).unwrap().reason,
+        ScheduleFailureReason::InsufficientBalance
+    );
+
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address).mint(&owner, &500);
+    set_ledger_time(&env, 3, 3_000 + SCHEDULE_RETRY_BASE_SECONDS);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 1);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.retry_at, None);
+    assert_eq!(schedule.next_due, 3_000 + 86_400);
+    assert_eq!(schedule.missed_count, 0);
+}
+
+#[test]
+fn test_schedule_redirect_overflow_fails_before_pulling_funds() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+    set_ledger_time(&env, 1, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Full"), &100, &2000000000);
+    let target_id = client.create_goal(
+        &owner,
+        &String::from_str(&env, "Target"),
+        &i128::MAX,
+        &2000000000,
+    );
+    client.set_completion_policy(
+        &owner,
+        &goal_id,
+        &CompletionPolicy::RedirectOverflow(target_id),
+    );
+    env.as_contract(&contract_id, || {
+        let mut target = SavingsGoalContract::load_goal(&env, target_id).unwrap();
+        target.current_amount = i128::MAX - 10;
+        SavingsGoalContract::save_goal(&env, &target);
+    });
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &200, &3_000, &86_400);
+    token.approve(&owner, &contract_id, &1_000, &10_000);
+
+    set_ledger_time(&env, 2, 3_000);
+    let report = client.execute_due_savings_schedules(&0);
+    assert_eq!(report.executed.len(), 0);
+    let failure = report.failed.get(0).unwrap();
+    assert_eq!(failure.schedule_id, schedule_id);
+    assert_eq!(failure.reason, ScheduleFailureReason::Overflow);
+    assert_eq!(
+        token.balance(&owner),
+        1_000,
+        "no tokens pulled for a failed run"
+    );
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+    assert_eq!(
+        client.get_goal(&target_id).unwrap().current_amount,
+        i128::MAX - 10
+    );
+}
+
+#[test]
+fn test_set_savings_token_rejected_after_goals_exist() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    client.create_goal(&owner, &String::from_str(&env, "Early"), &1000, &2000000000);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    assert_eq!(
+        client.try_set_savings_token(&admin, &token_id),
+        Err(Ok(SavingsGoalError::GoalsAlreadyExist))
+    );
+}
+
+// ============================================================================
+// Tag index and tag aggregate tests
+// ============================================================================
+
+fn tag_list(env: &Env, names: &[&str]) -> SorobanVec<String> {
+    let mut tags = SorobanVec::new(env);
+    for name in names {
+        tags.push_back(String::from_str(env, name));
+    }
+    tags
+}
+
+#[test]
+fn test_get_goals_by_tag_pages_owner_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let travel = String::from_str(&env, "travel");
+    let mut tagged = SorobanVec::new(&env);
+    for i in 0..5u32 {
+        let id = client.create_goal(&owner, &String::from_str(&env, "Goal"), &1000, &2000000000);
+        if i % 2 == 0 {
+            client.add_tags_to_goal(&owner, &id, &tag_list(&env, &["travel"]));
+            tagged.push_back(id);
+        }
+    }
+    let other_goal = client.create_goal(&other, &String::from_str(&env, "Other"), &1000, &2000000000);
+    client.add_tags_to_goal(&other, &other_goal, &tag_list(&env, &["travel"]));
+
+    let first = client.get_goals_by_tag(&owner, &travel, &0, &2);
+    assert_eq!(first.count, 2);
+    assert_eq!(first.items.get(0).unwrap().id, tagged.get(0).unwrap());
+    assert_eq!(first.items.get(1).unwrap().id, tagged.get(1).unwrap());
+    assert_eq!(first.next_cursor, tagged.get(1).unwrap());
+
+    let second = client.get_goals_by_tag(&owner, &travel, &first.next_cursor, &2);
+    assert_eq!(second.count, 1);
+    assert_eq!(second.items.get(0).unwrap().id, tagged.get(2).unwrap());
+    assert_eq!(second.next_cursor, 0);
+
+    let unknown = client.get_goals_by_tag(&owner, &String::from_str(&env, "none"), &0, &10);
+    assert_eq!(unknown.count, 0);
+    assert_eq!(client.get_goals_by_tag(&other, &travel, &0, &10).count, 1);
+}
+
+#[test]
+fn test_get_tag_totals_sums_saved_and_target() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&owner, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&owner, &String::from_str(&env, "B"), &3000, &2000000000);
+    let c = client.create_goal(&owner, &String::from_str(&env, "C"), &500, &2000000000);
+    client.add_tags_to_goal(&owner, &a, &tag_list(&env, &["family"]));
+    client.add_tags_to_goal(&owner, &b, &tag_list(&env, &["family", "house"]));
+    client.add_tags_to_goal(&owner, &c, &tag_list(&env, &["house"]));
+    client.add_to_goal(&owner, &a, &200);
+    client.add_to_goal(&owner, &b, &700);
+    client.add_to_goal(&owner, &c, &100);
+
+    let family = client.get_tag_totals(&owner, &String::from_str(&env, "family"));
+    assert_eq!(family.goal_count, 2);
+    assert_eq!(family.total_saved, 900);
+    assert_eq!(family.total_target, 4000);
+
+    let house = client.get_tag_totals(&owner, &String::from_str(&env, "house"));
+    assert_eq!(house.goal_count, 2);
+    assert_eq!(house.total_saved, 800);
+    assert_eq!(house.total_target, 3500);
+
+    let none = client.get_tag_totals(&owner, &String::from_str(&env, "none"));
+    assert_eq!(none.goal_count, 0);
+    assert_eq!(none.total_saved, 0);
+}
+
+#[test]
+fn test_owner_tag_registry_tracks_tags_in_use() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&owner, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&owner, &String::from_str(&env, "B"), &1000, &2000000000);
+    client.add_tags_to_goal(&owner, &a, &tag_list(&env, &["car", "kids"]));
+    client.add_tags_to_goal(&owner, &b, &tag_list(&env, &["kids"]));
+    assert_eq!(client.get_owner_tags(&owner), tag_list(&env, &["car", "kids"]));
+
+    // "kids" stays registered while goal b still carries it.
+    client.remove_tags_from_goal(&owner, &a, &tag_list(&env, &["kids"]));
+    assert_eq!(client.get_owner_tags(&owner), tag_list(&env, &["car", "kids"]));
+    assert_eq!(
+        client.get_goals_by_tag(&owner, &String::from_str(&env, "kids"), &0, &10).count,
+        1
+    );
+
+    client.remove_tags_from_goal(&owner, &b, &tag_list(&env, &["kids"]));
+    client.remove_tags_from_goal(&owner, &a, &tag_list(&env, &["car"]));
+    assert_eq!(client.get_owner_tags(&owner).len(), 0);
+}
+
+#[test]
+fn test_archive_and_restore_update_tag_index() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Trip"), &1000, &2000000000);
+    client.add_tags_to_goal(&owner, &goal_id, &tag_list(&env, &["travel"]));
+    let travel = String::from_str(&env, "travel");
+
+    client.archive_goal(&owner, &goal_id);
+    assert_eq!(client.get_goals_by_tag(&owner, &travel, &0, &10).count, 0);
+    assert_eq!(client.get_tag_totals(&owner, &travel).goal_count, 0);
+    assert_eq!(client.get_owner_tags(&owner).len(), 0);
+
+    client.restore_goal(&owner, &goal_id);
+    let page = client.get_goals_by_tag(&owner, &travel, &0, &10);
+    assert_eq!(page.count, 1);
+    assert_eq!(page.items.get(0).unwrap().id, goal_id);
+    assert_eq!(client.get_owner_tags(&owner), tag_list(&env, &["travel"]));
+}
+
+#[test]
+fn test_migrate_storage_dedups_and_indexes_legacy_tags() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    client.set_upgrade_admin(&admin, &admin);
+    seed_legacy_layout(&env, &contract_id, &owner, &other);
+    env.as_contract(&contract_id, || {
+        let mut goals: Map<u32, LegacySavingsGoal> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("GOALS"))
+            .unwrap();
+        let mut goal = goals.get(1).unwrap();
+        goal.tags = tag_list(&env, &["rent", "rent", "fees"]);
+        goals.set(1, goal);
+        env.storage().instance().set(&symbol_short!("GOALS"), &goals);
+    });
+
+    client.migrate_storage(&admin, &10);
+
+    assert_eq!(client.get_goal(&1).unwrap().tags, tag_list(&env, &["rent", "fees"]));
+    assert_eq!(client.get_owner_tags(&owner), tag_list(&env, &["rent", "fees"]));
+    let rent = client.get_tag_totals(&owner, &String::from_str(&env, "rent"));
+    assert_eq!(rent.goal_count, 1);
+    assert_eq!(rent.total_saved, 500);
+}
+
+// ============================================================================
+// Goal-to-goal transfer and rebalancing tests
+// ============================================================================
+
+#[test]
+fn test_transfer_between_goals_moves_funds_atomically() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+
+    let vacation = client.create_goal(&owner, &String::from_str(&env, "Vacation"), &1000, &2000000000);
+    let emergency = client.create_goal(&owner, &String::from_str(&env, "Emergency"), &5000, &2000000000);
+    client.add_to_goal(&owner, &vacation, &400);
+    client.unlock_goal(&owner, &vacation);
+
+    let remaining = client.transfer_between_goals(&owner, &vacation, &emergency, &100);
+    assert_eq!(remaining, 300);
+    assert_eq!(client.get_goal(&vacation).unwrap().current_amount, 300);
+    assert_eq!(client.get_goal(&emergency).unwrap().current_amount, 100);
+    // Funds stay in the contract.
+    assert_eq!(token.balance(&contract_id), 400);
+
+    let log = client.get_audit_log(&0, &100);
+    let last = log.get(log.len() - 1).unwrap();
+    assert_eq!(last.operation, symbol_short!("transfer"));
+    assert!(last.success);
+}
+
+#[test]
+fn test_transfer_between_goals_allows_locked_destination() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let from = client.create_goal(&owner, &String::from_str(&env, "From"), &1000, &2000000000);
+    let to = client.create_goal(&owner, &String::from_str(&env, "To"), &1000, &2000000000);
+    client.add_to_goal(&owner, &from, &500);
+    client.unlock_goal(&owner, &from);
+
+    // The destination is locked (the default), which only restricts withdrawals.
+    client.transfer_between_goals(&owner, &from, &to, &500);
+    assert_eq!(client.get_goal(&to).unwrap().current_amount, 500);
+    assert_eq!(client.get_goal(&from).unwrap().current_amount, 0);
+}
+
+#[test]
+fn test_transfer_between_goals_respects_lock_and_time_lock() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let from = client.create_goal(&owner, &String::from_str(&env, "From"), &1000, &2000000000);
+    let to = client.create_goal(&owner, &String::from_str(&env, "To"), &1000, &2000000000);
+    client.add_to_goal(&owner, &from, &500);
+
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &to, &100),
+        Err(Ok(SavingsGoalError::GoalLocked))
+    );
+
+    client.unlock_goal(&owner, &from);
+    client.set_time_lock(&owner, &from, &5_000);
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &to, &100),
+        Err(Ok(SavingsGoalError::GoalLocked))
+    );
+
+    set_ledger_time(&env, 2, 5_000);
+    client.transfer_between_goals(&owner, &from, &to, &100);
+    assert_eq!(client.get_goal(&to).unwrap().current_amount, 100);
+}
+
+#[test]
+fn test_transfer_between_goals_rejects_invalid_requests() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let from = client.create_goal(&owner, &String::from_str(&env, "From"), &1000, &2000000000);
+    let done = client.create_goal(&owner, &String::from_str(&env, "Done"), &100, &2000000000);
+    let foreign = client.create_goal(&other, &String::from_str(&env, "Foreign"), &1000, &2000000000);
+    client.add_to_goal(&owner, &from, &500);
+    client.unlock_goal(&owner, &from);
+    client.set_completion_policy(&owner, &done, &CompletionPolicy::StopSchedules);
+    client.add_to_goal(&owner, &done, &100);
+
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &from, &100),
+        Err(Ok(SavingsGoalError::DuplicateGoal))
+    );
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &done, &0),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &99, &100),
+        Err(Ok(SavingsGoalError::GoalNotFound))
+    );
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &foreign, &100),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &done, &100),
+        Err(Ok(SavingsGoalError::GoalCompleted))
+    );
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &done, &from, &100),
+        Err(Ok(SavingsGoalError::GoalLocked))
+    );
+
+    let new_goal = client.create_goal(&owner, &String::from_str(&env, "New"), &1000, &2000000000);
+    assert_eq!(
+        client.try_transfer_between_goals(&owner, &from, &new_goal, &501),
+        Err(Ok(SavingsGoalError::InsufficientBalance))
+    );
+    assert_eq!(client.get_goal(&from).unwrap().current_amount, 500);
+    assert_eq!(client.get_goal(&foreign).unwrap().current_amount, 0);
+}
+
+#[test]
+fn test_transfer_completing_destination_applies_its_policy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let from = client.create_goal(&owner, &String::from_str(&env, "From"), &1000, &2000000000);
+    let to = client.create_goal(&owner, &String::from_str(&env, "To"), &300, &2000000000);
+    client.add_to_goal(&owner, &from, &500);
+    client.unlock_goal(&owner, &from);
+    // Overflow above the destination's target is sent straight back.
+    client.set_completion_policy(&owner, &to, &CompletionPolicy::RedirectOverflow(from));
+
+    client.transfer_between_goals(&owner, &from, &to, &500);
+    let to_goal = client.get_goal(&to).unwrap();
+    assert_eq!(to_goal.current_amount, 300);
+    assert!(to_goal.completed_at.is_some());
+    assert_eq!(client.get_goal(&from).unwrap().current_amount, 200);
+}
+
+fn goal_weights(env: &Env, items: &[(u32, u32)]) -> SorobanVec<GoalWeight> {
+    let mut weights = SorobanVec::new(env);
+    for (goal_id, weight) in items {
+        weights.push_back(GoalWeight {
+            goal_id: *goal_id,
+            weight: *weight,
+        });
+    }
+    weights
+}
+
+#[test]
+fn test_rebalance_goals_redistributes_by_weight() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&owner, &String::from_str(&env, "A"), &5000, &2000000000);
+    let b = client.create_goal(&owner, &String::from_str(&env, "B"), &5000, &2000000000);
+    let c = client.create_goal(&owner, &String::from_str(&env, "C"), &5000, &2000000000);
+    client.add_to_goal(&owner, &a, &1000);
+    client.add_to_goal(&owner, &b, &1);
+    client.unlock_goal(&owner, &a);
+    client.unlock_goal(&owner, &b);
+    client.unlock_goal(&owner, &c);
+
+    // 1001 split 1:1:1 -> 333 each, remainder 2 to the first weighted goal.
+    let total = client.rebalance_goals(&owner, &goal_weights(&env, &[(a, 1), (b, 1), (c, 1)]));
+    assert_eq!(total, 1001);
+    assert_eq!(client.get_goal(&a).unwrap().current_amount, 335);
+    assert_eq!(client.get_goal(&b).unwrap().current_amount, 333);
+    assert_eq!(client.get_goal(&c).unwrap().current_amount, 333);
+
+    // A zero weight empties a goal.
+    client.rebalance_goals(&owner, &goal_weights(&env, &[(a, 0), (b, 3), (c, 1)]));
+    assert_eq!(client.get_goal(&a).unwrap().current_amount, 0);
+    assert_eq!(client.get_goal(&b).unwrap().current_amount, 751);
+    assert_eq!(client.get_goal(&c).unwrap().current_amount, 250);
+
+    let log = client.get_audit_log(&0, &100);
+    let last = log.get(log.len() - 1).unwrap();
+    assert_eq!(last.operation, symbol_short!("rebalance"));
+    assert!(last.success);
+}
+
+#[test]
+fn test_rebalance_goals_enforces_lock_rules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let locked = client.create_goal(&owner, &String::from_str(&env, "Locked"), &5000, &2000000000);
+    let open = client.create_goal(&owner, &String::from_str(&env, "Open"), &5000, &2000000000);
+    client.add_to_goal(&owner, &locked, &100);
+    client.add_to_goal(&owner, &open, &300);
+    client.unlock_goal(&owner, &open);
+
+    // The locked goal would lose funds.
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &goal_weights(&env, &[(locked, 0), (open, 1)])) ,
+        Err(Ok(SavingsGoalError::GoalLocked))
+    );
+    assert_eq!(client.get_goal(&locked).unwrap().current_amount, 100);
+    assert_eq!(client.get_goal(&open).unwrap().current_amount, 300);
+
+    // Gaining funds is fine while locked.
+    client.rebalance_goals(&owner, &goal_weights(&env, &[(locked, 1), (open, 1)]));
+    assert_eq!(client.get_goal(&locked).unwrap().current_amount, 200);
+    assert_eq!(client.get_goal(&open).unwrap().current_amount, 200);
+}
+
+#[test]
+fn test_rebalance_goals_rejects_invalid_requests() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&owner, &String::from_str(&env, "A"), &5000, &2000000000);
+    let b = client.create_goal(&owner, &String::from_str(&env, "B"), &5000, &2000000000);
+    let foreign = client.create_goal(&other, &String::from_str(&env, "Foreign"), &5000, &2000000000);
+
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &SorobanVec::new(&env)),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &goal_weights(&env, &[(a, 0), (b, 0)])) ,
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &goal_weights(&env, &[(a, 1), (a, 1)])) ,
+        Err(Ok(SavingsGoalError::DuplicateGoal))
+    );
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &goal_weights(&env, &[(a, 1), (99, 1)])) ,
+        Err(Ok(SavingsGoalError::GoalNotFound))
+    );
+    assert_eq!(
+        client.try_rebalance_goals(&owner, &goal_weights(&env, &[(a, 1), (foreign, 1)])) ,
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+}
+
+// ============================================================================
+// Beneficiary and inactivity claim tests
+// ============================================================================
+
+#[test]
+fn test_set_goal_beneficiary_validates_input() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let heir = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Family"), &1000, &2000000000);
+
+    assert_eq!(
+        client.try_set_goal_beneficiary(&owner, &goal_id, &owner, &MIN_INACTIVITY_PERIOD) ,
+        Err(Ok(SavingsGoalError::InvalidBeneficiary))
+    );
+    assert_eq!(
+        client.try_set_goal_beneficiary(&owner, &goal_id, &heir, &(MIN_INACTIVITY_PERIOD - 1)) ,
+        Err(Ok(SavingsGoalError::InvalidBeneficiary))
+    );
+    assert_eq!(
+        client.try_set_goal_beneficiary(&heir, &goal_id, &heir, &MIN_INACTIVITY_PERIOD) ,
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+
+    client.set_goal_beneficiary(&owner, &goal_id, &heir, &MIN_INACTIVITY_PERIOD);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.beneficiary, Some(heir.clone()));
+    assert_eq!(goal.inactivity_period, MIN_INACTIVITY_PERIOD);
+
+    client.clear_goal_beneficiary(&owner, &goal_id);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.beneficiary, None);
+    assert_eq!(goal.inactivity_period, 0);
+}
+
+#[test]
+fn test_owner_activity_resets_inactivity_timer() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let heir = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Family"), &1000, &2000000000);
+    client.set_goal_beneficiary(&owner, &goal_id, &heir, &MIN_INACTIVITY_PERIOD);
+    assert_eq!(client.get_last_activity(&owner), Some(1_000));
+    assert!(!client.is_goal_claimable(&goal_id));
+
+    // Any authorized call resets the timer.
+    set_ledger_time(&env, 2, 1_000 + MIN_INACTIVITY_PERIOD - 1);
+    client.add_to_goal(&owner, &goal_id, &100);
+    set_ledger_time(&env, 3, 1_000 + MIN_INACTIVITY_PERIOD);
+    assert!(!client.is_goal_claimable(&goal_id));
+    assert_eq!(
+        client.try_claim_goal(&heir, &goal_id),
+        Err(Ok(SavingsGoalError::NotClaimable))
+    );
+
+    // So does an explicit heartbeat.
+    let beat = 1_000 + 2 * MIN_INACTIVITY_PERIOD - 2;
+    set_ledger_time(&env, 4, beat);
+    assert_eq!(client.heartbeat(&owner), beat);
+    assert_eq!(client.get_last_activity(&owner), Some(beat));
+    set_ledger_time(&env, 5, beat + MIN_INACTIVITY_PERIOD - 1);
+    assert!(!client.is_goal_claimable(&goal_id));
+
+    set_ledger_time(&env, 6, beat + MIN_INACTIVITY_PERIOD);
+    assert!(client.is_goal_claimable(&goal_id));
+}
+
+#[test]
+fn test_claim_goal_transfers_ownership_to_beneficiary() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let heir = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Family"), &1000, &2000000000);
+    let other_id = client.create_goal(&owner, &String::from_str(&env, "Other"), &1000, &2000000000);
+    client.add_to_goal(&owner, &goal_id, &400);
+    client.add_tags_to_goal(&owner, &goal_id, &tag_list(&env, &["home"]));
+    client.set_completion_policy(&owner, &goal_id, &CompletionPolicy::RedirectOverflow(other_id));
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &50, &5_000, &86_400);
+    client.set_goal_beneficiary(&owner, &goal_id, &heir, &MIN_INACTIVITY_PERIOD);
+
+    set_ledger_time(&env, 2, 1_000 + MIN_INACTIVITY_PERIOD);
+    assert_eq!(
+        client.try_claim_goal(&stranger, &goal_id),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+    client.claim_goal(&heir, &goal_id);
+
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.owner, heir);
+    assert_eq!(goal.current_amount, 400);
+    assert_eq!(goal.beneficiary, None);
+    assert_eq!(goal.completion_policy, CompletionPolicy::KeepOpen);
+    assert!(!client.get_savings_schedule(&schedule_id).unwrap().active);
+
+    let heir_goals = client.get_goals(&heir, &0, &10);
+    assert_eq!(heir_goals.count, 1);
+    assert_eq!(heir_goals.items.get(0).unwrap().id, goal_id);
+    assert_eq!(client.get_goals(&owner, &0, &10).count, 1);
+    let home = String::from_str(&env, "home");
+    assert_eq!(client.get_goals_by_tag(&heir, &home, &0, &10).count, 1);
+    assert_eq!(client.get_goals_by_tag(&owner, &home, &0, &10).count, 0);
+
+    // The new owner manages the goal like any other.
+    client.unlock_goal(&heir, &goal_id);
+    client.withdraw_from_goal(&heir, &goal_id, &100);
+    assert_eq!(
+        client.try_claim_goal(&heir, &goal_id),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_claim_goal_funds_pays_beneficiary() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let heir = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+    set_ledger_time(&env, 1, 1_000);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Family"), &5_000, &2000000000);
+    client.add_to_goal(&owner, &goal_id, &600);
+    client.set_time_lock(&owner, &goal_id, &(1_000 + 2 * MIN_INACTIVITY_PERIOD));
+    client.set_goal_beneficiary(&owner, &goal_id, &heir, &MIN_INACTIVITY_PERIOD);
+
+    // Claimable, but the time-lock still applies.
+    set_ledger_time(&env, 2, 1_000 + MIN_INACTIVITY_PERIOD);
+    assert_eq!(
+        client.try_claim_goal_funds(&heir, &goal_id),
+        Err(Ok(SavingsGoalError::GoalLocked))
+    );
+
+    // The goal is still manually locked; that does not block the beneficiary.
+    set_ledger_time(&env, 3, 1_000 + 2 * MIN_INACTIVITY_PERIOD);
+    assert!(client.get_goal(&goal_id).unwrap().locked);
+    assert_eq!(client.claim_goal_funds(&heir, &goal_id), 600);
+    assert_eq!(token.balance(&heir), 600);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.owner, owner);
+    assert_eq!(goal.current_amount, 0);
+    assert_eq!(
+        client.try_claim_goal_funds(&heir, &goal_id),
+        Err(Ok(SavingsGoalError::InsufficientBalance))
+    );
+}
+
+#[test]
+fn test_beneficiary_survives_archive_and_restore() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let heir = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 1_000);
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Family"), &1000, &2000000000);
+    client.set_goal_beneficiary(&owner, &goal_id, &heir, &MIN_INACTIVITY_PERIOD);
+    client.archive_goal(&owner, &goal_id);
+
+    // Archived goals cannot be claimed.
+    set_ledger_time(&env, 2, 1_000 + MIN_INACTIVITY_PERIOD);
+    assert!(!client.is_goal_claimable(&goal_id));
+    assert_eq!(
+        client.try_claim_goal(&heir, &goal_id),
+        Err(Ok(SavingsGoalError::GoalNotFound))
+    );
+
+    client.restore_goal(&owner, &goal_id);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.beneficiary, Some(heir));
+    assert_eq!(goal.inactivity_period, MIN_INACTIVITY_PERIOD);
+}
+
+// ============================================================================
+// Goal template tests
+// ============================================================================
+
+#[test]
+fn test_create_goal_from_template_creates_goal_and_schedule() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    set_ledger_time(&env, 1, 10_000);
+    let term = 120 * 86_400;
+    let template_id = client.create_goal_template(
+        &owner,
+        &String::from_str(&env, "School fees"),
+        &3_000,
+        &term,
+        &tag_list(&env, &["school", "kids", "school"]),
+        &250,
+        &(30 * 86_400),
+    );
+    let template = client.get_goal_template(&template_id).unwrap();
+    assert_eq!(template.tags, tag_list(&env, &["school", "kids"]));
+
+    let goal_id = client.create_goal_from_template(&owner, &template_id, &None);
+    let goal = client.get_goal(&goal_id).unwrap();
+    assert_eq!(goal.owner, owner);
+    assert_eq!(goal.name, String::from_str(&env, "School fees #1"));
+    assert_eq!(goal.target_amount, 3_000);
+    assert_eq!(goal.target_date, 10_000 + term);
+    assert_eq!(goal.tags, tag_list(&env, &["school", "kids"]));
+    assert_eq!(
+        client.get_goals_by_tag(&owner, &String::from_str(&env, "kids"), &0, &10).count,
+        1
+    );
+
+    let schedules = client.get_savings_schedules(&owner);
+    assert_eq!(schedules.len(), 1);
+    let schedule = schedules.get(0).unwrap();
+    assert_eq!(schedule.goal_id, goal_id);
+    assert_eq!(schedule.amount, 250);
+    assert_eq!(schedule.interval, 30 * 86_400);
+    assert_eq!(schedule.next_due, 10_000 + 30 * 86_400);
+    assert!(schedule.active);
+
+    set_ledger_time(&env, 2, 10_000 + 30 * 86_400);
+    assert_eq!(client.execute_due_savings_schedules(&0).executed.len(), 1);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 250);
+}
+
+#[test]
+fn test_create_goal_from_template_overrides_target_without_schedule() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let template_id = client.create_goal_template(
+        &owner,
+        &String::from_str(&env, "Rent"),
+        &12_000,
+        &(365 * 86_400),
+        &SorobanVec::new(&env),
+        &0,
+        &0,
+    );
+
+    let first = client.create_goal_from_template(&owner, &template_id, &None);
+    let second = client.create_goal_from_template(&owner, &template_id, &Some(15_000));
+    assert_ne!(first, second);
+    assert_eq!(client.get_goal(&first).unwrap().name, String::from_str(&env, "Rent #1"));
+    assert_eq!(client.get_goal(&second).unwrap().name, String::from_str(&env, "Rent #2"));
+    assert_eq!(client.get_goal_template(&template_id).unwrap().uses, 2);
+    assert_eq!(client.get_goal(&first).unwrap().target_amount, 12_000);
+    assert_eq!(client.get_goal(&second).unwrap().target_amount, 15_000);
+    assert_eq!(client.get_savings_schedules(&owner).len(), 0);
+    assert_eq!(
+        client.try_create_goal_from_template(&owner, &template_id, &Some(0)) ,
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_goal_template_validation_and_ownership() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let name = String::from_str(&env, "Template");
+    let no_tags = SorobanVec::new(&env);
+
+    client.init();
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_create_goal_template(&owner, &name, &0, &86_400, &no_tags, &0, &0) ,
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_goal_template(&owner, &name, &1_000, &0, &no_tags, &0, &0) ,
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_create_goal_template(&owner, &name, &1_000, &86_400, &no_tags, &100, &0) ,
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    let long_name = String::from_bytes(&env, &[b'x'; MAX_TEMPLATE_NAME_LEN as usize + 1]);
+    assert_eq!(
+        client.try_create_goal_template(&owner, &long_name, &1_000, &86_400, &no_tags, &0, &0),
+        Err(Ok(SavingsGoalError::InvalidName))
+    );
+    let bad_tags = tag_list(&env, &[""]);
+    assert_eq!(
+        client.try_create_goal_template(&owner, &name, &1_000, &86_400, &bad_tags, &0, &0),
+        Err(Ok(SavingsGoalError::InvalidTags))
+    );
+
+    let template_id = client.create_goal_template(&owner, &name, &1_000, &86_400, &no_tags, &0, &0);
+    assert_eq!(
+        client.try_create_goal_from_template(&other, &template_id, &None) ,
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_delete_goal_template(&other, &template_id),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_create_goal_from_template(&owner, &99, &None),
+        Err(Ok(SavingsGoalError::TemplateNotFound))
+    );
+
+    let goal_id = client.create_goal_from_template(&owner, &template_id, &None);
+    client.delete_goal_template(&owner, &template_id);
+    assert!(client.get_goal_template(&template_id).is_none());
+    assert_eq!(client.get_goal_templates(&owner).len(), 0);
+    assert_eq!(
+        client.try_create_goal_from_template(&owner, &template_id, &None) ,
+        Err(Ok(SavingsGoalError::TemplateNotFound))
+    );
+    // Goals created from a deleted template are unaffected.
+    assert!(client.get_goal(&goal_id).is_some());
+}
+
+#[test]
+fn test_goal_templates_limited_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Template");
+    let no_tags = SorobanVec::new(&env);
+
+    client.init();
+    env.mock_all_auths();
+    for _ in 0..MAX_TEMPLATES_PER_OWNER {
+        client.create_goal_template(&owner, &name, &1_000, &86_400, &no_tags, &0, &0);
+    }
+    assert_eq!(client.get_goal_templates(&owner).len(), MAX_TEMPLATES_PER_OWNER);
+    assert_eq!(
+        client.try_create_goal_template(&owner, &name, &1_000, &86_400, &no_tags, &0, &0) ,
+        Err(Ok(SavingsGoalError::TemplateLimitReached))
+    );
+
+    let first = client.get_goal_templates(&owner).get(0).unwrap().id;
+    client.delete_goal_template(&owner, &first);
+    client.create_goal_template(&owner, &name, &1_000, &86_400, &no_tags, &0, &0);
+}
+
+// ============================================================================
+// Inflow rule tests
+// ============================================================================
+
+fn setup_inflow_reporter(client: &SavingsGoalContractClient, env: &Env) -> Address {
+    let admin = Address::generate(env);
+    let reporter = Address::generate(env);
+    client.set_upgrade_admin(&admin, &admin);
+    client.set_inflow_reporter(&admin, &reporter);
+    reporter
+}
+
+#[test]
+fn test_set_inflow_rule_validates_rules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let a = client.create_goal(&owner, &String::from_str(&env, "A"), &1000, &2000000000);
+    let b = client.create_goal(&owner, &String::from_str(&env, "B"), &1000, &2000000000);
+
+    assert_eq!(
+        client.try_set_inflow_rule(&owner, &a, &InflowRule::Percentage(0)),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_inflow_rule(&owner, &a, &InflowRule::Percentage(10_001)),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_inflow_rule(&owner, &a, &InflowRule::RoundUp(0)),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_inflow_rule(&other, &a, &InflowRule::Percentage(500)),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+
+    client.set_inflow_rule(&owner, &a, &InflowRule::Percentage(6_000));
+    // Percentage rules may not add up to more than 100%.
+    assert_eq!(
+        client.try_set_inflow_rule(&owner, &b, &InflowRule::Percentage(4_001)),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    client.set_inflow_rule(&owner, &b, &InflowRule::Percentage(4_000));
+    // Replacing a rule does not count it twice.
+    client.set_inflow_rule(&owner, &a, &InflowRule::Percentage(5_000));
+    client.set_inflow_rule(&owner, &b, &InflowRule::RoundUp(10));
+
+    let rules = client.get_inflow_rules(&owner);
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules.get(0).unwrap().rule, InflowRule::Percentage(5_000));
+    assert_eq!(rules.get(1).unwrap().rule, InflowRule::RoundUp(10));
+
+    client.clear_inflow_rule(&owner, &b);
+    assert_eq!(client.get_inflow_rule(&b), None);
+    assert_eq!(client.get_inflow_rules(&owner).len(), 1);
+}
+
+#[test]
+fn test_inflow_rules_limited_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    for _ in 0..MAX_INFLOW_RULES_PER_OWNER {
+        let id = client.create_goal(&owner, &String::from_str(&env, "Goal"), &1000, &2000000000);
+        client.set_inflow_rule(&owner, &id, &InflowRule::RoundUp(5));
+    }
+    let extra = client.create_goal(&owner, &String::from_str(&env, "Extra"), &1000, &2000000000);
+    assert_eq!(
+        client.try_set_inflow_rule(&owner, &extra, &InflowRule::RoundUp(5)) ,
+        Err(Ok(SavingsGoalError::InflowRuleLimitReached))
+    );
+}
+
+#[test]
+fn test_record_inflow_applies_percentage_and_round_up_rules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let reporter = setup_inflow_reporter(&client, &env);
+    let pct = client.create_goal(&owner, &String::from_str(&env, "Pct"), &10_000, &2000000000);
+    let round = client.create_goal(&owner, &String::from_str(&env, "Round"), &10_000, &2000000000);
+    let plain = client.create_goal(&owner, &String::from_str(&env, "Plain"), &10_000, &2000000000);
+    client.set_inflow_rule(&owner, &pct, &InflowRule::Percentage(500));
+    client.set_inflow_rule(&owner, &round, &InflowRule::RoundUp(10));
+
+    // 5% of 1_234 = 61; rounding 1_234 up to 1_240 saves 6.
+    assert_eq!(client.record_inflow(&owner, &1_234), 67);
+    assert_eq!(env.auths().first().unwrap().0, reporter);
+    assert_eq!(client.get_goal(&pct).unwrap().current_amount, 61);
+    assert_eq!(client.get_goal(&round).unwrap().current_amount, 6);
+    assert_eq!(client.get_goal(&plain).unwrap().current_amount, 0);
+
+    // An exact multiple rounds up by nothing.
+    assert_eq!(client.record_inflow(&owner, &2_000), 100);
+    assert_eq!(client.get_goal(&round).unwrap().current_amount, 6);
+
+    assert_eq!(
+        client.try_record_inflow(&owner, &0),
+        Err(Ok(SavingsGoalError::InvalidAmount))
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(client.record_inflow(&stranger, &1_000), 0);
+}
+
+#[test]
+fn test_record_inflow_requires_configured_reporter() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Pct"), &10_000, &2000000000);
+    client.set_inflow_rule(&owner, &goal_id, &InflowRule::Percentage(500));
+
+    assert_eq!(
+        client.try_record_inflow(&owner, &1_000),
+        Err(Ok(SavingsGoalError::Unauthorized))
+    );
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+}
+
+#[test]
+fn test_record_inflow_skips_closed_and_archived_goals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.init();
+    env.mock_all_auths();
+    setup_inflow_reporter(&client, &env);
+    let done = client.create_goal(&owner, &String::from_str(&env, "Done"), &100, &2000000000);
+    let open = client.create_goal(&owner, &String::from_str(&env, "Open"), &10_000, &2000000000);
+    let empty = client.create_goal(&owner, &String::from_str(&env, "Empty"), &10_000, &2000000000);
+    client.set_inflow_rule(&owner, &done, &InflowRule::Percentage(1_000));
+    client.set_inflow_rule(&owner, &open, &InflowRule::Percentage(1_000));
+    client.set_inflow_rule(&owner, &empty, &InflowRule::Percentage(1_000));
+    client.set_completion_policy(&owner, &done, &CompletionPolicy::StopSchedules);
+    client.add_to_goal(&owner, &done, &100);
+    client.archive_goal(&owner, &empty);
+    assert_eq!(client.get_inflow_rule(&empty), None);
+
+    assert_eq!(client.record_inflow(&owner, &1_000), 100);
+    assert_eq!(client.get_goal(&done).unwrap().current_amount, 100);
+    assert_eq!(client.get_goal(&open).unwrap().current_amount, 100);
+}
+
+#[test]
+fn test_token_backed_inflow_pulls_from_owner_allowance() {
+    let env = Env::default();
+    let owner = Address::generate(&env);
+    let (contract_id, client, token) = setup_token_backed(&env, &owner, 1_000);
+    let reporter = Address::generate(&env);
+    let admin = client.get_upgrade_admin_public().unwrap();
+    client.set_inflow_reporter(&admin, &reporter);
+
+    let goal_id = client.create_goal(&owner, &String::from_str(&env, "Pct"), &10_000, &2000000000);
+    client.set_inflow_rule(&owner, &goal_id, &InflowRule::Percentage(1_000));
+
+    // No allowance yet: the inflow is reported but nothing is applied.
+    assert_eq!(client.record_inflow(&owner, &500), 0);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+
+    token.approve(&owner, &contract_id, &1_000, &10_000);
+    assert_eq!(client.record_inflow(&owner, &500), 50);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 50);
+    assert_eq!(token.balance(&owner), 950);
+    assert_eq!(token.balance(&contract_id), 50);
+}
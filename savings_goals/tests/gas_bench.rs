@@ -114,7 +114,7 @@ fn bench_execute_due_savings_schedules() {
         max_entry_ttl: 100_000,
     });
 
-    let (cpu, mem, executed) = measure(&env, || client.execute_due_savings_schedules());
+    let (cpu, mem, executed) = measure(&env, || client.execute_due_savings_schedules(&50).executed);
     assert_eq!(executed.len(), 50);
 
     println!(
@@ -145,3 +145,145 @@ fn bench_create_savings_schedule() {
         cpu, mem
     );
 }
+
+/// Registers a contract holding `other_goals` goals spread over ten other
+/// owners, plus one goal (with one schedule) for the returned owner.
+fn populated_contract(
+    env: &Env,
+    other_goals: u32,
+) -> (SavingsGoalContractClient<'_>, Address, u32) {
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(env, &contract_id);
+    client.init();
+
+    let name = String::from_str(env, "Filler");
+    let mut others = Vec::new(env);
+    for _ in 0..10 {
+        others.push_back(<Address as AddressTrait>::generate(env));
+    }
+    for i in 0..other_goals {
+        let other = others.get(i % 10).unwrap();
+        client.create_goal(&other, &name, &1_000_000i128, &1_800_000u64);
+    }
+
+    let owner = <Address as AddressTrait>::generate(env);
+    let goal_id = client.create_goal(
+        &owner,
+        &String::from_str(env, "Measured"),
+        &1_000_000i128,
+        &1_800_000u64,
+    );
+    client.create_savings_schedule(&owner, &goal_id, &100i128, &1_700_000_010u64, &86400u64);
+    (client, owner, goal_id)
+}
+
+/// Per-call cost must not grow with the number of goals other users hold.
+///
+/// The test host keeps ledger entries in a sorted vector, so every storage
+/// access gets slightly dearer as the total entry count grows, whatever the
+/// contract does. Each sample is therefore paired with a control call making
+/// one fixed-size storage access; the measured call may grow by at most
+/// `accesses` times the control's growth, where `accesses` is the number of
+/// storage entries it touches.
+/// Both sizes are past MAX_AUDIT_ENTRIES so the audit log is the same length.
+fn assert_flat(method: &str, accesses: u64, small: [(u64, u64); 2], large: [(u64, u64); 2]) {
+    let [op_small, ctrl_small] = small;
+    let [op_large, ctrl_large] = large;
+    println!(
+        r#"{{"contract":"savings_goals","method":"{}","scenario":"150_vs_300_goals","cpu_150":{},"cpu_300":{},"mem_150":{},"mem_300":{},"control_cpu_150":{},"control_cpu_300":{}}}"#,
+        method, op_small.0, op_large.0, op_small.1, op_large.1, ctrl_small.0, ctrl_large.0
+    );
+    let cpu_growth = op_large.0.saturating_sub(op_small.0);
+    let ctrl_cpu_growth = ctrl_large.0.saturating_sub(ctrl_small.0);
+    assert!(
+        cpu_growth <= accesses * ctrl_cpu_growth + op_small.0 / 20,
+        "{} cpu grew with total goal count: {} -> {} (control {} -> {})",
+        method,
+        op_small.0,
+        op_large.0,
+        ctrl_small.0,
+        ctrl_large.0
+    );
+    let mem_growth = op_large.1.saturating_sub(op_small.1);
+    let ctrl_mem_growth = ctrl_large.1.saturating_sub(ctrl_small.1);
+    assert!(
+        mem_growth <= accesses * ctrl_mem_growth + op_small.1 / 20,
+        "{} mem grew with total goal count: {} -> {} (control {} -> {})",
+        method,
+        op_small.1,
+        op_large.1,
+        ctrl_small.1,
+        ctrl_large.1
+    );
+}
+
+#[test]
+fn bench_add_to_goal_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, goal_id) = populated_contract(&env, *n);
+        // Control: a single fixed-size instance write.
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.set_pause_admin(&owner, &owner));
+        let (cpu, mem, _) = measure(&env, || client.add_to_goal(&owner, &goal_id, &100i128));
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Goal entry + instance (audit log).
+    assert_flat("add_to_goal", 2, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_get_goal_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, _owner, goal_id) = populated_contract(&env, *n);
+        // Control: a single instance read.
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, goal) = measure(&env, || client.get_goal(&goal_id));
+        assert!(goal.is_some());
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Goal entry + instance.
+    assert_flat("get_goal", 2, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_get_goals_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, _goal_id) = populated_contract(&env, *n);
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.get_version());
+        let (cpu, mem, page) = measure(&env, || client.get_goals(&owner, &0u32, &20u32));
+        assert_eq!(page.count, 1);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Owner index + one goal entry + instance.
+    assert_flat("get_goals", 3, samples[0], samples[1]);
+}
+
+#[test]
+fn bench_execute_due_savings_schedules_flat_cost() {
+    let mut samples = [[(0u64, 0u64); 2]; 2];
+    for (i, n) in [150u32, 300].iter().enumerate() {
+        let env = bench_env();
+        let (client, owner, _goal_id) = populated_contract(&env, *n);
+        env.ledger().set(LedgerInfo {
+            protocol_version: env.ledger().protocol_version(),
+            sequence_number: 2,
+            timestamp: 1_700_000_100,
+            network_id: [0; 32],
+            base_reserve: 10,
+            min_temp_entry_ttl: 1,
+            min_persistent_entry_ttl: 1,
+            max_entry_ttl: 100_000,
+        });
+        let (ctrl_cpu, ctrl_mem, _) = measure(&env, || client.set_pause_admin(&owner, &owner));
+        let (cpu, mem, executed) = measure(&env, || client.execute_due_savings_schedules(&50).executed);
+        assert_eq!(executed.len(), 1);
+        samples[i] = [(cpu, mem), (ctrl_cpu, ctrl_mem)];
+    }
+    // Old and new due buckets, schedule, goal, instance.
+    assert_flat("execute_due_savings_schedules", 5, samples[0], samples[1]);
+}
@@ -14,7 +14,7 @@
 //! - No explicit caps are imposed by the contract, but overflow/underflow will panic
 //! - batch_add_to_goals has same limitations as add_to_goal for each contribution
 
-use savings_goals::{ContributionItem, SavingsGoalContract, SavingsGoalContractClient, SavingsGoalsError};
+use savings_goals::{ContributionItem, SavingsGoalContract, SavingsGoalContractClient, SavingsGoalError};
 use soroban_sdk::testutils::{Address as AddressTrait, Ledger, LedgerInfo};
 use soroban_sdk::{Env, String, Vec};
 
@@ -142,7 +142,7 @@ fn test_add_to_goal_overflow_returns_error() {
     let result = client.try_add_to_goal(&owner, &goal_id, &overflow_amount);
     assert!(result.is_err());
     let err = result.unwrap_err();
-    assert_eq!(err, SavingsGoalsError::Overflow);
+    assert_eq!(err, Ok(SavingsGoalError::Overflow));
 }
 
 #[test]
@@ -176,10 +176,10 @@ fn test_batch_add_to_goals_overflow_returns_error() {
     });
 
     env.mock_all_auths();
-    let result = client.batch_add_to_goals(&owner, &contributions);
+    let result = client.try_batch_add_to_goals(&owner, &contributions);
     assert!(result.is_err());
     let err = result.unwrap_err();
-    assert_eq!(err, SavingsGoalsError::Overflow);
+    assert_eq!(err, Ok(SavingsGoalError::Overflow));
 }
 #[test]
 fn test_withdraw_from_goal_with_large_amount() {
@@ -333,7 +333,7 @@ fn test_batch_add_with_large_amounts() {
     });
 
     env.mock_all_auths();
-    let count = client.batch_add_to_goals(&owner, &contributions).unwrap();
+    let count = client.batch_add_to_goals(&owner, &contributions);
 
     assert_eq!(count, 3);
 